@@ -6,8 +6,11 @@ use solana_program::{
 };
 
 use crate::{
-    state::{AuditLog, OperationType, SecureVault},
+    state::{
+        AuditLog, OperationStatus, OperationType, PendingOperation, SecureVault, TimedPause,
+    },
     error::VaultError,
+    utils::Utils,
 };
 
 pub struct AuditManager;
@@ -22,8 +25,14 @@ impl AuditManager {
         amount: Option<u64>,
         target_account: Option<Pubkey>,
     ) -> AuditLog {
-        // TODO: Implementar
-        todo!()
+        let mut log = AuditLog::new(operation_id, operation_type, performer, timestamp);
+        if let Some(amount) = amount {
+            log = log.with_amount(amount);
+        }
+        if let Some(target) = target_account {
+            log = log.with_target_account(target);
+        }
+        log
     }
 
     /// Registrar operación exitosa
@@ -36,8 +45,108 @@ impl AuditManager {
         target_account: Option<Pubkey>,
         gas_used: u64,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        let timestamp = Utils::get_current_timestamp()?;
+        let mut log = Self::create_audit_log(
+            operation_id,
+            operation_type,
+            performer,
+            timestamp,
+            amount,
+            target_account,
+        );
+        log.mark_success(gas_used);
+
+        Utils::serialize_to_account(audit_account, &log)
+    }
+
+    /// Registrar operación exitosa como parte de un grupo correlacionado
+    #[allow(clippy::too_many_arguments)]
+    pub fn log_correlated_operation(
+        audit_account: &AccountInfo,
+        operation_id: u64,
+        correlation_id: u64,
+        operation_type: OperationType,
+        performer: Pubkey,
+        amount: Option<u64>,
+        target_account: Option<Pubkey>,
+        gas_used: u64,
+    ) -> Result<(), VaultError> {
+        let timestamp = Utils::get_current_timestamp()?;
+        let mut log = Self::create_audit_log(
+            operation_id,
+            operation_type,
+            performer,
+            timestamp,
+            amount,
+            target_account,
+        )
+        .with_correlation_id(correlation_id);
+        log.mark_success(gas_used);
+
+        Utils::serialize_to_account(audit_account, &log)
+    }
+
+    /// Registrar un paso de un flujo con estado (p.ej. recuperación del admin)
+    pub fn log_operation_with_status(
+        audit_account: &AccountInfo,
+        operation_id: u64,
+        operation_type: OperationType,
+        performer: Pubkey,
+        target_account: Option<Pubkey>,
+        status: OperationStatus,
+    ) -> Result<(), VaultError> {
+        let timestamp = Utils::get_current_timestamp()?;
+        let mut log = Self::create_audit_log(
+            operation_id,
+            operation_type,
+            performer,
+            timestamp,
+            None,
+            target_account,
+        )
+        .with_operation_status(status);
+        log.mark_success(0);
+
+        Utils::serialize_to_account(audit_account, &log)
+    }
+
+    /// Registrar un evento del ciclo de vida de una operación pendiente
+    pub fn log_pending_operation_event(
+        audit_account: &AccountInfo,
+        operation_id: u64,
+        operation: &PendingOperation,
+        performer: Pubkey,
+    ) -> Result<(), VaultError> {
+        let timestamp = Utils::get_current_timestamp()?;
+        let mut log = Self::create_audit_log(
+            operation_id,
+            operation.operation_type.clone(),
+            performer,
+            timestamp,
+            Some(operation.amount),
+            Some(operation.target_account),
+        )
+        .with_correlation_id(operation.id)
+        .with_operation_status(operation.status.clone());
+        log.mark_success(0);
+
+        Utils::serialize_to_account(audit_account, &log)
+    }
+
+    /// Registrar el inicio o la extensión de una pausa temporal con su motivo y expiración
+    pub fn log_timed_pause(
+        audit_account: &AccountInfo,
+        operation_id: u64,
+        performer: Pubkey,
+        pause: &TimedPause,
+    ) -> Result<(), VaultError> {
+        let timestamp = Utils::get_current_timestamp()?;
+        let mut log =
+            AuditLog::new(operation_id, OperationType::EmergencyPause, performer, timestamp)
+                .with_pause(pause.clone());
+        log.mark_success(0);
+
+        Utils::serialize_to_account(audit_account, &log)
     }
 
     /// Registrar operación fallida
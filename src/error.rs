@@ -190,6 +190,42 @@ pub enum VaultError {
 
     #[error("Invalid PDA signer")]
     InvalidPDASigner,
+
+    #[error("Recipient is not in the vault address book")]
+    RecipientNotAllowlisted,
+
+    #[error("Time lock delay shorter than required")]
+    DelayTooShort,
+
+    #[error("Manager spending quota exceeded")]
+    ManagerQuotaExceeded,
+
+    #[error("Invalid or unknown custom role")]
+    InvalidRole,
+
+    #[error("An admin recovery proposal is already active")]
+    RecoveryInProgress,
+
+    #[error("Hourly withdrawal limit exceeded")]
+    HourlyLimitExceeded,
+
+    #[error("Weekly withdrawal limit exceeded")]
+    WeeklyLimitExceeded,
+
+    #[error("Monthly withdrawal limit exceeded")]
+    MonthlyLimitExceeded,
+
+    #[error("Circuit breaker pause can only be lifted by quorum")]
+    CircuitBreakerTripped,
+
+    #[error("Vault inactivity timeout not reached")]
+    VaultNotInactive,
+
+    #[error("Timed pause cooldown has not elapsed")]
+    PauseCooldownActive,
+
+    #[error("Vault state failed consistency checks")]
+    InvalidVaultState,
 }
 
 impl From<VaultError> for ProgramError {
@@ -1,9 +1,111 @@
+//! Formato de cable de las instrucciones (versión 2):
+//!
+//! ```text
+//! [0]    versión del formato (`INSTRUCTION_VERSION`)
+//! [1]    discriminante estable de la variante (ver módulo `tag`)
+//! [2..]  campos de la variante serializados con borsh, en orden de declaración
+//! ```
+//!
+//! Los discriminantes son fijos e independientes del orden del enum: las variantes
+//! nuevas reciben un tag nuevo y las existentes nunca cambian de número. Cualquier cambio
+//! en los campos o en la lista de cuentas de una variante existente sube la versión.
+//! Versiones o tags desconocidos, o bytes sobrantes tras el payload, se rechazan con
+//! `VaultError::InvalidInstruction`.
+//!
+//! Versiones:
+//! - v1: tags 0–19, con `SecurityConfigV1`. Se sigue aceptando: una actualización de
+//!   configuración v1 conserva los campos posteriores de la configuración vigente, `Deposit`
+//!   y `ExecutePendingOperation` admiten el layout v1 (las cuentas nuevas son opcionales) y
+//!   `SignPendingOperation` se decodifica como `SignPendingOperationV1`. Solo
+//!   `CreateTimeLockOperation` se rechaza: sin system program no puede crear la PDA.
+//! - v2: `SecurityConfig` con límites en moneda de cotización, tramos, ventanas y disyuntor;
+//!   registro del depositante en `Deposit`, vault en `SignPendingOperation`, system program
+//!   en `CreateTimeLockOperation` y solicitante en `ExecutePendingOperation`.
+
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
-use crate::state::{OperationType, SecurityConfig};
+use solana_program::{msg, program_error::ProgramError, pubkey::Pubkey};
+use crate::{
+    error::VaultError,
+    state::{
+        Asset, DeadManSwitch, MultisigPolicy, OperationType, PauseReason, Permissions,
+        RecipientPolicy, SecurityConfig,
+    },
+};
+
+pub mod builders;
+
+/// Versión actual del formato de cable
+pub const INSTRUCTION_VERSION: u8 = 2;
+
+/// Primera versión del formato, aceptada por compatibilidad con clientes antiguos
+pub const INSTRUCTION_VERSION_V1: u8 = 1;
+
+/// Discriminantes estables de cada variante (nunca reutilizar ni renumerar)
+pub mod tag {
+    pub const INITIALIZE_VAULT: u8 = 0;
+    pub const DEPOSIT: u8 = 1;
+    pub const WITHDRAW: u8 = 2;
+    pub const EMERGENCY_PAUSE: u8 = 3;
+    pub const RESUME: u8 = 4;
+    pub const ADD_MANAGER: u8 = 5;
+    pub const REMOVE_MANAGER: u8 = 6;
+    pub const UPDATE_SECURITY_CONFIG: u8 = 7;
+    pub const TRANSFER_ADMIN: u8 = 8;
+    pub const CREATE_TIME_LOCK_OPERATION: u8 = 9;
+    pub const SIGN_PENDING_OPERATION: u8 = 10;
+    pub const EXECUTE_PENDING_OPERATION: u8 = 11;
+    pub const CANCEL_PENDING_OPERATION: u8 = 12;
+    pub const EMERGENCY_WITHDRAW: u8 = 13;
+    pub const GET_VAULT_INFO: u8 = 14;
+    pub const GET_AUDIT_LOGS: u8 = 15;
+    pub const DEPOSIT_TOKEN: u8 = 16;
+    pub const WITHDRAW_TOKEN: u8 = 17;
+    pub const SET_ASSET_LIMITS: u8 = 18;
+    pub const EXECUTE_BATCH: u8 = 19;
+    pub const ADD_ADDRESS_BOOK_ENTRY: u8 = 20;
+    pub const REMOVE_ADDRESS_BOOK_ENTRY: u8 = 21;
+    pub const SET_RECIPIENT_POLICY: u8 = 22;
+    pub const SET_MANAGER_QUOTA: u8 = 23;
+    pub const UPSERT_ROLE: u8 = 24;
+    pub const REMOVE_ROLE: u8 = 25;
+    pub const ASSIGN_ROLE: u8 = 26;
+    pub const REVOKE_ROLE: u8 = 27;
+    pub const SET_MULTISIG_POLICY: u8 = 28;
+    pub const CLOSE_EXPIRED_OPERATION: u8 = 29;
+    pub const REJECT_PENDING_OPERATION: u8 = 30;
+    pub const PROPOSE_ADMIN_RECOVERY: u8 = 31;
+    pub const APPROVE_ADMIN_RECOVERY: u8 = 32;
+    pub const CANCEL_ADMIN_RECOVERY: u8 = 33;
+    pub const EXECUTE_ADMIN_RECOVERY: u8 = 34;
+    pub const SET_RECOVERY_CONFIG: u8 = 35;
+    pub const PROPOSE_ADMIN: u8 = 36;
+    pub const ACCEPT_ADMIN: u8 = 37;
+    pub const CANCEL_ADMIN_PROPOSAL: u8 = 38;
+    pub const ADD_EMERGENCY_CONTACT: u8 = 39;
+    pub const REMOVE_EMERGENCY_CONTACT: u8 = 40;
+    pub const CREATE_VESTING_SCHEDULE: u8 = 41;
+    pub const CLAIM_VESTED: u8 = 42;
+    pub const CREATE_RECURRING_PAYMENT: u8 = 43;
+    pub const EXECUTE_RECURRING_PAYMENT: u8 = 44;
+    pub const CONFIGURE_COMPARTMENT: u8 = 45;
+    pub const TRANSFER_BETWEEN_COMPARTMENTS: u8 = 46;
+    pub const WITHDRAW_FROM_COMPARTMENT: u8 = 47;
+    pub const REFUND_DEPOSITOR: u8 = 48;
+    pub const WITHDRAW_DEPOSIT: u8 = 49;
+    pub const SET_DEAD_MAN_SWITCH: u8 = 50;
+    pub const CLAIM_INACTIVE_VAULT: u8 = 51;
+    pub const EXECUTE_INACTIVITY_CLAIM: u8 = 52;
+    pub const HEARTBEAT: u8 = 53;
+    pub const TIMED_PAUSE: u8 = 54;
+    pub const EXTEND_PAUSE: u8 = 55;
+    pub const REMOVE_TOKEN_ASSET: u8 = 56;
+}
+
+/// Número máximo de operaciones dentro de un `ExecuteBatch`
+pub const MAX_BATCH_OPERATIONS: usize = 8;
 
 /// Instrucciones del programa Secure Vault
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[derive(Debug)]
 pub enum VaultInstruction {
     /// Inicializar un nuevo vault
     /// Accounts:
@@ -18,8 +120,10 @@ pub enum VaultInstruction {
     /// Accounts:
     /// 0. [signer] Depositante
     /// 1. [writable] Cuenta del vault
-    /// 2. [writable] Cuenta de origen de los fondos
+    /// 2. [signer, writable] Cuenta de origen de los fondos (paga el registro si es nuevo)
     /// 3. [] System program
+    /// 4. [writable] (Opcional) Registro del depositante (PDA, se crea en el primer depósito);
+    ///    sin él el depósito no es reembolsable, y es obligatorio con `refundable_mode`
     Deposit {
         amount: u64,
     },
@@ -30,6 +134,11 @@ pub enum VaultInstruction {
     /// 1. [writable] Cuenta del vault
     /// 2. [writable] Cuenta destino
     /// 3. [writable] Cuenta de audit log
+    /// 4. [] Address book del vault (requerido si la política de destinatarios está activa)
+    /// 5. [] Opcionales, por dirección: cuenta de precio del oráculo (si hay límites en moneda
+    ///    de cotización); si el monto cae en un tramo de delay o el destinatario no está listado
+    ///    bajo `TimeLockUnlisted`, la cuenta de operación pendiente (PDA del próximo id,
+    ///    writable; el solicitante debe ser writable y la paga) y el system program
     Withdraw {
         amount: u64,
         recipient: Pubkey,
@@ -42,7 +151,8 @@ pub enum VaultInstruction {
     /// 2. [writable] Cuenta de audit log
     EmergencyPause,
 
-    /// Reanudar operaciones del vault
+    /// Reanudar operaciones del vault (una pausa del disyuntor requiere una operación
+    /// `Resume` time-locked firmada por quórum)
     /// Accounts:
     /// 0. [signer] Admin
     /// 1. [writable] Cuenta del vault
@@ -76,7 +186,17 @@ pub enum VaultInstruction {
         new_config: SecurityConfig,
     },
 
-    /// Transferir administración del vault
+    /// Actualizar configuración desde un cliente v1: solo los campos de `SecurityConfigV1`
+    /// (se codifica con el tag `UPDATE_SECURITY_CONFIG` en formato v1)
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    UpdateSecurityConfigV1 {
+        new_config: SecurityConfigV1,
+    },
+
+    /// Transferir administración del vault en un solo paso (ver `ProposeAdmin`/`AcceptAdmin`)
     /// Accounts:
     /// 0. [signer] Admin actual
     /// 1. [writable] Cuenta del vault
@@ -88,10 +208,14 @@ pub enum VaultInstruction {
 
     /// Crear operación con delay (time-locked)
     /// Accounts:
-    /// 0. [signer] Solicitante
+    /// 0. [signer, writable] Solicitante (paga la cuenta de la operación)
     /// 1. [writable] Cuenta del vault
-    /// 2. [writable] Cuenta de operación pendiente
+    /// 2. [writable] Cuenta de operación pendiente (PDA)
     /// 3. [writable] Cuenta de audit log
+    /// 4. [] System program
+    /// 5. [] Address book del vault (requerido para `Withdraw` si la política está activa)
+    /// 6. [] Opcionales, por dirección: cuenta de precio del oráculo (`Withdraw` con límites
+    ///    en moneda de cotización) o token account destino (`WithdrawToken`, `target_account`)
     CreateTimeLockOperation {
         operation_type: OperationType,
         amount: u64,
@@ -99,26 +223,42 @@ pub enum VaultInstruction {
         delay_seconds: i64,
     },
 
-    /// Firmar operación pendiente
+    /// Firmar operación pendiente (suma el peso del firmante)
     /// Accounts:
     /// 0. [signer] Firmante autorizado
-    /// 1. [writable] Cuenta de operación pendiente
-    /// 2. [writable] Cuenta de audit log
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de operación pendiente
+    /// 3. [writable] Cuenta de audit log
     SignPendingOperation {
         operation_id: u64,
     },
 
-    /// Ejecutar operación pendiente
+    /// Ejecutar operación pendiente (peso acumulado >= umbral y delay cumplido)
     /// Accounts:
-    /// 0. [signer] Ejecutor
+    /// 0. [signer] Ejecutor (solicitante o firmante autorizado)
     /// 1. [writable] Cuenta del vault
     /// 2. [writable] Cuenta de operación pendiente
     /// 3. [writable] Cuenta destino (si aplica)
     /// 4. [writable] Cuenta de audit log
+    /// 5. [writable] (Opcional) Solicitante original (recibe la renta de la operación); sin
+    ///    él la operación queda ejecutada pero abierta hasta `CloseExpiredOperation`
+    /// 6. [] Cuenta de precio del oráculo (retiros con límites en moneda de cotización)
+    /// 7. [] `WithdrawToken`: mint, ATA del vault (writable) y token program, por dirección
     ExecutePendingOperation {
         operation_id: u64,
     },
 
+    /// Firmar operación pendiente desde un cliente v1, sin cuenta del vault (se codifica
+    /// con el tag `SIGN_PENDING_OPERATION` en formato v1). Sin vault no hay pesos: la
+    /// operación se aprueba cuando firman todos los firmantes requeridos
+    /// Accounts:
+    /// 0. [signer] Firmante requerido
+    /// 1. [writable] Cuenta de operación pendiente
+    /// 2. [writable] Cuenta de audit log
+    SignPendingOperationV1 {
+        operation_id: u64,
+    },
+
     /// Cancelar operación pendiente
     /// Accounts:
     /// 0. [signer] Admin o creador de la operación
@@ -151,19 +291,1098 @@ pub enum VaultInstruction {
         from_operation_id: u64,
         limit: u32,
     },
+
+    /// Depositar tokens SPL en la ATA del vault (solo mints registrados con `SetAssetLimits`)
+    /// Accounts:
+    /// 0. [signer] Depositante (authority de la cuenta origen)
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Token account de origen
+    /// 3. [writable] Token account del vault (ATA del PDA para el mint)
+    /// 4. [] Mint
+    /// 5. [] Token program
+    DepositToken {
+        amount: u64,
+    },
+
+    /// Retirar tokens SPL desde la ATA del vault
+    /// Accounts:
+    /// 0. [signer] Solicitante (debe ser manager o admin)
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Token account del vault (ATA del PDA para el mint)
+    /// 3. [writable] Token account destino (owner = recipient)
+    /// 4. [] Mint
+    /// 5. [writable] Cuenta de audit log
+    /// 6. [] Token program
+    /// 7. [] Address book del vault (requerido si la política de destinatarios está activa)
+    /// 8. [] Opcionales, por dirección: si el destinatario no está listado bajo
+    ///    `TimeLockUnlisted`, la cuenta de operación pendiente (PDA del próximo id, writable; el
+    ///    solicitante debe ser writable y la paga) y el system program
+    WithdrawToken {
+        amount: u64,
+        recipient: Pubkey,
+    },
+
+    /// Configurar límite diario y umbral de multisig de un activo (registra el mint si es nuevo)
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    SetAssetLimits {
+        asset: Asset,
+        daily_withdrawal_limit: u64,
+        multisig_threshold: u64,
+    },
+
+    /// Ejecutar varias operaciones de forma atómica (todas o ninguna)
+    /// Accounts:
+    /// 0. [signer] Solicitante (debe estar autorizado para cada operación)
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] N cuentas de audit log, una por operación y en el mismo orden
+    /// 3. [writable] A partir de 2+N: cuentas destino de los `Withdraw` (buscadas por pubkey)
+    ///    y address book del vault si la política de destinatarios está activa, más la cuenta
+    ///    de precio del oráculo si hay límites en moneda de cotización
+    ExecuteBatch {
+        operations: Vec<BatchOperation>,
+    },
+
+    /// Agregar destinatario aprobado al address book del vault
+    /// Accounts:
+    /// 0. [signer, writable] Admin (paga la creación del address book)
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Address book del vault (PDA, se crea si no existe)
+    /// 3. [writable] Cuenta de audit log
+    /// 4. [] System program
+    AddAddressBookEntry {
+        address: Pubkey,
+        label: String,
+    },
+
+    /// Remover destinatario del address book del vault
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Address book del vault (PDA)
+    /// 3. [writable] Cuenta de audit log
+    RemoveAddressBookEntry {
+        address: Pubkey,
+    },
+
+    /// Configurar la política para retiros a destinatarios fuera del address book
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    SetRecipientPolicy {
+        policy: RecipientPolicy,
+    },
+
+    /// Configurar cuota de retiros de SOL de un manager (por encima, requiere multisig)
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    SetManagerQuota {
+        manager: Pubkey,
+        daily_limit: u64,
+        per_transaction_limit: u64,
+    },
+
+    /// Crear o actualizar un rol personalizado con su bitmap de permisos
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    UpsertRole {
+        name: String,
+        permissions: Permissions,
+    },
+
+    /// Eliminar un rol personalizado y todas sus asignaciones
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    RemoveRole {
+        name: String,
+    },
+
+    /// Asignar un rol personalizado a una cuenta
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    AssignRole {
+        account: Pubkey,
+        role_name: String,
+    },
+
+    /// Revocar un rol personalizado de una cuenta
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    RevokeRole {
+        account: Pubkey,
+        role_name: String,
+    },
+
+    /// Configurar pesos de firmantes y umbrales por tipo de operación
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    SetMultisigPolicy {
+        policy: MultisigPolicy,
+    },
+
+    /// Cerrar una operación pendiente expirada (cualquiera puede invocarla)
+    /// Accounts:
+    /// 0. [signer] Cualquier cuenta
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de operación pendiente
+    /// 3. [writable] Cuenta de audit log (tamaño `AuditLog::MAX_SIZE`, distinta del resto)
+    /// 4. [writable] Solicitante original (recibe la renta de la operación)
+    CloseExpiredOperation {
+        operation_id: u64,
+    },
+
+    /// Rechazar operación pendiente (suma peso de rechazo; bloquea si ya no puede aprobarse)
+    /// Accounts:
+    /// 0. [signer] Firmante autorizado
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de operación pendiente
+    /// 3. [writable] Cuenta de audit log
+    RejectPendingOperation {
+        operation_id: u64,
+    },
+
+    /// Proponer un nuevo admin para recuperar el vault (cuenta como primera aprobación);
+    /// reemplaza una propuesta previa que no alcanzó quorum antes de expirar
+    /// Accounts:
+    /// 0. [signer] Emergency contact
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    ProposeAdminRecovery {
+        new_admin: Pubkey,
+    },
+
+    /// Aprobar la propuesta de recuperación activa; al alcanzar quorum inicia el time-lock
+    /// Accounts:
+    /// 0. [signer] Emergency contact
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    ApproveAdminRecovery {
+        new_admin: Pubkey,
+    },
+
+    /// Cancelar la recuperación en curso
+    /// Accounts:
+    /// 0. [signer] Admin actual
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    CancelAdminRecovery,
+
+    /// Ejecutar la recuperación tras el time-lock, rotando el admin
+    /// Accounts:
+    /// 0. [signer] Nuevo admin propuesto
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    ExecuteAdminRecovery,
+
+    /// Configurar quorum de emergency contacts y time-lock de la recuperación
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    SetRecoveryConfig {
+        threshold: u8,
+        delay_seconds: i64,
+    },
+
+    /// Proponer un nuevo admin; debe aceptar antes de que expire la propuesta
+    /// Accounts:
+    /// 0. [signer] Admin actual
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    ProposeAdmin {
+        new_admin: Pubkey,
+    },
+
+    /// Aceptar la administración propuesta
+    /// Accounts:
+    /// 0. [signer] Nuevo admin propuesto
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    AcceptAdmin,
+
+    /// Cancelar la transferencia de admin propuesta
+    /// Accounts:
+    /// 0. [signer] Admin actual
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    CancelAdminProposal,
+
+    /// Programar alta de contacto de emergencia (time-locked)
+    /// Accounts:
+    /// 0. [signer, writable] Admin (paga la cuenta de la operación)
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de operación pendiente (PDA)
+    /// 3. [writable] Cuenta de audit log
+    /// 4. [] System program
+    AddEmergencyContact {
+        contact: Pubkey,
+        delay_seconds: i64,
+    },
+
+    /// Programar baja de contacto de emergencia (time-locked)
+    /// Accounts:
+    /// 0. [signer, writable] Admin (paga la cuenta de la operación)
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de operación pendiente (PDA)
+    /// 3. [writable] Cuenta de audit log
+    /// 4. [] System program
+    RemoveEmergencyContact {
+        contact: Pubkey,
+        delay_seconds: i64,
+    },
+
+    /// Crear calendario de vesting lineal pagado desde el vault (reserva el total)
+    /// Accounts:
+    /// 0. [signer, writable] Admin (paga la cuenta del calendario)
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta del calendario de vesting (PDA)
+    /// 3. [writable] Cuenta de audit log
+    /// 4. [] System program
+    CreateVestingSchedule {
+        beneficiary: Pubkey,
+        total_amount: u64,
+        start_timestamp: i64,
+        cliff_timestamp: i64,
+        end_timestamp: i64,
+    },
+
+    /// Reclamar el monto liberado de un calendario de vesting
+    /// Accounts:
+    /// 0. [signer, writable] Beneficiario (recibe los fondos)
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta del calendario de vesting (PDA)
+    /// 3. [writable] Cuenta de audit log
+    ClaimVested {
+        schedule_id: u64,
+    },
+
+    /// Crear pago recurrente; se activa al ejecutar su operación pendiente (multisig)
+    /// Accounts:
+    /// 0. [signer, writable] Solicitante (paga las cuentas nuevas)
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de operación pendiente de aprobación (PDA)
+    /// 3. [writable] Cuenta de audit log
+    /// 4. [] System program
+    /// 5. [writable] Cuenta del pago recurrente (PDA)
+    /// 6. [] Address book del vault (requerido si la política de destinatarios está activa)
+    CreateRecurringPayment {
+        recipient: Pubkey,
+        amount: u64,
+        interval_seconds: i64,
+        total_payments: u32,
+        first_payment_at: i64,
+        delay_seconds: i64,
+    },
+
+    /// Disparar un pago recurrente vencido (cualquier firmante)
+    /// Accounts:
+    /// 0. [signer] Cualquier cuenta
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta del pago recurrente (PDA)
+    /// 3. [writable] Destinatario del pago
+    /// 4. [writable] Cuenta de audit log (tamaño `AuditLog::MAX_SIZE`, distinta del resto)
+    /// 5. [] Cuenta de precio del oráculo (requerida si hay límites en moneda de cotización)
+    ExecuteRecurringPayment {
+        payment_id: u64,
+    },
+
+    /// Crear o reconfigurar un compartimento (managers y límite diario propios)
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    ConfigureCompartment {
+        name: String,
+        managers: Vec<Pubkey>,
+        daily_withdrawal_limit: u64,
+    },
+
+    /// Mover balance entre compartimentos; `None` es el fondo general del vault
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    TransferBetweenCompartments {
+        from: Option<String>,
+        to: Option<String>,
+        amount: u64,
+    },
+
+    /// Retirar fondos de un compartimento
+    /// Accounts:
+    /// 0. [signer] Admin, o manager del compartimento con permiso `CompartmentWithdraw`
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta destino
+    /// 3. [writable] Cuenta de audit log
+    /// 4. [] Address book del vault (requerido si la política de destinatarios está activa)
+    /// 5. [] Cuenta de precio del oráculo (requerida si hay límites en moneda de cotización)
+    WithdrawFromCompartment {
+        compartment: String,
+        amount: u64,
+        recipient: Pubkey,
+    },
+
+    /// Devolver fondos a un depositante, hasta su balance registrado
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Registro del depositante (PDA)
+    /// 3. [writable] Cuenta del depositante (recibe los fondos)
+    /// 4. [writable] Cuenta de audit log
+    RefundDepositor {
+        depositor: Pubkey,
+        amount: u64,
+    },
+
+    /// Retirar el propio balance depositado (solo con `refundable_mode` activo)
+    /// Accounts:
+    /// 0. [signer, writable] Depositante (recibe los fondos)
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Registro del depositante (PDA)
+    /// 3. [writable] Cuenta de audit log
+    WithdrawDeposit {
+        amount: u64,
+    },
+
+    /// Configurar (o desactivar con `None`) el dead-man's switch; cancela un reclamo en curso
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    SetDeadManSwitch {
+        config: Option<DeadManSwitch>,
+    },
+
+    /// Iniciar el reclamo de fondos de un vault inactivo (beneficiario o emergency contact)
+    /// Accounts:
+    /// 0. [signer] Reclamante
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    ClaimInactiveVault,
+
+    /// Ejecutar el reclamo por inactividad cumplido su time-lock (sin permisos); transfiere el
+    /// SOL libre, conserva la renta del vault y no incluye los tokens custodiados
+    /// Accounts:
+    /// 0. [signer] Cualquier cuenta
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta del reclamante (recibe los fondos)
+    /// 3. [writable] Cuenta de audit log
+    ExecuteInactivityClaim,
+
+    /// Señal de vida de un firmante autorizado: reinicia el timeout y cancela reclamos
+    /// Accounts:
+    /// 0. [signer] Admin o manager
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    Heartbeat,
+
+    /// Pausar el vault por un plazo acotado (`MAX_TIMED_PAUSE_SECONDS`) con un motivo;
+    /// se reanuda solo al expirar salvo que el admin la extienda
+    /// Accounts:
+    /// 0. [signer] Admin o emergency contact
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    TimedPause {
+        reason: PauseReason,
+        duration_seconds: i64,
+    },
+
+    /// Extender la pausa temporal vigente
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    ExtendPause {
+        extension_seconds: i64,
+    },
+
+    /// Dar de baja un mint registrado sin balance, liberando su entrada
+    /// Accounts:
+    /// 0. [signer] Admin
+    /// 1. [writable] Cuenta del vault
+    /// 2. [writable] Cuenta de audit log
+    RemoveTokenAsset {
+        mint: Pubkey,
+    },
+}
+
+/// Operaciones admitidas dentro de un `ExecuteBatch`
+/// (borsh por orden de variante: solo agregar al final)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum BatchOperation {
+    AddManager {
+        new_manager: Pubkey,
+    },
+    RemoveManager {
+        manager_to_remove: Pubkey,
+    },
+    UpdateSecurityConfig {
+        new_config: SecurityConfig,
+    },
+    SetAssetLimits {
+        asset: Asset,
+        daily_withdrawal_limit: u64,
+        multisig_threshold: u64,
+    },
+    Withdraw {
+        amount: u64,
+        recipient: Pubkey,
+    },
+    /// Solo los campos de `SecurityConfigV1`; el resto conserva su valor
+    UpdateSecurityConfigV1 {
+        new_config: SecurityConfigV1,
+    },
+}
+
+/// Operaciones de `ExecuteBatch` en formato v1
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum BatchOperationV1 {
+    AddManager {
+        new_manager: Pubkey,
+    },
+    RemoveManager {
+        manager_to_remove: Pubkey,
+    },
+    UpdateSecurityConfig {
+        new_config: SecurityConfigV1,
+    },
+    SetAssetLimits {
+        asset: Asset,
+        daily_withdrawal_limit: u64,
+        multisig_threshold: u64,
+    },
+    Withdraw {
+        amount: u64,
+        recipient: Pubkey,
+    },
+}
+
+impl From<BatchOperationV1> for BatchOperation {
+    fn from(operation: BatchOperationV1) -> Self {
+        match operation {
+            BatchOperationV1::AddManager { new_manager } => Self::AddManager { new_manager },
+            BatchOperationV1::RemoveManager { manager_to_remove } => {
+                Self::RemoveManager { manager_to_remove }
+            }
+            BatchOperationV1::UpdateSecurityConfig { new_config } => {
+                Self::UpdateSecurityConfigV1 { new_config }
+            }
+            BatchOperationV1::SetAssetLimits {
+                asset,
+                daily_withdrawal_limit,
+                multisig_threshold,
+            } => Self::SetAssetLimits {
+                asset,
+                daily_withdrawal_limit,
+                multisig_threshold,
+            },
+            BatchOperationV1::Withdraw { amount, recipient } => {
+                Self::Withdraw { amount, recipient }
+            }
+        }
+    }
+}
+
+/// `SecurityConfig` del formato v1, anterior a los campos añadidos en v2
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct SecurityConfigV1 {
+    pub max_daily_operations: u32,
+    pub min_time_between_operations: i64,
+    pub require_multisig_for_large_amounts: u64,
+    pub auto_pause_on_suspicious_activity: bool,
+    pub max_managers: u8,
+    pub withdrawal_delay_seconds: i64,
+}
+
+impl SecurityConfigV1 {
+    /// Reemplazar en `base` los campos que conoce v1, conservando los demás
+    pub fn apply_to(&self, mut base: SecurityConfig) -> SecurityConfig {
+        base.max_daily_operations = self.max_daily_operations;
+        base.min_time_between_operations = self.min_time_between_operations;
+        base.require_multisig_for_large_amounts = self.require_multisig_for_large_amounts;
+        base.auto_pause_on_suspicious_activity = self.auto_pause_on_suspicious_activity;
+        base.max_managers = self.max_managers;
+        base.withdrawal_delay_seconds = self.withdrawal_delay_seconds;
+        base
+    }
+}
+
+impl BatchOperation {
+    /// Tipo de operación registrado en el audit log
+    pub fn operation_type(&self) -> OperationType {
+        match self {
+            BatchOperation::AddManager { .. } => OperationType::AddManager,
+            BatchOperation::RemoveManager { .. } => OperationType::RemoveManager,
+            BatchOperation::UpdateSecurityConfig { .. }
+            | BatchOperation::UpdateSecurityConfigV1 { .. }
+            | BatchOperation::SetAssetLimits { .. } => OperationType::UpdateConfig,
+            BatchOperation::Withdraw { .. } => OperationType::Withdraw,
+        }
+    }
 }
 
 impl VaultInstruction {
     /// Deserializar instrucción desde bytes
-    pub fn unpack(input: &[u8]) -> Result<Self, std::io::Error> {
-        // TODO: Implementar deserialización
-        todo!()
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&version, rest) = input
+            .split_first()
+            .ok_or(VaultError::InvalidInstruction)?;
+        let (&variant, payload) = rest.split_first().ok_or(VaultError::InvalidInstruction)?;
+        match version {
+            INSTRUCTION_VERSION => Self::unpack_v2(variant, payload),
+            INSTRUCTION_VERSION_V1 => Self::unpack_v1(variant, payload),
+            _ => Err(VaultError::InvalidInstruction.into()),
+        }
+    }
+
+    /// Decodificar una instrucción v1 (tags 0–19)
+    fn unpack_v1(variant: u8, payload: &[u8]) -> Result<Self, ProgramError> {
+        match variant {
+            tag::INITIALIZE_VAULT => {
+                let config: SecurityConfigV1 = decode(payload)?;
+                Ok(Self::InitializeVault {
+                    config: config.apply_to(SecurityConfig::default()),
+                })
+            }
+            tag::UPDATE_SECURITY_CONFIG => {
+                let new_config = decode(payload)?;
+                Ok(Self::UpdateSecurityConfigV1 { new_config })
+            }
+            tag::EXECUTE_BATCH => {
+                let operations: Vec<BatchOperationV1> = decode(payload)?;
+                Ok(Self::ExecuteBatch {
+                    operations: operations.into_iter().map(BatchOperation::from).collect(),
+                })
+            }
+            tag::SIGN_PENDING_OPERATION => {
+                let operation_id = decode(payload)?;
+                Ok(Self::SignPendingOperationV1 { operation_id })
+            }
+            // Sin system program entre sus cuentas no puede crear la operación pendiente
+            tag::CREATE_TIME_LOCK_OPERATION => {
+                msg!("Instruction {} requires wire format v{}", variant, INSTRUCTION_VERSION);
+                Err(VaultError::InvalidInstruction.into())
+            }
+            // Mismos campos y cuentas (v2 solo añadió cuentas opcionales al final)
+            tag::DEPOSIT..=tag::SET_ASSET_LIMITS => Self::unpack_v2(variant, payload),
+            _ => Err(VaultError::InvalidInstruction.into()),
+        }
+    }
+
+    /// Decodificar una instrucción en el formato actual
+    fn unpack_v2(variant: u8, payload: &[u8]) -> Result<Self, ProgramError> {
+        Ok(match variant {
+            tag::INITIALIZE_VAULT => {
+                let config = decode(payload)?;
+                Self::InitializeVault { config }
+            }
+            tag::DEPOSIT => {
+                let amount = decode(payload)?;
+                Self::Deposit { amount }
+            }
+            tag::WITHDRAW => {
+                let (amount, recipient) = decode(payload)?;
+                Self::Withdraw { amount, recipient }
+            }
+            tag::EMERGENCY_PAUSE => {
+                decode::<()>(payload)?;
+                Self::EmergencyPause
+            }
+            tag::RESUME => {
+                decode::<()>(payload)?;
+                Self::Resume
+            }
+            tag::ADD_MANAGER => {
+                let new_manager = decode(payload)?;
+                Self::AddManager { new_manager }
+            }
+            tag::REMOVE_MANAGER => {
+                let manager_to_remove = decode(payload)?;
+                Self::RemoveManager { manager_to_remove }
+            }
+            tag::UPDATE_SECURITY_CONFIG => {
+                let new_config = decode(payload)?;
+                Self::UpdateSecurityConfig { new_config }
+            }
+            tag::TRANSFER_ADMIN => {
+                let new_admin = decode(payload)?;
+                Self::TransferAdmin { new_admin }
+            }
+            tag::CREATE_TIME_LOCK_OPERATION => {
+                let (operation_type, amount, target_account, delay_seconds) = decode(payload)?;
+                Self::CreateTimeLockOperation {
+                    operation_type,
+                    amount,
+                    target_account,
+                    delay_seconds,
+                }
+            }
+            tag::SIGN_PENDING_OPERATION => {
+                let operation_id = decode(payload)?;
+                Self::SignPendingOperation { operation_id }
+            }
+            tag::EXECUTE_PENDING_OPERATION => {
+                let operation_id = decode(payload)?;
+                Self::ExecutePendingOperation { operation_id }
+            }
+            tag::CANCEL_PENDING_OPERATION => {
+                let operation_id = decode(payload)?;
+                Self::CancelPendingOperation { operation_id }
+            }
+            tag::EMERGENCY_WITHDRAW => {
+                let (amount, emergency_recipient) = decode(payload)?;
+                Self::EmergencyWithdraw {
+                    amount,
+                    emergency_recipient,
+                }
+            }
+            tag::GET_VAULT_INFO => {
+                decode::<()>(payload)?;
+                Self::GetVaultInfo
+            }
+            tag::GET_AUDIT_LOGS => {
+                let (from_operation_id, limit) = decode(payload)?;
+                Self::GetAuditLogs {
+                    from_operation_id,
+                    limit,
+                }
+            }
+            tag::DEPOSIT_TOKEN => {
+                let amount = decode(payload)?;
+                Self::DepositToken { amount }
+            }
+            tag::WITHDRAW_TOKEN => {
+                let (amount, recipient) = decode(payload)?;
+                Self::WithdrawToken { amount, recipient }
+            }
+            tag::SET_ASSET_LIMITS => {
+                let (asset, daily_withdrawal_limit, multisig_threshold) = decode(payload)?;
+                Self::SetAssetLimits {
+                    asset,
+                    daily_withdrawal_limit,
+                    multisig_threshold,
+                }
+            }
+            tag::EXECUTE_BATCH => {
+                let operations = decode(payload)?;
+                Self::ExecuteBatch { operations }
+            }
+            tag::ADD_ADDRESS_BOOK_ENTRY => {
+                let (address, label) = decode(payload)?;
+                Self::AddAddressBookEntry { address, label }
+            }
+            tag::REMOVE_ADDRESS_BOOK_ENTRY => {
+                let address = decode(payload)?;
+                Self::RemoveAddressBookEntry { address }
+            }
+            tag::SET_RECIPIENT_POLICY => {
+                let policy = decode(payload)?;
+                Self::SetRecipientPolicy { policy }
+            }
+            tag::SET_MANAGER_QUOTA => {
+                let (manager, daily_limit, per_transaction_limit) = decode(payload)?;
+                Self::SetManagerQuota {
+                    manager,
+                    daily_limit,
+                    per_transaction_limit,
+                }
+            }
+            tag::UPSERT_ROLE => {
+                let (name, permissions) = decode(payload)?;
+                Self::UpsertRole { name, permissions }
+            }
+            tag::REMOVE_ROLE => {
+                let name = decode(payload)?;
+                Self::RemoveRole { name }
+            }
+            tag::ASSIGN_ROLE => {
+                let (account, role_name) = decode(payload)?;
+                Self::AssignRole { account, role_name }
+            }
+            tag::REVOKE_ROLE => {
+                let (account, role_name) = decode(payload)?;
+                Self::RevokeRole { account, role_name }
+            }
+            tag::SET_MULTISIG_POLICY => {
+                let policy = decode(payload)?;
+                Self::SetMultisigPolicy { policy }
+            }
+            tag::CLOSE_EXPIRED_OPERATION => {
+                let operation_id = decode(payload)?;
+                Self::CloseExpiredOperation { operation_id }
+            }
+            tag::REJECT_PENDING_OPERATION => {
+                let operation_id = decode(payload)?;
+                Self::RejectPendingOperation { operation_id }
+            }
+            tag::PROPOSE_ADMIN_RECOVERY => {
+                let new_admin = decode(payload)?;
+                Self::ProposeAdminRecovery { new_admin }
+            }
+            tag::APPROVE_ADMIN_RECOVERY => {
+                let new_admin = decode(payload)?;
+                Self::ApproveAdminRecovery { new_admin }
+            }
+            tag::CANCEL_ADMIN_RECOVERY => {
+                decode::<()>(payload)?;
+                Self::CancelAdminRecovery
+            }
+            tag::EXECUTE_ADMIN_RECOVERY => {
+                decode::<()>(payload)?;
+                Self::ExecuteAdminRecovery
+            }
+            tag::PROPOSE_ADMIN => {
+                let new_admin = decode(payload)?;
+                Self::ProposeAdmin { new_admin }
+            }
+            tag::ACCEPT_ADMIN => {
+                decode::<()>(payload)?;
+                Self::AcceptAdmin
+            }
+            tag::CANCEL_ADMIN_PROPOSAL => {
+                decode::<()>(payload)?;
+                Self::CancelAdminProposal
+            }
+            tag::ADD_EMERGENCY_CONTACT => {
+                let (contact, delay_seconds) = decode(payload)?;
+                Self::AddEmergencyContact {
+                    contact,
+                    delay_seconds,
+                }
+            }
+            tag::REMOVE_EMERGENCY_CONTACT => {
+                let (contact, delay_seconds) = decode(payload)?;
+                Self::RemoveEmergencyContact {
+                    contact,
+                    delay_seconds,
+                }
+            }
+            tag::CREATE_VESTING_SCHEDULE => {
+                let (beneficiary, total_amount, start_timestamp, cliff_timestamp, end_timestamp) =
+                    decode(payload)?;
+                Self::CreateVestingSchedule {
+                    beneficiary,
+                    total_amount,
+                    start_timestamp,
+                    cliff_timestamp,
+                    end_timestamp,
+                }
+            }
+            tag::CLAIM_VESTED => {
+                let schedule_id = decode(payload)?;
+                Self::ClaimVested { schedule_id }
+            }
+            tag::CREATE_RECURRING_PAYMENT => {
+                let (
+                    recipient,
+                    amount,
+                    interval_seconds,
+                    total_payments,
+                    first_payment_at,
+                    delay_seconds,
+                ) = decode(payload)?;
+                Self::CreateRecurringPayment {
+                    recipient,
+                    amount,
+                    interval_seconds,
+                    total_payments,
+                    first_payment_at,
+                    delay_seconds,
+                }
+            }
+            tag::EXECUTE_RECURRING_PAYMENT => {
+                let payment_id = decode(payload)?;
+                Self::ExecuteRecurringPayment { payment_id }
+            }
+            tag::CONFIGURE_COMPARTMENT => {
+                let (name, managers, daily_withdrawal_limit) = decode(payload)?;
+                Self::ConfigureCompartment {
+                    name,
+                    managers,
+                    daily_withdrawal_limit,
+                }
+            }
+            tag::TRANSFER_BETWEEN_COMPARTMENTS => {
+                let (from, to, amount) = decode(payload)?;
+                Self::TransferBetweenCompartments { from, to, amount }
+            }
+            tag::WITHDRAW_FROM_COMPARTMENT => {
+                let (compartment, amount, recipient) = decode(payload)?;
+                Self::WithdrawFromCompartment {
+                    compartment,
+                    amount,
+                    recipient,
+                }
+            }
+            tag::REFUND_DEPOSITOR => {
+                let (depositor, amount) = decode(payload)?;
+                Self::RefundDepositor { depositor, amount }
+            }
+            tag::WITHDRAW_DEPOSIT => {
+                let amount = decode(payload)?;
+                Self::WithdrawDeposit { amount }
+            }
+            tag::SET_RECOVERY_CONFIG => {
+                let (threshold, delay_seconds) = decode(payload)?;
+                Self::SetRecoveryConfig {
+                    threshold,
+                    delay_seconds,
+                }
+            }
+            tag::SET_DEAD_MAN_SWITCH => {
+                let config = decode(payload)?;
+                Self::SetDeadManSwitch { config }
+            }
+            tag::CLAIM_INACTIVE_VAULT => {
+                decode::<()>(payload)?;
+                Self::ClaimInactiveVault
+            }
+            tag::EXECUTE_INACTIVITY_CLAIM => {
+                decode::<()>(payload)?;
+                Self::ExecuteInactivityClaim
+            }
+            tag::HEARTBEAT => {
+                decode::<()>(payload)?;
+                Self::Heartbeat
+            }
+            tag::TIMED_PAUSE => {
+                let (reason, duration_seconds) = decode(payload)?;
+                Self::TimedPause {
+                    reason,
+                    duration_seconds,
+                }
+            }
+            tag::EXTEND_PAUSE => {
+                let extension_seconds = decode(payload)?;
+                Self::ExtendPause { extension_seconds }
+            }
+            tag::REMOVE_TOKEN_ASSET => {
+                let mint = decode(payload)?;
+                Self::RemoveTokenAsset { mint }
+            }
+            _ => return Err(VaultError::InvalidInstruction.into()),
+        })
     }
 
     /// Serializar instrucción a bytes
     pub fn pack(&self) -> Vec<u8> {
-        // TODO: Implementar serialización
-        todo!()
+        let (variant, payload) = match self {
+            Self::InitializeVault { config } => (tag::INITIALIZE_VAULT, encode(config)),
+            Self::Deposit { amount } => (tag::DEPOSIT, encode(amount)),
+            Self::Withdraw { amount, recipient } => (tag::WITHDRAW, encode(&(amount, recipient))),
+            Self::EmergencyPause => (tag::EMERGENCY_PAUSE, Vec::new()),
+            Self::Resume => (tag::RESUME, Vec::new()),
+            Self::AddManager { new_manager } => (tag::ADD_MANAGER, encode(new_manager)),
+            Self::RemoveManager { manager_to_remove } => {
+                (tag::REMOVE_MANAGER, encode(manager_to_remove))
+            }
+            Self::UpdateSecurityConfig { new_config } => {
+                (tag::UPDATE_SECURITY_CONFIG, encode(new_config))
+            }
+            Self::UpdateSecurityConfigV1 { new_config } => {
+                (tag::UPDATE_SECURITY_CONFIG, encode(new_config))
+            }
+            Self::TransferAdmin { new_admin } => (tag::TRANSFER_ADMIN, encode(new_admin)),
+            Self::CreateTimeLockOperation {
+                operation_type,
+                amount,
+                target_account,
+                delay_seconds,
+            } => (
+                tag::CREATE_TIME_LOCK_OPERATION,
+                encode(&(operation_type, amount, target_account, delay_seconds)),
+            ),
+            Self::SignPendingOperation { operation_id } => {
+                (tag::SIGN_PENDING_OPERATION, encode(operation_id))
+            }
+            Self::ExecutePendingOperation { operation_id } => {
+                (tag::EXECUTE_PENDING_OPERATION, encode(operation_id))
+            }
+            Self::SignPendingOperationV1 { operation_id } => {
+                (tag::SIGN_PENDING_OPERATION, encode(operation_id))
+            }
+            Self::CancelPendingOperation { operation_id } => {
+                (tag::CANCEL_PENDING_OPERATION, encode(operation_id))
+            }
+            Self::EmergencyWithdraw {
+                amount,
+                emergency_recipient,
+            } => (tag::EMERGENCY_WITHDRAW, encode(&(amount, emergency_recipient))),
+            Self::GetVaultInfo => (tag::GET_VAULT_INFO, Vec::new()),
+            Self::GetAuditLogs {
+                from_operation_id,
+                limit,
+            } => (tag::GET_AUDIT_LOGS, encode(&(from_operation_id, limit))),
+            Self::DepositToken { amount } => (tag::DEPOSIT_TOKEN, encode(amount)),
+            Self::WithdrawToken { amount, recipient } => {
+                (tag::WITHDRAW_TOKEN, encode(&(amount, recipient)))
+            }
+            Self::SetAssetLimits {
+                asset,
+                daily_withdrawal_limit,
+                multisig_threshold,
+            } => (
+                tag::SET_ASSET_LIMITS,
+                encode(&(asset, daily_withdrawal_limit, multisig_threshold)),
+            ),
+            Self::ExecuteBatch { operations } => (tag::EXECUTE_BATCH, encode(operations)),
+            Self::AddAddressBookEntry { address, label } => {
+                (tag::ADD_ADDRESS_BOOK_ENTRY, encode(&(address, label)))
+            }
+            Self::RemoveAddressBookEntry { address } => {
+                (tag::REMOVE_ADDRESS_BOOK_ENTRY, encode(address))
+            }
+            Self::SetRecipientPolicy { policy } => (tag::SET_RECIPIENT_POLICY, encode(policy)),
+            Self::SetManagerQuota {
+                manager,
+                daily_limit,
+                per_transaction_limit,
+            } => (
+                tag::SET_MANAGER_QUOTA,
+                encode(&(manager, daily_limit, per_transaction_limit)),
+            ),
+            Self::UpsertRole { name, permissions } => {
+                (tag::UPSERT_ROLE, encode(&(name, permissions)))
+            }
+            Self::RemoveRole { name } => (tag::REMOVE_ROLE, encode(name)),
+            Self::AssignRole { account, role_name } => {
+                (tag::ASSIGN_ROLE, encode(&(account, role_name)))
+            }
+            Self::RevokeRole { account, role_name } => {
+                (tag::REVOKE_ROLE, encode(&(account, role_name)))
+            }
+            Self::SetMultisigPolicy { policy } => (tag::SET_MULTISIG_POLICY, encode(policy)),
+            Self::CloseExpiredOperation { operation_id } => {
+                (tag::CLOSE_EXPIRED_OPERATION, encode(operation_id))
+            }
+            Self::RejectPendingOperation { operation_id } => {
+                (tag::REJECT_PENDING_OPERATION, encode(operation_id))
+            }
+            Self::ProposeAdminRecovery { new_admin } => {
+                (tag::PROPOSE_ADMIN_RECOVERY, encode(new_admin))
+            }
+            Self::ApproveAdminRecovery { new_admin } => {
+                (tag::APPROVE_ADMIN_RECOVERY, encode(new_admin))
+            }
+            Self::CancelAdminRecovery => (tag::CANCEL_ADMIN_RECOVERY, Vec::new()),
+            Self::ExecuteAdminRecovery => (tag::EXECUTE_ADMIN_RECOVERY, Vec::new()),
+            Self::SetRecoveryConfig {
+                threshold,
+                delay_seconds,
+            } => (tag::SET_RECOVERY_CONFIG, encode(&(threshold, delay_seconds))),
+            Self::ProposeAdmin { new_admin } => (tag::PROPOSE_ADMIN, encode(new_admin)),
+            Self::AcceptAdmin => (tag::ACCEPT_ADMIN, Vec::new()),
+            Self::CancelAdminProposal => (tag::CANCEL_ADMIN_PROPOSAL, Vec::new()),
+            Self::AddEmergencyContact {
+                contact,
+                delay_seconds,
+            } => (tag::ADD_EMERGENCY_CONTACT, encode(&(contact, delay_seconds))),
+            Self::RemoveEmergencyContact {
+                contact,
+                delay_seconds,
+            } => (tag::REMOVE_EMERGENCY_CONTACT, encode(&(contact, delay_seconds))),
+            Self::CreateVestingSchedule {
+                beneficiary,
+                total_amount,
+                start_timestamp,
+                cliff_timestamp,
+                end_timestamp,
+            } => (
+                tag::CREATE_VESTING_SCHEDULE,
+                encode(&(
+                    beneficiary,
+                    total_amount,
+                    start_timestamp,
+                    cliff_timestamp,
+                    end_timestamp,
+                )),
+            ),
+            Self::ClaimVested { schedule_id } => (tag::CLAIM_VESTED, encode(schedule_id)),
+            Self::CreateRecurringPayment {
+                recipient,
+                amount,
+                interval_seconds,
+                total_payments,
+                first_payment_at,
+                delay_seconds,
+            } => (
+                tag::CREATE_RECURRING_PAYMENT,
+                encode(&(
+                    recipient,
+                    amount,
+                    interval_seconds,
+                    total_payments,
+                    first_payment_at,
+                    delay_seconds,
+                )),
+            ),
+            Self::ExecuteRecurringPayment { payment_id } => {
+                (tag::EXECUTE_RECURRING_PAYMENT, encode(payment_id))
+            }
+            Self::RefundDepositor { depositor, amount } => {
+                (tag::REFUND_DEPOSITOR, encode(&(depositor, amount)))
+            }
+            Self::WithdrawDeposit { amount } => (tag::WITHDRAW_DEPOSIT, encode(amount)),
+            Self::ConfigureCompartment {
+                name,
+                managers,
+                daily_withdrawal_limit,
+            } => (
+                tag::CONFIGURE_COMPARTMENT,
+                encode(&(name, managers, daily_withdrawal_limit)),
+            ),
+            Self::TransferBetweenCompartments { from, to, amount } => (
+                tag::TRANSFER_BETWEEN_COMPARTMENTS,
+                encode(&(from, to, amount)),
+            ),
+            Self::WithdrawFromCompartment {
+                compartment,
+                amount,
+                recipient,
+            } => (
+                tag::WITHDRAW_FROM_COMPARTMENT,
+                encode(&(compartment, amount, recipient)),
+            ),
+            Self::SetDeadManSwitch { config } => (tag::SET_DEAD_MAN_SWITCH, encode(config)),
+            Self::ClaimInactiveVault => (tag::CLAIM_INACTIVE_VAULT, Vec::new()),
+            Self::ExecuteInactivityClaim => (tag::EXECUTE_INACTIVITY_CLAIM, Vec::new()),
+            Self::Heartbeat => (tag::HEARTBEAT, Vec::new()),
+            Self::TimedPause {
+                reason,
+                duration_seconds,
+            } => (tag::TIMED_PAUSE, encode(&(reason, duration_seconds))),
+            Self::ExtendPause { extension_seconds } => {
+                (tag::EXTEND_PAUSE, encode(extension_seconds))
+            }
+            Self::RemoveTokenAsset { mint } => (tag::REMOVE_TOKEN_ASSET, encode(mint)),
+        };
+
+        let version = match self {
+            Self::UpdateSecurityConfigV1 { .. } | Self::SignPendingOperationV1 { .. } => {
+                INSTRUCTION_VERSION_V1
+            }
+            _ => INSTRUCTION_VERSION,
+        };
+        let mut data = Vec::with_capacity(2 + payload.len());
+        data.push(version);
+        data.push(variant);
+        data.extend_from_slice(&payload);
+        data
     }
 
     /// Obtener el tipo de operación de la instrucción
@@ -185,6 +1404,18 @@ impl VaultInstruction {
     }
 }
 
+/// Serializar los campos de una variante
+fn encode<T: BorshSerialize>(fields: &T) -> Vec<u8> {
+    fields
+        .try_to_vec()
+        .expect("VaultInstruction serialization cannot fail")
+}
+
+/// Deserializar los campos de una variante, rechazando bytes sobrantes
+fn decode<T: BorshDeserialize>(payload: &[u8]) -> Result<T, ProgramError> {
+    T::try_from_slice(payload).map_err(|_| VaultError::InvalidInstruction.into())
+}
+
 /// Niveles de riesgo para las operaciones
 #[derive(Debug, Clone, PartialEq)]
 pub enum RiskLevel {
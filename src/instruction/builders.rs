@@ -0,0 +1,1320 @@
+//! Constructores de instrucciones para clientes.
+//!
+//! Cada función devuelve una `Instruction` lista para firmar, con las cuentas en el
+//! orden documentado en `VaultInstruction` y los flags signer/writable correctos.
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::{
+    instruction::{BatchOperation, VaultInstruction},
+    state::{
+        AddressBook, Asset, DeadManSwitch, DepositorLedger, MultisigPolicy, OperationType,
+        PauseReason, Permissions, RecipientPolicy, RecurringPayment, SecurityConfig,
+        VestingSchedule,
+    },
+    utils::VaultUtils,
+};
+
+/// Derivar la dirección del vault (PDA) a partir de su admin original (`seed_authority`)
+pub fn find_vault_address(program_id: &Pubkey, admin: &Pubkey) -> (Pubkey, u8) {
+    let seeds = VaultUtils::generate_vault_seeds(admin);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+    Pubkey::find_program_address(&seeds, program_id)
+}
+
+/// Derivar la dirección de una operación pendiente (PDA)
+pub fn find_pending_operation_address(
+    program_id: &Pubkey,
+    vault: &Pubkey,
+    operation_id: u64,
+) -> (Pubkey, u8) {
+    let seeds = VaultUtils::generate_operation_seeds(vault, operation_id);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+    Pubkey::find_program_address(&seeds, program_id)
+}
+
+/// Derivar la dirección del address book (PDA) de un vault
+pub fn find_address_book_address(program_id: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+    let seeds = AddressBook::get_seeds(vault);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+    Pubkey::find_program_address(&seeds, program_id)
+}
+
+/// Derivar la dirección del registro (PDA) de un depositante
+pub fn find_depositor_ledger_address(
+    program_id: &Pubkey,
+    vault: &Pubkey,
+    depositor: &Pubkey,
+) -> (Pubkey, u8) {
+    let seeds = DepositorLedger::get_seeds(vault, depositor);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+    Pubkey::find_program_address(&seeds, program_id)
+}
+
+/// Derivar la dirección de un pago recurrente (PDA)
+pub fn find_recurring_payment_address(
+    program_id: &Pubkey,
+    vault: &Pubkey,
+    payment_id: u64,
+) -> (Pubkey, u8) {
+    let seeds = RecurringPayment::get_seeds(vault, payment_id);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+    Pubkey::find_program_address(&seeds, program_id)
+}
+
+/// Derivar la dirección de un calendario de vesting (PDA)
+pub fn find_vesting_schedule_address(
+    program_id: &Pubkey,
+    vault: &Pubkey,
+    schedule_id: u64,
+) -> (Pubkey, u8) {
+    let seeds = VestingSchedule::get_seeds(vault, schedule_id);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+    Pubkey::find_program_address(&seeds, program_id)
+}
+
+fn build(
+    program_id: &Pubkey,
+    instruction: VaultInstruction,
+    accounts: Vec<AccountMeta>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.pack(),
+    }
+}
+
+/// Cuentas comunes de las instrucciones administrativas: firmante, vault y audit log
+fn admin_accounts(signer: &Pubkey, vault: &Pubkey, audit_log: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(*signer, true),
+        AccountMeta::new(*vault, false),
+        AccountMeta::new(*audit_log, false),
+    ]
+}
+
+pub fn initialize_vault(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    config: SecurityConfig,
+) -> Instruction {
+    let (vault, _) = find_vault_address(program_id, admin);
+    build(
+        program_id,
+        VaultInstruction::InitializeVault { config },
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn deposit(
+    program_id: &Pubkey,
+    depositor: &Pubkey,
+    vault: &Pubkey,
+    source: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::Deposit { amount },
+        vec![
+            AccountMeta::new_readonly(*depositor, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*source, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(
+                find_depositor_ledger_address(program_id, vault, depositor).0,
+                false,
+            ),
+        ],
+    )
+}
+
+pub fn withdraw(
+    program_id: &Pubkey,
+    performer: &Pubkey,
+    vault: &Pubkey,
+    recipient: &Pubkey,
+    audit_log: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::Withdraw {
+            amount,
+            recipient: *recipient,
+        },
+        vec![
+            AccountMeta::new_readonly(*performer, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*recipient, false),
+            AccountMeta::new(*audit_log, false),
+            AccountMeta::new_readonly(find_address_book_address(program_id, vault).0, false),
+        ],
+    )
+}
+
+pub fn emergency_pause(
+    program_id: &Pubkey,
+    performer: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::EmergencyPause,
+        admin_accounts(performer, vault, audit_log),
+    )
+}
+
+pub fn resume(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::Resume,
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn add_manager(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    new_manager: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::AddManager {
+            new_manager: *new_manager,
+        },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn remove_manager(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    manager_to_remove: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::RemoveManager {
+            manager_to_remove: *manager_to_remove,
+        },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn update_security_config(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    new_config: SecurityConfig,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::UpdateSecurityConfig { new_config },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn transfer_admin(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    new_admin: &Pubkey,
+) -> Instruction {
+    let mut accounts = admin_accounts(admin, vault, audit_log);
+    accounts.push(AccountMeta::new_readonly(*new_admin, true));
+    build(
+        program_id,
+        VaultInstruction::TransferAdmin {
+            new_admin: *new_admin,
+        },
+        accounts,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_time_lock_operation(
+    program_id: &Pubkey,
+    requester: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    operation_id: u64,
+    operation_type: OperationType,
+    amount: u64,
+    target_account: &Pubkey,
+    delay_seconds: i64,
+) -> Instruction {
+    let (pending_operation, _) = find_pending_operation_address(program_id, vault, operation_id);
+    let (address_book, _) = find_address_book_address(program_id, vault);
+    build(
+        program_id,
+        VaultInstruction::CreateTimeLockOperation {
+            operation_type,
+            amount,
+            target_account: *target_account,
+            delay_seconds,
+        },
+        vec![
+            AccountMeta::new(*requester, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(pending_operation, false),
+            AccountMeta::new(*audit_log, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(address_book, false),
+        ],
+    )
+}
+
+/// Programa un retiro de tokens por encima del umbral de multisig del mint hacia la ATA de
+/// `recipient`
+#[allow(clippy::too_many_arguments)]
+pub fn create_token_withdraw_operation(
+    program_id: &Pubkey,
+    requester: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    operation_id: u64,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    delay_seconds: i64,
+) -> Instruction {
+    let destination = get_associated_token_address(recipient, mint);
+    let mut instruction = create_time_lock_operation(
+        program_id,
+        requester,
+        vault,
+        audit_log,
+        operation_id,
+        OperationType::WithdrawToken,
+        amount,
+        &destination,
+        delay_seconds,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(destination, false));
+    instruction
+}
+
+/// Ejecuta un retiro de tokens programado con `create_token_withdraw_operation`
+#[allow(clippy::too_many_arguments)]
+pub fn execute_token_withdraw_operation(
+    program_id: &Pubkey,
+    executor: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    performer: &Pubkey,
+    operation_id: u64,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    let mut instruction = execute_pending_operation(
+        program_id,
+        executor,
+        vault,
+        &get_associated_token_address(recipient, mint),
+        audit_log,
+        performer,
+        operation_id,
+    );
+    instruction.accounts.extend([
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(VaultUtils::get_vault_token_address(vault, mint), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ]);
+    instruction
+}
+
+/// Programa el levantamiento del disyuntor: operación `Resume` sin delay que ejecuta
+/// `execute_pending_operation` con el vault como cuenta destino al reunir el quórum
+pub fn lift_circuit_breaker(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    operation_id: u64,
+) -> Instruction {
+    create_time_lock_operation(
+        program_id,
+        admin,
+        vault,
+        audit_log,
+        operation_id,
+        OperationType::Resume,
+        0,
+        vault,
+        0,
+    )
+}
+
+pub fn sign_pending_operation(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    operation_id: u64,
+) -> Instruction {
+    let (pending_operation, _) = find_pending_operation_address(program_id, vault, operation_id);
+    build(
+        program_id,
+        VaultInstruction::SignPendingOperation { operation_id },
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(pending_operation, false),
+            AccountMeta::new(*audit_log, false),
+        ],
+    )
+}
+
+pub fn execute_pending_operation(
+    program_id: &Pubkey,
+    executor: &Pubkey,
+    vault: &Pubkey,
+    target_account: &Pubkey,
+    audit_log: &Pubkey,
+    performer: &Pubkey,
+    operation_id: u64,
+) -> Instruction {
+    let (pending_operation, _) = find_pending_operation_address(program_id, vault, operation_id);
+    build(
+        program_id,
+        VaultInstruction::ExecutePendingOperation { operation_id },
+        vec![
+            AccountMeta::new_readonly(*executor, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(pending_operation, false),
+            AccountMeta::new(*target_account, false),
+            AccountMeta::new(*audit_log, false),
+            AccountMeta::new(*performer, false),
+        ],
+    )
+}
+
+pub fn cancel_pending_operation(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    operation_id: u64,
+) -> Instruction {
+    let (pending_operation, _) = find_pending_operation_address(program_id, vault, operation_id);
+    build(
+        program_id,
+        VaultInstruction::CancelPendingOperation { operation_id },
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(pending_operation, false),
+            AccountMeta::new(*audit_log, false),
+        ],
+    )
+}
+
+pub fn emergency_withdraw(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    emergency_recipient: &Pubkey,
+    audit_log: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::EmergencyWithdraw {
+            amount,
+            emergency_recipient: *emergency_recipient,
+        },
+        vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*emergency_recipient, false),
+            AccountMeta::new(*audit_log, false),
+        ],
+    )
+}
+
+pub fn get_vault_info(program_id: &Pubkey, vault: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::GetVaultInfo,
+        vec![AccountMeta::new_readonly(*vault, false)],
+    )
+}
+
+pub fn get_audit_logs(
+    program_id: &Pubkey,
+    audit_log: &Pubkey,
+    from_operation_id: u64,
+    limit: u32,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::GetAuditLogs {
+            from_operation_id,
+            limit,
+        },
+        vec![AccountMeta::new_readonly(*audit_log, false)],
+    )
+}
+
+/// Depósito de tokens desde `source_token_account` hacia la ATA del vault
+pub fn deposit_token(
+    program_id: &Pubkey,
+    depositor: &Pubkey,
+    vault: &Pubkey,
+    source_token_account: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::DepositToken { amount },
+        vec![
+            AccountMeta::new_readonly(*depositor, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*source_token_account, false),
+            AccountMeta::new(VaultUtils::get_vault_token_address(vault, mint), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+}
+
+/// Retiro de tokens hacia la ATA de `recipient`
+pub fn withdraw_token(
+    program_id: &Pubkey,
+    performer: &Pubkey,
+    vault: &Pubkey,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    audit_log: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::WithdrawToken {
+            amount,
+            recipient: *recipient,
+        },
+        vec![
+            AccountMeta::new_readonly(*performer, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(VaultUtils::get_vault_token_address(vault, mint), false),
+            AccountMeta::new(get_associated_token_address(recipient, mint), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*audit_log, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(find_address_book_address(program_id, vault).0, false),
+        ],
+    )
+}
+
+pub fn set_asset_limits(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    asset: Asset,
+    daily_withdrawal_limit: u64,
+    multisig_threshold: u64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::SetAssetLimits {
+            asset,
+            daily_withdrawal_limit,
+            multisig_threshold,
+        },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn remove_token_asset(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::RemoveTokenAsset { mint: *mint },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+/// Batch atómico: `audit_logs` debe tener una cuenta por operación, en el mismo orden
+pub fn execute_batch(
+    program_id: &Pubkey,
+    performer: &Pubkey,
+    vault: &Pubkey,
+    audit_logs: &[Pubkey],
+    operations: Vec<BatchOperation>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*performer, true),
+        AccountMeta::new(*vault, false),
+    ];
+    accounts.extend(
+        audit_logs
+            .iter()
+            .map(|audit_log| AccountMeta::new(*audit_log, false)),
+    );
+    for operation in operations.iter() {
+        if let BatchOperation::Withdraw { recipient, .. } = operation {
+            if !accounts.iter().any(|meta| meta.pubkey == *recipient) {
+                accounts.push(AccountMeta::new(*recipient, false));
+            }
+        }
+    }
+    accounts.push(AccountMeta::new_readonly(
+        find_address_book_address(program_id, vault).0,
+        false,
+    ));
+
+    build(
+        program_id,
+        VaultInstruction::ExecuteBatch { operations },
+        accounts,
+    )
+}
+
+pub fn add_address_book_entry(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    address: &Pubkey,
+    label: String,
+) -> Instruction {
+    let (address_book, _) = find_address_book_address(program_id, vault);
+    build(
+        program_id,
+        VaultInstruction::AddAddressBookEntry {
+            address: *address,
+            label,
+        },
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(address_book, false),
+            AccountMeta::new(*audit_log, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn remove_address_book_entry(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    address: &Pubkey,
+) -> Instruction {
+    let (address_book, _) = find_address_book_address(program_id, vault);
+    build(
+        program_id,
+        VaultInstruction::RemoveAddressBookEntry { address: *address },
+        vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(address_book, false),
+            AccountMeta::new(*audit_log, false),
+        ],
+    )
+}
+
+pub fn set_recipient_policy(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    policy: RecipientPolicy,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::SetRecipientPolicy { policy },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn set_manager_quota(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    manager: &Pubkey,
+    daily_limit: u64,
+    per_transaction_limit: u64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::SetManagerQuota {
+            manager: *manager,
+            daily_limit,
+            per_transaction_limit,
+        },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn upsert_role(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    name: String,
+    permissions: Permissions,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::UpsertRole { name, permissions },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn remove_role(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    name: String,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::RemoveRole { name },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn assign_role(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    account: &Pubkey,
+    role_name: String,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::AssignRole {
+            account: *account,
+            role_name,
+        },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn revoke_role(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    account: &Pubkey,
+    role_name: String,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::RevokeRole {
+            account: *account,
+            role_name,
+        },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn set_multisig_policy(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    policy: MultisigPolicy,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::SetMultisigPolicy { policy },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+/// Cierre sin permisos de una operación expirada; la renta vuelve a `performer`
+pub fn close_expired_operation(
+    program_id: &Pubkey,
+    caller: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    performer: &Pubkey,
+    operation_id: u64,
+) -> Instruction {
+    let (pending_operation, _) = find_pending_operation_address(program_id, vault, operation_id);
+    build(
+        program_id,
+        VaultInstruction::CloseExpiredOperation { operation_id },
+        vec![
+            AccountMeta::new_readonly(*caller, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(pending_operation, false),
+            AccountMeta::new(*audit_log, false),
+            AccountMeta::new(*performer, false),
+        ],
+    )
+}
+
+pub fn reject_pending_operation(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    operation_id: u64,
+) -> Instruction {
+    let (pending_operation, _) = find_pending_operation_address(program_id, vault, operation_id);
+    build(
+        program_id,
+        VaultInstruction::RejectPendingOperation { operation_id },
+        vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(pending_operation, false),
+            AccountMeta::new(*audit_log, false),
+        ],
+    )
+}
+
+pub fn propose_admin_recovery(
+    program_id: &Pubkey,
+    guardian: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    new_admin: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::ProposeAdminRecovery {
+            new_admin: *new_admin,
+        },
+        admin_accounts(guardian, vault, audit_log),
+    )
+}
+
+pub fn approve_admin_recovery(
+    program_id: &Pubkey,
+    guardian: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    new_admin: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::ApproveAdminRecovery {
+            new_admin: *new_admin,
+        },
+        admin_accounts(guardian, vault, audit_log),
+    )
+}
+
+pub fn cancel_admin_recovery(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::CancelAdminRecovery,
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn execute_admin_recovery(
+    program_id: &Pubkey,
+    new_admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::ExecuteAdminRecovery,
+        admin_accounts(new_admin, vault, audit_log),
+    )
+}
+
+pub fn set_recovery_config(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    threshold: u8,
+    delay_seconds: i64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::SetRecoveryConfig {
+            threshold,
+            delay_seconds,
+        },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn propose_admin(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    new_admin: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::ProposeAdmin {
+            new_admin: *new_admin,
+        },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn accept_admin(
+    program_id: &Pubkey,
+    new_admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::AcceptAdmin,
+        admin_accounts(new_admin, vault, audit_log),
+    )
+}
+
+pub fn cancel_admin_proposal(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::CancelAdminProposal,
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+fn emergency_contact_accounts(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    operation_id: u64,
+) -> Vec<AccountMeta> {
+    let (pending_operation, _) = find_pending_operation_address(program_id, vault, operation_id);
+    vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(*vault, false),
+        AccountMeta::new(pending_operation, false),
+        AccountMeta::new(*audit_log, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+pub fn add_emergency_contact(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    operation_id: u64,
+    contact: &Pubkey,
+    delay_seconds: i64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::AddEmergencyContact {
+            contact: *contact,
+            delay_seconds,
+        },
+        emergency_contact_accounts(program_id, admin, vault, audit_log, operation_id),
+    )
+}
+
+pub fn remove_emergency_contact(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    operation_id: u64,
+    contact: &Pubkey,
+    delay_seconds: i64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::RemoveEmergencyContact {
+            contact: *contact,
+            delay_seconds,
+        },
+        emergency_contact_accounts(program_id, admin, vault, audit_log, operation_id),
+    )
+}
+
+/// Agregar la cuenta de precio del oráculo a un retiro (límites en moneda de cotización)
+pub fn with_price_feed(mut instruction: Instruction, price_feed: &Pubkey) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(*price_feed, false));
+    instruction
+}
+
+/// Agrega a un `withdraw` o `withdraw_token` las cuentas para programarlo como operación
+/// time-locked cuando el monto cae en un tramo de delay o el destinatario no está listado;
+/// `operation_id` es `pending_operation_count + 1` y las cuentas se buscan por dirección, así
+/// que puede combinarse con `with_price_feed`
+pub fn with_pending_operation(
+    mut instruction: Instruction,
+    program_id: &Pubkey,
+    vault: &Pubkey,
+    operation_id: u64,
+) -> Instruction {
+    let (pending_operation, _) = find_pending_operation_address(program_id, vault, operation_id);
+    // El solicitante paga la cuenta de la operación
+    instruction.accounts[0].is_writable = true;
+    instruction
+        .accounts
+        .push(AccountMeta::new(pending_operation, false));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(system_program::id(), false));
+    instruction
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_vesting_schedule(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    schedule_id: u64,
+    beneficiary: &Pubkey,
+    total_amount: u64,
+    start_timestamp: i64,
+    cliff_timestamp: i64,
+    end_timestamp: i64,
+) -> Instruction {
+    let (schedule, _) = find_vesting_schedule_address(program_id, vault, schedule_id);
+    build(
+        program_id,
+        VaultInstruction::CreateVestingSchedule {
+            beneficiary: *beneficiary,
+            total_amount,
+            start_timestamp,
+            cliff_timestamp,
+            end_timestamp,
+        },
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(schedule, false),
+            AccountMeta::new(*audit_log, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn claim_vested(
+    program_id: &Pubkey,
+    beneficiary: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    schedule_id: u64,
+) -> Instruction {
+    let (schedule, _) = find_vesting_schedule_address(program_id, vault, schedule_id);
+    build(
+        program_id,
+        VaultInstruction::ClaimVested { schedule_id },
+        vec![
+            AccountMeta::new(*beneficiary, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(schedule, false),
+            AccountMeta::new(*audit_log, false),
+        ],
+    )
+}
+
+/// `operation_id` y `payment_id` son los siguientes contadores del vault
+#[allow(clippy::too_many_arguments)]
+pub fn create_recurring_payment(
+    program_id: &Pubkey,
+    requester: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    operation_id: u64,
+    payment_id: u64,
+    recipient: &Pubkey,
+    amount: u64,
+    interval_seconds: i64,
+    total_payments: u32,
+    first_payment_at: i64,
+    delay_seconds: i64,
+) -> Instruction {
+    let (pending_operation, _) = find_pending_operation_address(program_id, vault, operation_id);
+    let (payment, _) = find_recurring_payment_address(program_id, vault, payment_id);
+    let (address_book, _) = find_address_book_address(program_id, vault);
+    build(
+        program_id,
+        VaultInstruction::CreateRecurringPayment {
+            recipient: *recipient,
+            amount,
+            interval_seconds,
+            total_payments,
+            first_payment_at,
+            delay_seconds,
+        },
+        vec![
+            AccountMeta::new(*requester, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(pending_operation, false),
+            AccountMeta::new(*audit_log, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(payment, false),
+            AccountMeta::new_readonly(address_book, false),
+        ],
+    )
+}
+
+pub fn execute_recurring_payment(
+    program_id: &Pubkey,
+    caller: &Pubkey,
+    vault: &Pubkey,
+    recipient: &Pubkey,
+    audit_log: &Pubkey,
+    payment_id: u64,
+) -> Instruction {
+    let (payment, _) = find_recurring_payment_address(program_id, vault, payment_id);
+    build(
+        program_id,
+        VaultInstruction::ExecuteRecurringPayment { payment_id },
+        vec![
+            AccountMeta::new_readonly(*caller, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(payment, false),
+            AccountMeta::new(*recipient, false),
+            AccountMeta::new(*audit_log, false),
+        ],
+    )
+}
+
+pub fn configure_compartment(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    name: String,
+    managers: Vec<Pubkey>,
+    daily_withdrawal_limit: u64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::ConfigureCompartment {
+            name,
+            managers,
+            daily_withdrawal_limit,
+        },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn transfer_between_compartments(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    from: Option<String>,
+    to: Option<String>,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::TransferBetweenCompartments { from, to, amount },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn withdraw_from_compartment(
+    program_id: &Pubkey,
+    performer: &Pubkey,
+    vault: &Pubkey,
+    recipient: &Pubkey,
+    audit_log: &Pubkey,
+    compartment: String,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::WithdrawFromCompartment {
+            compartment,
+            amount,
+            recipient: *recipient,
+        },
+        vec![
+            AccountMeta::new_readonly(*performer, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*recipient, false),
+            AccountMeta::new(*audit_log, false),
+            AccountMeta::new_readonly(find_address_book_address(program_id, vault).0, false),
+        ],
+    )
+}
+
+pub fn refund_depositor(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    depositor: &Pubkey,
+    audit_log: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (ledger, _) = find_depositor_ledger_address(program_id, vault, depositor);
+    build(
+        program_id,
+        VaultInstruction::RefundDepositor {
+            depositor: *depositor,
+            amount,
+        },
+        vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(ledger, false),
+            AccountMeta::new(*depositor, false),
+            AccountMeta::new(*audit_log, false),
+        ],
+    )
+}
+
+pub fn withdraw_deposit(
+    program_id: &Pubkey,
+    depositor: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (ledger, _) = find_depositor_ledger_address(program_id, vault, depositor);
+    build(
+        program_id,
+        VaultInstruction::WithdrawDeposit { amount },
+        vec![
+            AccountMeta::new(*depositor, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(ledger, false),
+            AccountMeta::new(*audit_log, false),
+        ],
+    )
+}
+
+pub fn set_dead_man_switch(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    config: Option<DeadManSwitch>,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::SetDeadManSwitch { config },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
+
+pub fn claim_inactive_vault(
+    program_id: &Pubkey,
+    claimant: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::ClaimInactiveVault,
+        admin_accounts(claimant, vault, audit_log),
+    )
+}
+
+pub fn execute_inactivity_claim(
+    program_id: &Pubkey,
+    caller: &Pubkey,
+    vault: &Pubkey,
+    claimant: &Pubkey,
+    audit_log: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::ExecuteInactivityClaim,
+        vec![
+            AccountMeta::new_readonly(*caller, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*claimant, false),
+            AccountMeta::new(*audit_log, false),
+        ],
+    )
+}
+
+pub fn heartbeat(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::Heartbeat,
+        admin_accounts(signer, vault, audit_log),
+    )
+}
+
+pub fn timed_pause(
+    program_id: &Pubkey,
+    performer: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    reason: PauseReason,
+    duration_seconds: i64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::TimedPause {
+            reason,
+            duration_seconds,
+        },
+        admin_accounts(performer, vault, audit_log),
+    )
+}
+
+pub fn extend_pause(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    vault: &Pubkey,
+    audit_log: &Pubkey,
+    extension_seconds: i64,
+) -> Instruction {
+    build(
+        program_id,
+        VaultInstruction::ExtendPause { extension_seconds },
+        admin_accounts(admin, vault, audit_log),
+    )
+}
@@ -1,17 +1,31 @@
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
     pubkey::Pubkey,
+    system_instruction, system_program,
     msg,
 };
 
 use crate::{
-    instruction::VaultInstruction,
-    state::{SecurityConfig, OperationType},
+    instruction::{BatchOperation, SecurityConfigV1, VaultInstruction, MAX_BATCH_OPERATIONS},
+    state::{
+        AddressBook, Asset, DeadManSwitch, DepositorLedger, MultisigPolicy, OperationStatus,
+        OperationType, PauseReason, PendingOperation, PriceFeed, RecipientPolicy, RecurringPayment,
+        SecureVault, SecurityConfig, VestingSchedule, ADDRESS_BOOK_DISCRIMINATOR,
+        DEPOSITOR_LEDGER_DISCRIMINATOR, MAX_EMERGENCY_CONTACTS, MAX_TIMED_PAUSE_SECONDS,
+        MIN_RECOVERY_DELAY_SECONDS, RECURRING_PAYMENT_DISCRIMINATOR, VAULT_DISCRIMINATOR,
+        VESTING_DISCRIMINATOR,
+    },
     validation::Validator,
     security::SecurityManager,
     audit::AuditManager,
     error::VaultError,
+    utils::{
+        constants::{MIN_DEPOSIT_AMOUNT, OPERATION_EXPIRY_HOURS},
+        Utils, VaultUtils,
+    },
 };
 
 pub struct Processor;
@@ -50,6 +64,9 @@ impl Processor {
             VaultInstruction::UpdateSecurityConfig { new_config } => {
                 Self::process_update_config(program_id, accounts, new_config)
             }
+            VaultInstruction::UpdateSecurityConfigV1 { new_config } => {
+                Self::process_update_config_v1(program_id, accounts, new_config)
+            }
             VaultInstruction::TransferAdmin { new_admin } => {
                 Self::process_transfer_admin(program_id, accounts, new_admin)
             }
@@ -74,6 +91,9 @@ impl Processor {
             VaultInstruction::ExecutePendingOperation { operation_id } => {
                 Self::process_execute_pending_operation(program_id, accounts, operation_id)
             }
+            VaultInstruction::SignPendingOperationV1 { operation_id } => {
+                Self::process_sign_pending_operation_v1(program_id, accounts, operation_id)
+            }
             VaultInstruction::CancelPendingOperation { operation_id } => {
                 Self::process_cancel_pending_operation(program_id, accounts, operation_id)
             }
@@ -86,6 +106,204 @@ impl Processor {
             VaultInstruction::GetAuditLogs { from_operation_id, limit } => {
                 Self::process_get_audit_logs(program_id, accounts, from_operation_id, limit)
             }
+            VaultInstruction::DepositToken { amount } => {
+                Self::process_deposit_token(program_id, accounts, amount)
+            }
+            VaultInstruction::WithdrawToken { amount, recipient } => {
+                Self::process_withdraw_token(program_id, accounts, amount, recipient)
+            }
+            VaultInstruction::SetAssetLimits {
+                asset,
+                daily_withdrawal_limit,
+                multisig_threshold,
+            } => Self::process_set_asset_limits(
+                program_id,
+                accounts,
+                asset,
+                daily_withdrawal_limit,
+                multisig_threshold,
+            ),
+            VaultInstruction::ExecuteBatch { operations } => {
+                Self::process_execute_batch(program_id, accounts, operations)
+            }
+            VaultInstruction::AddAddressBookEntry { address, label } => {
+                Self::process_add_address_book_entry(program_id, accounts, address, label)
+            }
+            VaultInstruction::RemoveAddressBookEntry { address } => {
+                Self::process_remove_address_book_entry(program_id, accounts, address)
+            }
+            VaultInstruction::SetRecipientPolicy { policy } => {
+                Self::process_set_recipient_policy(program_id, accounts, policy)
+            }
+            VaultInstruction::SetManagerQuota {
+                manager,
+                daily_limit,
+                per_transaction_limit,
+            } => Self::process_set_manager_quota(
+                program_id,
+                accounts,
+                manager,
+                daily_limit,
+                per_transaction_limit,
+            ),
+            VaultInstruction::UpsertRole { name, permissions } => {
+                msg!("Processing: Upsert Role {} permissions {:#x}", name, permissions);
+                Self::process_manage_roles(program_id, accounts, None, |vault| {
+                    vault.upsert_custom_role(name, permissions).map(|_| ())
+                })
+            }
+            VaultInstruction::RemoveRole { name } => {
+                msg!("Processing: Remove Role {}", name);
+                Self::process_manage_roles(program_id, accounts, None, |vault| {
+                    vault.remove_custom_role(&name)
+                })
+            }
+            VaultInstruction::AssignRole { account, role_name } => {
+                msg!("Processing: Assign Role {} to {}", role_name, account);
+                Self::process_manage_roles(program_id, accounts, Some(account), |vault| {
+                    vault.assign_role(account, &role_name)
+                })
+            }
+            VaultInstruction::AddEmergencyContact {
+                contact,
+                delay_seconds,
+            } => Self::process_add_emergency_contact(program_id, accounts, contact, delay_seconds),
+            VaultInstruction::RemoveEmergencyContact {
+                contact,
+                delay_seconds,
+            } => Self::process_remove_emergency_contact(
+                program_id,
+                accounts,
+                contact,
+                delay_seconds,
+            ),
+            VaultInstruction::ProposeAdmin { new_admin } => {
+                Self::process_propose_admin(program_id, accounts, new_admin)
+            }
+            VaultInstruction::AcceptAdmin => Self::process_accept_admin(program_id, accounts),
+            VaultInstruction::CancelAdminProposal => {
+                Self::process_cancel_admin_proposal(program_id, accounts)
+            }
+            VaultInstruction::CreateVestingSchedule {
+                beneficiary,
+                total_amount,
+                start_timestamp,
+                cliff_timestamp,
+                end_timestamp,
+            } => Self::process_create_vesting_schedule(
+                program_id,
+                accounts,
+                beneficiary,
+                total_amount,
+                start_timestamp,
+                cliff_timestamp,
+                end_timestamp,
+            ),
+            VaultInstruction::ClaimVested { schedule_id } => {
+                Self::process_claim_vested(program_id, accounts, schedule_id)
+            }
+            VaultInstruction::CreateRecurringPayment {
+                recipient,
+                amount,
+                interval_seconds,
+                total_payments,
+                first_payment_at,
+                delay_seconds,
+            } => Self::process_create_recurring_payment(
+                program_id,
+                accounts,
+                recipient,
+                amount,
+                interval_seconds,
+                total_payments,
+                first_payment_at,
+                delay_seconds,
+            ),
+            VaultInstruction::ExecuteRecurringPayment { payment_id } => {
+                Self::process_execute_recurring_payment(program_id, accounts, payment_id)
+            }
+            VaultInstruction::RefundDepositor { depositor, amount } => {
+                Self::process_refund_depositor(program_id, accounts, depositor, amount)
+            }
+            VaultInstruction::WithdrawDeposit { amount } => {
+                Self::process_withdraw_deposit(program_id, accounts, amount)
+            }
+            VaultInstruction::SetDeadManSwitch { config } => {
+                Self::process_set_dead_man_switch(program_id, accounts, config)
+            }
+            VaultInstruction::ClaimInactiveVault => {
+                Self::process_claim_inactive_vault(program_id, accounts)
+            }
+            VaultInstruction::ExecuteInactivityClaim => {
+                Self::process_execute_inactivity_claim(program_id, accounts)
+            }
+            VaultInstruction::Heartbeat => Self::process_heartbeat(program_id, accounts),
+            VaultInstruction::TimedPause {
+                reason,
+                duration_seconds,
+            } => Self::process_timed_pause(program_id, accounts, reason, duration_seconds),
+            VaultInstruction::ExtendPause { extension_seconds } => {
+                Self::process_extend_pause(program_id, accounts, extension_seconds)
+            }
+            VaultInstruction::RemoveTokenAsset { mint } => {
+                Self::process_remove_token_asset(program_id, accounts, mint)
+            }
+            VaultInstruction::ConfigureCompartment {
+                name,
+                managers,
+                daily_withdrawal_limit,
+            } => Self::process_configure_compartment(
+                program_id,
+                accounts,
+                name,
+                managers,
+                daily_withdrawal_limit,
+            ),
+            VaultInstruction::TransferBetweenCompartments { from, to, amount } => {
+                Self::process_transfer_between_compartments(program_id, accounts, from, to, amount)
+            }
+            VaultInstruction::WithdrawFromCompartment {
+                compartment,
+                amount,
+                recipient,
+            } => Self::process_withdraw_from_compartment(
+                program_id,
+                accounts,
+                compartment,
+                amount,
+                recipient,
+            ),
+            VaultInstruction::ProposeAdminRecovery { new_admin } => {
+                Self::process_propose_admin_recovery(program_id, accounts, new_admin)
+            }
+            VaultInstruction::ApproveAdminRecovery { new_admin } => {
+                Self::process_approve_admin_recovery(program_id, accounts, new_admin)
+            }
+            VaultInstruction::CancelAdminRecovery => {
+                Self::process_cancel_admin_recovery(program_id, accounts)
+            }
+            VaultInstruction::ExecuteAdminRecovery => {
+                Self::process_execute_admin_recovery(program_id, accounts)
+            }
+            VaultInstruction::SetRecoveryConfig {
+                threshold,
+                delay_seconds,
+            } => Self::process_set_recovery_config(program_id, accounts, threshold, delay_seconds),
+            VaultInstruction::RejectPendingOperation { operation_id } => {
+                Self::process_reject_pending_operation(program_id, accounts, operation_id)
+            }
+            VaultInstruction::CloseExpiredOperation { operation_id } => {
+                Self::process_close_expired_operation(program_id, accounts, operation_id)
+            }
+            VaultInstruction::SetMultisigPolicy { policy } => {
+                Self::process_set_multisig_policy(program_id, accounts, policy)
+            }
+            VaultInstruction::RevokeRole { account, role_name } => {
+                msg!("Processing: Revoke Role {} from {}", role_name, account);
+                Self::process_manage_roles(program_id, accounts, Some(account), |vault| {
+                    vault.revoke_role(&account, &role_name)
+                })
+            }
         }
     }
 
@@ -96,8 +314,43 @@ impl Processor {
         config: SecurityConfig,
     ) -> ProgramResult {
         msg!("Processing: Initialize Vault");
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_vault_initialization(admin.key, &config)?;
+        Validator::validate_account_not_initialized(vault_account)?;
+
+        let mut vault = SecureVault::new(*admin.key);
+        // Los tramos no pueden exigir más peso del que suman los firmantes iniciales
+        let total_weight = vault.total_signing_weight();
+        if config
+            .withdrawal_delay_tiers
+            .iter()
+            .any(|tier| tier.required_weight as u32 > total_weight)
+        {
+            return Err(VaultError::InvalidSecurityConfig.into());
+        }
+
+        let seeds = vault.get_seeds();
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::create_pda_account(
+            admin,
+            vault_account,
+            system_program,
+            program_id,
+            &seeds,
+            SecureVault::MAX_SIZE,
+        )?;
+        let bump = Utils::verify_pda(vault_account.key, &seeds, program_id)?;
+        vault.set_bump(bump);
+        vault.config = config;
+        Self::store_vault(vault_account, &vault)?;
+
+        msg!("Vault {} initialized for admin {}", vault_account.key, admin.key);
+        Ok(())
     }
 
     /// Procesar depósito
@@ -107,8 +360,91 @@ impl Processor {
         amount: u64,
     ) -> ProgramResult {
         msg!("Processing: Deposit {}", amount);
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let depositor = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let source_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let ledger_account = next_account_info(account_info_iter).ok();
+
+        Validator::validate_signer(depositor)?;
+        Validator::validate_signer(source_account)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_not_paused(&vault, current_timestamp)?;
+        Validator::validate_amount(amount, Some(MIN_DEPOSIT_AMOUNT), None)?;
+        // Sin registro el depósito no es reembolsable: con reembolsos activos es obligatorio
+        if ledger_account.is_none() && vault.config.refundable_mode {
+            msg!("Refundable vault requires the depositor ledger account");
+            return Err(VaultError::InvalidAccount.into());
+        }
+
+        invoke(
+            &system_instruction::transfer(source_account.key, vault_account.key, amount),
+            &[
+                source_account.clone(),
+                vault_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        if let Some(ledger_account) = ledger_account {
+            Self::record_depositor_deposit(
+                program_id,
+                depositor,
+                vault_account,
+                source_account,
+                system_program,
+                ledger_account,
+                amount,
+            )?;
+        }
+
+        vault.total_balance = Validator::validate_no_overflow(vault.total_balance, amount)?;
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        Ok(())
+    }
+
+    /// Registro por depositante: base para reembolsos (se crea en el primer depósito)
+    fn record_depositor_deposit<'a>(
+        program_id: &Pubkey,
+        depositor: &AccountInfo<'a>,
+        vault_account: &AccountInfo<'a>,
+        source_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        ledger_account: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        let mut ledger = if ledger_account.data_is_empty() {
+            let seeds = DepositorLedger::get_seeds(vault_account.key, depositor.key);
+            let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+            Utils::create_pda_account(
+                source_account,
+                ledger_account,
+                system_program,
+                program_id,
+                &seeds,
+                DepositorLedger::MAX_SIZE,
+            )?;
+            let bump = Utils::verify_pda(ledger_account.key, &seeds, program_id)?;
+            DepositorLedger::new(*vault_account.key, *depositor.key, bump)
+        } else {
+            Self::load_depositor_ledger(
+                program_id,
+                vault_account.key,
+                depositor.key,
+                ledger_account,
+            )?
+        };
+        ledger
+            .record_deposit(amount)
+            .map_err(|_| VaultError::ArithmeticOverflow)?;
+        Utils::serialize_to_account(ledger_account, &ledger)?;
+        Ok(())
     }
 
     /// Procesar retiro
@@ -119,8 +455,180 @@ impl Processor {
         recipient: Pubkey,
     ) -> ProgramResult {
         msg!("Processing: Withdraw {} to {}", amount, recipient);
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let performer = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let recipient_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        let address_book_account = next_account_info(account_info_iter).ok();
+
+        Validator::validate_signer(performer)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[performer, vault_account, recipient_account],
+        )?;
+        if *recipient_account.key != recipient {
+            return Err(VaultError::InvalidAccount.into());
+        }
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let address_book = Self::load_address_book_for_policy(
+            program_id,
+            vault_account.key,
+            &vault,
+            address_book_account,
+        )?;
+        if SecurityManager::requires_time_lock(&vault, amount)
+            || Self::is_time_locked_recipient(&vault, address_book.as_ref(), &recipient)
+        {
+            return Self::schedule_tiered_withdraw(
+                program_id,
+                accounts,
+                &vault,
+                address_book.as_ref(),
+                amount,
+                recipient,
+            );
+        }
+        let current_timestamp = Utils::get_current_timestamp()?;
+        let price_feed_account = Self::find_price_feed(&vault, &accounts[4..]);
+        let price = Self::load_price_feed(&vault, price_feed_account, current_timestamp)?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Self::apply_withdraw(
+            &mut vault,
+            performer.key,
+            amount,
+            &recipient,
+            address_book.as_ref(),
+            price.as_ref(),
+            current_timestamp,
+        )?;
+
+        Utils::transfer_lamports(vault_account, recipient_account, amount)?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::Withdraw,
+            *performer.key,
+            Some(amount),
+            Some(recipient),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Convertir un retiro con tramo de delay, o hacia un destinatario no listado bajo
+    /// `TimeLockUnlisted`, en operación pendiente con el mayor delay aplicable
+    fn schedule_tiered_withdraw(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        vault: &SecureVault,
+        address_book: Option<&AddressBook>,
+        amount: u64,
+        recipient: Pubkey,
+    ) -> ProgramResult {
+        let mut delay_seconds = SecurityManager::calculate_required_delay(
+            vault,
+            &OperationType::Withdraw,
+            Some(amount),
+        );
+        if let RecipientPolicy::TimeLockUnlisted { min_delay_seconds } = vault.recipient_policy {
+            if Validator::validate_allowlisted_recipient(vault, address_book, &recipient).is_err() {
+                delay_seconds = delay_seconds.max(min_delay_seconds);
+            }
+        }
+        msg!("Withdrawal scheduled as time-locked operation ({}s delay)", delay_seconds);
+
+        let optional_accounts = &accounts[4..];
+        let (pending, system) =
+            Self::find_scheduling_accounts(program_id, accounts[1].key, vault, optional_accounts)?;
+
+        // Reordenar al layout de `CreateTimeLockOperation`; el address book conserva su
+        // posición y el resto de cuentas opcionales se buscan por dirección
+        let mut timelock_accounts = vec![
+            accounts[0].clone(),
+            accounts[1].clone(),
+            pending.clone(),
+            accounts[3].clone(),
+            system.clone(),
+        ];
+        timelock_accounts.extend(optional_accounts.iter().cloned());
+        Self::process_create_timelock_operation(
+            program_id,
+            &timelock_accounts,
+            OperationType::Withdraw,
+            amount,
+            recipient,
+            delay_seconds,
+        )
+    }
+
+    /// Convertir un retiro de tokens hacia un destinatario no listado bajo `TimeLockUnlisted`
+    /// en operación pendiente `WithdrawToken` con el delay mínimo de la política
+    fn schedule_unlisted_token_withdraw(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        vault: &SecureVault,
+        amount: u64,
+    ) -> ProgramResult {
+        let mut delay_seconds = SecurityManager::calculate_required_delay(
+            vault,
+            &OperationType::WithdrawToken,
+            Some(amount),
+        );
+        if let RecipientPolicy::TimeLockUnlisted { min_delay_seconds } = vault.recipient_policy {
+            delay_seconds = delay_seconds.max(min_delay_seconds);
+        }
+        msg!("Token withdrawal scheduled as time-locked operation ({}s delay)", delay_seconds);
+
+        let address_book = accounts.get(7).ok_or(VaultError::InvalidAccount)?;
+        let (pending, system) =
+            Self::find_scheduling_accounts(program_id, accounts[1].key, vault, &accounts[8..])?;
+
+        // Layout de `CreateTimeLockOperation`; la token account destino va entre las opcionales
+        let timelock_accounts = vec![
+            accounts[0].clone(),
+            accounts[1].clone(),
+            pending.clone(),
+            accounts[5].clone(),
+            system.clone(),
+            address_book.clone(),
+            accounts[3].clone(),
+        ];
+        Self::process_create_timelock_operation(
+            program_id,
+            &timelock_accounts,
+            OperationType::WithdrawToken,
+            amount,
+            *accounts[3].key,
+            delay_seconds,
+        )
+    }
+
+    /// Buscar por dirección la PDA de la próxima operación pendiente y el system program
+    fn find_scheduling_accounts<'a, 'b>(
+        program_id: &Pubkey,
+        vault_key: &Pubkey,
+        vault: &SecureVault,
+        optional_accounts: &'b [AccountInfo<'a>],
+    ) -> Result<(&'b AccountInfo<'a>, &'b AccountInfo<'a>), VaultError> {
+        let operation_id = Validator::validate_no_overflow(vault.pending_operation_count, 1)?;
+        let seeds = VaultUtils::generate_operation_seeds(vault_key, operation_id);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        let (pending_key, _) = Pubkey::find_program_address(&seeds, program_id);
+        let pending = Self::find_account(optional_accounts, &pending_key).ok_or_else(|| {
+            msg!("Time-locked withdrawal requires pending operation account {}", pending_key);
+            VaultError::InvalidAccount
+        })?;
+        let system = Self::find_account(optional_accounts, &system_program::id())
+            .ok_or(VaultError::InvalidAccount)?;
+        Ok((pending, system))
     }
 
     /// Procesar pausa de emergencia
@@ -129,8 +637,108 @@ impl Processor {
         accounts: &[AccountInfo],
     ) -> ProgramResult {
         msg!("Processing: Emergency Pause");
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        // La pausa indefinida tiene su propio permiso; el resto usa `TimedPause`
+        Validator::validate_authorization(&vault, admin.key, &OperationType::IndefinitePause)?;
+
+        vault.is_paused = true;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::EmergencyPause,
+            *admin.key,
+            None,
+            None,
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Pausa temporal con motivo (admin o emergency contacts); se reanuda sola al expirar
+    fn process_timed_pause(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        reason: PauseReason,
+        duration_seconds: i64,
+    ) -> ProgramResult {
+        msg!("Processing: Timed Pause {:?} for {}s", reason, duration_seconds);
+        let account_info_iter = &mut accounts.iter();
+        let performer = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(performer)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[performer, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_authorization(&vault, performer.key, &OperationType::EmergencyPause)?;
+        Validator::validate_not_paused(&vault, current_timestamp)?;
+        if duration_seconds <= 0 || duration_seconds > MAX_TIMED_PAUSE_SECONDS {
+            return Err(VaultError::InvalidTimestamp.into());
+        }
+        vault
+            .start_timed_pause(*performer.key, reason, duration_seconds, current_timestamp)
+            .map_err(|e| {
+                msg!("Timed pause rejected: {}", e);
+                VaultError::PauseCooldownActive
+            })?;
+
+        // Flujo de emergencia: no cuenta como actividad ni para el rate limiting
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        let pause = vault.timed_pause.as_ref().ok_or(VaultError::InvalidVaultState)?;
+        AuditManager::log_timed_pause(audit_account, vault.operation_count, *performer.key, pause)?;
+        Ok(())
+    }
+
+    /// Extender la pausa temporal vigente (solo admin)
+    fn process_extend_pause(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        extension_seconds: i64,
+    ) -> ProgramResult {
+        msg!("Processing: Extend Pause by {}s", extension_seconds);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::ExtendPause)?;
+        vault
+            .extend_timed_pause(extension_seconds, current_timestamp)
+            .map_err(|e| {
+                msg!("Pause extension rejected: {}", e);
+                VaultError::InvalidOperationStatus
+            })?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        let pause = vault.timed_pause.as_ref().ok_or(VaultError::InvalidVaultState)?;
+        AuditManager::log_timed_pause(audit_account, operation_id, *admin.key, pause)?;
+        Ok(())
     }
 
     /// Procesar reanudación
@@ -139,8 +747,41 @@ impl Processor {
         accounts: &[AccountInfo],
     ) -> ProgramResult {
         msg!("Processing: Resume");
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::Resume)?;
+        // La pausa del disyuntor solo se levanta con una operación `Resume` firmada por quórum
+        if vault.circuit_breaker.trip.is_some() {
+            return Err(VaultError::CircuitBreakerTripped.into());
+        }
+        if !vault.is_paused_at(current_timestamp) {
+            return Err(VaultError::InvalidOperationStatus.into());
+        }
+
+        vault.resume(current_timestamp);
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::Resume,
+            *admin.key,
+            None,
+            None,
+            0,
+        )?;
+        Ok(())
     }
 
     /// Procesar agregar manager
@@ -150,8 +791,33 @@ impl Processor {
         new_manager: Pubkey,
     ) -> ProgramResult {
         msg!("Processing: Add Manager {}", new_manager);
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Self::apply_add_manager(&mut vault, admin.key, new_manager)?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::AddManager,
+            *admin.key,
+            None,
+            Some(new_manager),
+            0,
+        )?;
+        Ok(())
     }
 
     /// Procesar remover manager
@@ -161,8 +827,46 @@ impl Processor {
         manager_to_remove: Pubkey,
     ) -> ProgramResult {
         msg!("Processing: Remove Manager {}", manager_to_remove);
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Self::apply_remove_manager(&mut vault, admin.key, &manager_to_remove)?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::RemoveManager,
+            *admin.key,
+            None,
+            Some(manager_to_remove),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Procesar actualización de configuración de un cliente v1: completa `new_config`
+    /// con los campos vigentes que v1 no conoce
+    fn process_update_config_v1(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_config: SecurityConfigV1,
+    ) -> ProgramResult {
+        let vault_account = accounts.get(1).ok_or(ProgramError::NotEnoughAccountKeys)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        let vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        Self::process_update_config(program_id, accounts, new_config.apply_to(vault.config))
     }
 
     /// Procesar actualización de configuración
@@ -172,8 +876,33 @@ impl Processor {
         new_config: SecurityConfig,
     ) -> ProgramResult {
         msg!("Processing: Update Security Config");
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Self::apply_update_config(&mut vault, admin.key, new_config)?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::UpdateConfig,
+            *admin.key,
+            None,
+            None,
+            0,
+        )?;
+        Ok(())
     }
 
     /// Procesar transferencia de admin
@@ -183,8 +912,165 @@ impl Processor {
         new_admin: Pubkey,
     ) -> ProgramResult {
         msg!("Processing: Transfer Admin to {}", new_admin);
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        let new_admin_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_signer(new_admin_account)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[admin, vault_account, new_admin_account],
+        )?;
+        if *new_admin_account.key != new_admin {
+            return Err(VaultError::InvalidAccount.into());
+        }
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::TransferAdmin)?;
+
+        vault
+            .rotate_admin(new_admin)
+            .map_err(|_| VaultError::InvalidPubkey)?;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_operation_with_status(
+            audit_account,
+            operation_id,
+            OperationType::TransferAdmin,
+            *admin.key,
+            Some(new_admin),
+            OperationStatus::Executed,
+        )?;
+        Ok(())
+    }
+
+    /// Proponer un nuevo admin; el cambio ocurre cuando la nueva clave acepta
+    fn process_propose_admin(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_admin: Pubkey,
+    ) -> ProgramResult {
+        msg!("Processing: Propose Admin {}", new_admin);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::TransferAdmin)?;
+
+        vault
+            .propose_admin(new_admin, current_timestamp)
+            .map_err(|_| VaultError::InvalidPubkey)?;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_operation_with_status(
+            audit_account,
+            operation_id,
+            OperationType::TransferAdmin,
+            *admin.key,
+            Some(new_admin),
+            OperationStatus::Pending,
+        )?;
+        Ok(())
+    }
+
+    /// Aceptar la administración propuesta (firma la nueva clave)
+    fn process_accept_admin(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        msg!("Processing: Accept Admin");
+        let account_info_iter = &mut accounts.iter();
+        let new_admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(new_admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[new_admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        let pending = vault
+            .pending_admin
+            .clone()
+            .ok_or(VaultError::OperationNotFound)?;
+        if pending.new_admin != *new_admin.key {
+            return Err(VaultError::Unauthorized.into());
+        }
+        if current_timestamp > pending.expires_at {
+            return Err(VaultError::OperationExpired.into());
+        }
+
+        let old_admin = vault
+            .accept_admin(new_admin.key, current_timestamp)
+            .map_err(|_| VaultError::InvalidPubkey)?;
+        msg!("Admin rotated from {} to {}", old_admin, new_admin.key);
+
+        // Aceptar no cuenta para el rate limiting, solo consume un id de auditoría
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_operation_with_status(
+            audit_account,
+            vault.operation_count,
+            OperationType::TransferAdmin,
+            *new_admin.key,
+            Some(old_admin),
+            OperationStatus::Executed,
+        )?;
+        Ok(())
+    }
+
+    /// Cancelar la transferencia de admin propuesta
+    fn process_cancel_admin_proposal(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Processing: Cancel Admin Proposal");
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::TransferAdmin)?;
+
+        let pending = vault
+            .pending_admin
+            .take()
+            .ok_or(VaultError::OperationNotFound)?;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_operation_with_status(
+            audit_account,
+            operation_id,
+            OperationType::TransferAdmin,
+            *admin.key,
+            Some(pending.new_admin),
+            OperationStatus::Rejected,
+        )?;
+        Ok(())
     }
 
     /// Procesar creación de operación con delay
@@ -197,74 +1083,2784 @@ impl Processor {
         delay_seconds: i64,
     ) -> ProgramResult {
         msg!("Processing: Create TimeLock Operation");
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let requester = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let pending_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let address_book_account = next_account_info(account_info_iter).ok();
+
+        Validator::validate_signer(requester)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[requester, vault_account, pending_account, system_program],
+        )?;
+        Validator::validate_account_not_initialized(pending_account)?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_not_paused_for(&vault, &operation_type, current_timestamp)?;
+        Validator::validate_authorization(&vault, requester.key, &operation_type)?;
+
+        let price = match operation_type {
+            OperationType::Withdraw => {
+                let price_feed_account = Self::find_price_feed(&vault, &accounts[5..]);
+                Self::load_price_feed(&vault, price_feed_account, current_timestamp)?
+            }
+            _ => None,
+        };
+        let mut required_delay =
+            SecurityManager::calculate_required_delay(&vault, &operation_type, Some(amount));
+        match operation_type {
+            OperationType::Withdraw => {
+                Validator::validate_amount(amount, Some(1), None)?;
+                Validator::validate_sufficient_balance(&vault, &Asset::Native, amount)?;
+
+                // Destinatarios no listados: rechazados o con delay extendido según política
+                let address_book = Self::load_address_book_for_policy(
+                    program_id,
+                    vault_account.key,
+                    &vault,
+                    address_book_account,
+                )?;
+                if Validator::validate_allowlisted_recipient(
+                    &vault,
+                    address_book.as_ref(),
+                    &target_account,
+                )
+                .is_err()
+                {
+                    match vault.recipient_policy {
+                        RecipientPolicy::TimeLockUnlisted { min_delay_seconds } => {
+                            required_delay = required_delay.max(min_delay_seconds);
+                        }
+                        _ => return Err(VaultError::RecipientNotAllowlisted.into()),
+                    }
+                }
+            }
+            // `target_account` es la token account destino; el mint queda fijado por ella
+            OperationType::WithdrawToken => {
+                let token_account = Self::find_account(&accounts[5..], &target_account)
+                    .ok_or(VaultError::InvalidTokenAccount)?;
+                let destination = Validator::unpack_token_account(token_account)?;
+                Validator::validate_amount(amount, Some(1), None)?;
+                Validator::validate_sufficient_balance(
+                    &vault,
+                    &Asset::Token(destination.mint),
+                    amount,
+                )?;
+                let address_book = Self::load_address_book_for_policy(
+                    program_id,
+                    vault_account.key,
+                    &vault,
+                    address_book_account,
+                )?;
+                if Validator::validate_allowlisted_recipient(
+                    &vault,
+                    address_book.as_ref(),
+                    &destination.owner,
+                )
+                .is_err()
+                {
+                    match vault.recipient_policy {
+                        RecipientPolicy::TimeLockUnlisted { min_delay_seconds } => {
+                            required_delay = required_delay.max(min_delay_seconds);
+                        }
+                        _ => return Err(VaultError::RecipientNotAllowlisted.into()),
+                    }
+                }
+            }
+            OperationType::AddManager | OperationType::RemoveManager => {}
+            // El calendario se valida al ejecutar la aprobación
+            OperationType::RecurringPayment => {}
+            // Levantar el disyuntor exige quórum de firmas
+            OperationType::Resume => {
+                if vault.circuit_breaker.trip.is_none() {
+                    return Err(VaultError::InvalidOperationType.into());
+                }
+            }
+            OperationType::AddEmergencyContact | OperationType::RemoveEmergencyContact => {
+                Validator::validate_emergency_contact_change(
+                    &vault,
+                    &operation_type,
+                    &target_account,
+                )?;
+            }
+            _ => return Err(VaultError::InvalidOperationType.into()),
+        }
+        if delay_seconds < required_delay {
+            return Err(VaultError::DelayTooShort.into());
+        }
+        // La ventana de ejecución debe abrirse antes de que la operación expire
+        if delay_seconds >= OPERATION_EXPIRY_HOURS * 3600 {
+            return Err(VaultError::InvalidTimestamp.into());
+        }
+
+        let operation_id =
+            Validator::validate_no_overflow(vault.pending_operation_count, 1)?;
+        let seeds = VaultUtils::generate_operation_seeds(vault_account.key, operation_id);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::create_pda_account(
+            requester,
+            pending_account,
+            system_program,
+            program_id,
+            &seeds,
+            PendingOperation::MAX_SIZE,
+        )?;
+
+        let required_signatures =
+            SecurityManager::get_required_signers(&vault, &operation_type, Some(amount));
+        let required_weight =
+            SecurityManager::get_required_weight(
+                &vault,
+                &operation_type,
+                Some(amount),
+                price.as_ref(),
+            );
+        let mut operation = PendingOperation::new(
+            operation_id,
+            operation_type.clone(),
+            *requester.key,
+            amount,
+            target_account,
+            delay_seconds,
+            required_signatures,
+        )
+        .with_required_weight(required_weight);
+        operation.set_bump(Utils::verify_pda(pending_account.key, &seeds, program_id)?);
+        // Crear la operación cuenta como la firma del solicitante
+        operation.add_signature(*requester.key, vault.signer_weight(requester.key));
+        Utils::serialize_to_account(pending_account, &operation)?;
+
+        vault.pending_operation_count = operation_id;
+        let audit_operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_pending_operation_event(
+            audit_account,
+            audit_operation_id,
+            &operation,
+            *requester.key,
+        )?;
+        Ok(())
     }
 
-    /// Procesar firma de operación pendiente
-    fn process_sign_pending_operation(
+    /// Programar alta de contacto de emergencia (se ejecuta vía `ExecutePendingOperation`)
+    fn process_add_emergency_contact(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        operation_id: u64,
+        contact: Pubkey,
+        delay_seconds: i64,
     ) -> ProgramResult {
-        msg!("Processing: Sign Pending Operation {}", operation_id);
-        // TODO: Implementar
-        todo!()
+        msg!("Processing: Add Emergency Contact {}", contact);
+        Self::process_create_timelock_operation(
+            program_id,
+            accounts,
+            OperationType::AddEmergencyContact,
+            0,
+            contact,
+            delay_seconds,
+        )
     }
 
-    /// Procesar ejecución de operación pendiente
-    fn process_execute_pending_operation(
+    /// Programar baja de contacto de emergencia (se ejecuta vía `ExecutePendingOperation`)
+    fn process_remove_emergency_contact(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        contact: Pubkey,
+        delay_seconds: i64,
+    ) -> ProgramResult {
+        msg!("Processing: Remove Emergency Contact {}", contact);
+        Self::process_create_timelock_operation(
+            program_id,
+            accounts,
+            OperationType::RemoveEmergencyContact,
+            0,
+            contact,
+            delay_seconds,
+        )
+    }
+
+    /// Procesar firma de operación pendiente
+    fn process_sign_pending_operation(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
         operation_id: u64,
     ) -> ProgramResult {
-        msg!("Processing: Execute Pending Operation {}", operation_id);
-        // TODO: Implementar
-        todo!()
+        msg!("Processing: Sign Pending Operation {}", operation_id);
+        Self::vote_pending_operation(program_id, accounts, operation_id, true)
     }
 
-    /// Procesar cancelación de operación pendiente
-    fn process_cancel_pending_operation(
+    /// Procesar firma de operación pendiente con el layout v1 (sin cuenta del vault)
+    fn process_sign_pending_operation_v1(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
         operation_id: u64,
     ) -> ProgramResult {
-        msg!("Processing: Cancel Pending Operation {}", operation_id);
-        // TODO: Implementar
-        todo!()
+        msg!("Processing: Sign Pending Operation {} (v1)", operation_id);
+        let account_info_iter = &mut accounts.iter();
+        let signer = next_account_info(account_info_iter)?;
+        let pending_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(signer)?;
+        Validator::validate_audit_account(audit_account, program_id, &[signer, pending_account])?;
+        // Sin vault no se puede derivar la PDA: solo las operaciones pendientes tienen este
+        // tamaño entre las cuentas del programa
+        Validator::validate_account_owner(pending_account, program_id)?;
+        if pending_account.data_len() != PendingOperation::MAX_SIZE {
+            return Err(VaultError::InvalidAccount.into());
+        }
+        let mut operation: PendingOperation = Utils::deserialize_from_account(pending_account)?;
+        if operation.id != operation_id {
+            return Err(VaultError::OperationNotFound.into());
+        }
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_pending_operation(&operation, current_timestamp)?;
+
+        if !operation.add_legacy_signature(*signer.key) {
+            return Err(VaultError::Unauthorized.into());
+        }
+        msg!(
+            "Operation {} signed by {} of {} required: {:?}",
+            operation_id,
+            operation.received_signatures.len(),
+            operation.required_signatures.len(),
+            operation.status
+        );
+        Utils::serialize_to_account(pending_account, &operation)?;
+
+        // Sin vault no se consume un id de auditoría: la entrada usa el id de la operación
+        AuditManager::log_pending_operation_event(
+            audit_account,
+            operation.id,
+            &operation,
+            *signer.key,
+        )?;
+        Ok(())
     }
 
-    /// Procesar retiro de emergencia
-    fn process_emergency_withdraw(
+    /// Procesar rechazo de operación pendiente
+    fn process_reject_pending_operation(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        amount: u64,
-        emergency_recipient: Pubkey,
+        operation_id: u64,
     ) -> ProgramResult {
-        msg!("Processing: Emergency Withdraw {} to {}", amount, emergency_recipient);
-        // TODO: Implementar
-        todo!()
+        msg!("Processing: Reject Pending Operation {}", operation_id);
+        Self::vote_pending_operation(program_id, accounts, operation_id, false)
     }
 
-    /// Procesar obtención de información del vault
-    fn process_get_vault_info(
+    /// Registrar un voto (aprobación o rechazo) ponderado sobre una operación pendiente
+    fn vote_pending_operation(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
+        operation_id: u64,
+        approve: bool,
     ) -> ProgramResult {
-        msg!("Processing: Get Vault Info");
-        // TODO: Implementar
-        todo!()
+        let account_info_iter = &mut accounts.iter();
+        let signer = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let pending_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(signer)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[signer, vault_account, pending_account],
+        )?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let mut operation = Self::load_pending_operation(
+            program_id,
+            vault_account.key,
+            pending_account,
+            operation_id,
+        )?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_not_paused_for(
+            &vault,
+            &operation.operation_type,
+            current_timestamp,
+        )?;
+        Validator::validate_pending_operation(&operation, current_timestamp)?;
+
+        // El peso se toma de la política vigente al momento de votar
+        let weight = vault.signer_weight(signer.key);
+        let accepted = if approve {
+            operation.add_signature(*signer.key, weight)
+        } else {
+            operation.add_rejection(*signer.key, weight, vault.total_signing_weight())
+        };
+        if !accepted {
+            return Err(VaultError::Unauthorized.into());
+        }
+        msg!(
+            "Operation {} weight approve {} reject {} of {} required: {:?}",
+            operation_id,
+            operation.received_weight,
+            operation.rejected_weight,
+            operation.required_weight,
+            operation.status
+        );
+        Utils::serialize_to_account(pending_account, &operation)?;
+
+        // Votar no cuenta para el rate limiting, solo consume un id de auditoría
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_pending_operation_event(
+            audit_account,
+            vault.operation_count,
+            &operation,
+            *signer.key,
+        )?;
+        Ok(())
     }
 
-    /// Procesar obtención de logs de auditoría
-    fn process_get_audit_logs(
+    /// Procesar ejecución de operación pendiente
+    fn process_execute_pending_operation(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        from_operation_id: u64,
-        limit: u32,
+        operation_id: u64,
     ) -> ProgramResult {
-        msg!("Processing: Get Audit Logs from {} limit {}", from_operation_id, limit);
+        msg!("Processing: Execute Pending Operation {}", operation_id);
+        let account_info_iter = &mut accounts.iter();
+        let executor = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let pending_account = next_account_info(account_info_iter)?;
+        let target_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        // Opcional: los clientes v1 no envían al solicitante
+        let performer_account = next_account_info(account_info_iter).ok();
+        let price_feed_account = next_account_info(account_info_iter).ok();
+
+        Validator::validate_signer(executor)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        let mut related = vec![executor, vault_account, pending_account, target_account];
+        related.extend(performer_account);
+        Validator::validate_audit_account(audit_account, program_id, &related)?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let mut operation = Self::load_pending_operation(
+            program_id,
+            vault_account.key,
+            pending_account,
+            operation_id,
+        )?;
+        if *executor.key != operation.performer
+            && !operation.required_signatures.contains(executor.key)
+        {
+            return Err(VaultError::Unauthorized.into());
+        }
+        if *target_account.key != operation.target_account
+            || performer_account.is_some_and(|account| *account.key != operation.performer)
+        {
+            return Err(VaultError::InvalidAccount.into());
+        }
+
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_not_paused_for(
+            &vault,
+            &operation.operation_type,
+            current_timestamp,
+        )?;
+        Validator::validate_pending_operation(&operation, current_timestamp)?;
+        Validator::validate_ready_to_execute(&operation, current_timestamp)?;
+
+        // Las firmas reemplazan el umbral de multisig y las cuotas, no los límites diarios
+        match operation.operation_type {
+            OperationType::Withdraw => {
+                Validator::validate_window_limits(
+                    &vault,
+                    &Asset::Native,
+                    operation.amount,
+                    current_timestamp,
+                )?;
+                vault
+                    .can_withdraw(&Asset::Native, operation.amount, current_timestamp)
+                    .map_err(|e| {
+                        msg!("Withdrawal rejected: {}", e);
+                        VaultError::DailyLimitExceeded
+                    })?;
+                let price = Self::load_price_feed(&vault, price_feed_account, current_timestamp)?;
+                Validator::validate_quote_daily_limit(
+                    &vault,
+                    price.as_ref(),
+                    operation.amount,
+                    current_timestamp,
+                )?;
+                vault.reset_daily_limits_if_needed(current_timestamp);
+                vault
+                    .record_withdrawal(&Asset::Native, operation.amount)
+                    .map_err(|_| VaultError::InsufficientBalance)?;
+                Self::check_circuit_breaker(&mut vault, current_timestamp);
+                Utils::transfer_lamports(vault_account, target_account, operation.amount)?;
+            }
+            OperationType::WithdrawToken => {
+                Self::execute_token_withdraw(
+                    &mut vault,
+                    vault_account,
+                    target_account,
+                    accounts.get(6..).unwrap_or_default(),
+                    operation.amount,
+                    current_timestamp,
+                )?;
+            }
+            OperationType::AddManager => {
+                Self::add_manager_checked(&mut vault, operation.target_account)?;
+            }
+            OperationType::RemoveManager => {
+                Self::remove_manager_checked(&mut vault, &operation.target_account)?;
+            }
+            OperationType::RecurringPayment => {
+                let mut payment =
+                    Self::load_recurring_payment(program_id, vault_account.key, target_account)?;
+                if payment.amount != operation.amount {
+                    return Err(VaultError::InvalidAccount.into());
+                }
+                payment
+                    .approve()
+                    .map_err(|_| VaultError::InvalidOperationStatus)?;
+                Utils::serialize_to_account(target_account, &payment)?;
+            }
+            OperationType::AddEmergencyContact => {
+                vault
+                    .add_emergency_contact(operation.target_account)
+                    .map_err(|e| {
+                        msg!("Emergency contact rejected: {}", e);
+                        VaultError::InvalidEmergencyOperation
+                    })?;
+            }
+            OperationType::RemoveEmergencyContact => {
+                vault
+                    .remove_emergency_contact(&operation.target_account)
+                    .map_err(|e| {
+                        msg!("Emergency contact rejected: {}", e);
+                        VaultError::InvalidEmergencyOperation
+                    })?;
+            }
+            OperationType::Resume => {
+                vault
+                    .lift_circuit_breaker(current_timestamp)
+                    .map_err(|_| VaultError::InvalidOperationStatus)?;
+            }
+            _ => return Err(VaultError::InvalidOperationType.into()),
+        }
+
+        let audit_operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+        // Cerrar la operación evita re-ejecuciones y devuelve la renta al solicitante; sin
+        // él basta el estado final, y la renta se recupera con `CloseExpiredOperation`
+        match performer_account {
+            Some(performer_account) => Self::close_pending_operation(
+                pending_account,
+                &mut operation,
+                OperationStatus::Executed,
+                performer_account,
+            )?,
+            None => {
+                operation.status = OperationStatus::Executed;
+                Utils::serialize_to_account(pending_account, &operation)?;
+            }
+        }
+
+        AuditManager::log_pending_operation_event(
+            audit_account,
+            audit_operation_id,
+            &operation,
+            *executor.key,
+        )?;
+        Ok(())
+    }
+
+    /// Cerrar una operación expirada (sin permisos) y devolver la renta al solicitante
+    fn process_close_expired_operation(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        operation_id: u64,
+    ) -> ProgramResult {
+        msg!("Processing: Close Expired Operation {}", operation_id);
+        let account_info_iter = &mut accounts.iter();
+        let caller = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let pending_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        let performer_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(caller)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[vault_account, pending_account, performer_account],
+        )?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let mut operation = Self::load_pending_operation(
+            program_id,
+            vault_account.key,
+            pending_account,
+            operation_id,
+        )?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        if !operation.is_expired(current_timestamp, OPERATION_EXPIRY_HOURS) {
+            return Err(VaultError::InvalidTimestamp.into());
+        }
+
+        // Las rechazadas y las ejecutadas sin cerrar (layout v1) conservan su estado; las
+        // abiertas pasan a expiradas
+        let final_status = match operation.status {
+            OperationStatus::Pending | OperationStatus::Approved => OperationStatus::Expired,
+            OperationStatus::Rejected => OperationStatus::Rejected,
+            OperationStatus::Executed => OperationStatus::Executed,
+            OperationStatus::Expired => return Err(VaultError::OperationAlreadyExecuted.into()),
+        };
+        Self::close_pending_operation(
+            pending_account,
+            &mut operation,
+            final_status,
+            performer_account,
+        )?;
+
+        // Limpieza sin permisos: no cuenta para el rate limiting
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_pending_operation_event(
+            audit_account,
+            vault.operation_count,
+            &operation,
+            *caller.key,
+        )?;
+        Ok(())
+    }
+
+    /// Procesar cancelación de operación pendiente
+    fn process_cancel_pending_operation(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        operation_id: u64,
+    ) -> ProgramResult {
+        msg!("Processing: Cancel Pending Operation {}", operation_id);
+        // TODO: Implementar
+        todo!()
+    }
+
+    /// Procesar retiro de emergencia
+    fn process_emergency_withdraw(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+        emergency_recipient: Pubkey,
+    ) -> ProgramResult {
+        msg!("Processing: Emergency Withdraw {} to {}", amount, emergency_recipient);
+        // TODO: Implementar
+        todo!()
+    }
+
+    /// Procesar obtención de información del vault
+    fn process_get_vault_info(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Processing: Get Vault Info");
+        // TODO: Implementar
+        todo!()
+    }
+
+    /// Procesar obtención de logs de auditoría
+    fn process_get_audit_logs(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        from_operation_id: u64,
+        limit: u32,
+    ) -> ProgramResult {
+        msg!("Processing: Get Audit Logs from {} limit {}", from_operation_id, limit);
         // TODO: Implementar
         todo!()
     }
-} 
\ No newline at end of file
+
+    /// Procesar depósito de tokens SPL
+    fn process_deposit_token(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Processing: Deposit Token {}", amount);
+        let account_info_iter = &mut accounts.iter();
+        let depositor = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let source_token_account = next_account_info(account_info_iter)?;
+        let vault_token_account = next_account_info(account_info_iter)?;
+        let mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(depositor)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        if *token_program.key != spl_token::id() {
+            return Err(VaultError::IncorrectProgramId.into());
+        }
+        let expected_vault_token_account =
+            VaultUtils::get_vault_token_address(vault_account.key, mint.key);
+        if *vault_token_account.key != expected_vault_token_account {
+            return Err(VaultError::InvalidTokenAccount.into());
+        }
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_not_paused(&vault, current_timestamp)?;
+        Validator::validate_amount(amount, Some(1), None)?;
+        // Solo mints registrados por el admin: los depósitos no pueden ocupar entradas
+        if vault.get_token_balance(mint.key).is_none() {
+            msg!("Mint {} not registered in vault", mint.key);
+            return Err(VaultError::InvalidMintAccount.into());
+        }
+
+        let decimals = Validator::validate_mint_account(mint)?;
+        Validator::validate_token_account(source_token_account, mint.key, depositor.key)?;
+        Validator::validate_token_account(vault_token_account, mint.key, vault_account.key)?;
+
+        Utils::transfer_tokens(
+            token_program,
+            source_token_account,
+            mint,
+            vault_token_account,
+            depositor,
+            amount,
+            decimals,
+            None,
+        )?;
+
+        vault
+            .credit_token(*mint.key, amount)
+            .map_err(|_| VaultError::InvalidMintAccount)?;
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        Ok(())
+    }
+
+    /// Procesar retiro de tokens SPL
+    fn process_withdraw_token(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+        recipient: Pubkey,
+    ) -> ProgramResult {
+        msg!("Processing: Withdraw Token {} to {}", amount, recipient);
+        let account_info_iter = &mut accounts.iter();
+        let performer = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let vault_token_account = next_account_info(account_info_iter)?;
+        let recipient_token_account = next_account_info(account_info_iter)?;
+        let mint = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let address_book_account = next_account_info(account_info_iter).ok();
+
+        Validator::validate_signer(performer)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[
+                performer,
+                vault_account,
+                vault_token_account,
+                recipient_token_account,
+                mint,
+                token_program,
+            ],
+        )?;
+        if *token_program.key != spl_token::id() {
+            return Err(VaultError::IncorrectProgramId.into());
+        }
+        let expected_vault_token_account =
+            VaultUtils::get_vault_token_address(vault_account.key, mint.key);
+        if *vault_token_account.key != expected_vault_token_account {
+            return Err(VaultError::InvalidTokenAccount.into());
+        }
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let address_book = Self::load_address_book_for_policy(
+            program_id,
+            vault_account.key,
+            &vault,
+            address_book_account,
+        )?;
+        if Self::is_time_locked_recipient(&vault, address_book.as_ref(), &recipient) {
+            return Self::schedule_unlisted_token_withdraw(program_id, accounts, &vault, amount);
+        }
+        Validator::validate_allowlisted_recipient(&vault, address_book.as_ref(), &recipient)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_token_withdrawal_request(
+            &vault,
+            performer.key,
+            mint.key,
+            amount,
+            current_timestamp,
+        )?;
+
+        let decimals = Validator::validate_mint_account(mint)?;
+        Validator::validate_token_account(vault_token_account, mint.key, vault_account.key)?;
+        Validator::validate_token_account(recipient_token_account, mint.key, &recipient)?;
+
+        let signer_seeds = vault.get_signer_seeds();
+        let signer_seeds: Vec<&[u8]> = signer_seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::transfer_tokens(
+            token_program,
+            vault_token_account,
+            mint,
+            recipient_token_account,
+            vault_account,
+            amount,
+            decimals,
+            Some(&signer_seeds),
+        )?;
+
+        vault.reset_daily_limits_if_needed(current_timestamp);
+        vault
+            .record_withdrawal(&Asset::Token(*mint.key), amount)
+            .map_err(|_| VaultError::InsufficientBalance)?;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::WithdrawToken,
+            *performer.key,
+            Some(amount),
+            Some(recipient),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Procesar configuración de límites por activo
+    fn process_set_asset_limits(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        asset: Asset,
+        daily_withdrawal_limit: u64,
+        multisig_threshold: u64,
+    ) -> ProgramResult {
+        msg!(
+            "Processing: Set Asset Limits {:?} daily {} multisig {}",
+            asset,
+            daily_withdrawal_limit,
+            multisig_threshold
+        );
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Self::apply_set_asset_limits(
+            &mut vault,
+            admin.key,
+            &asset,
+            daily_withdrawal_limit,
+            multisig_threshold,
+        )?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        let target_account = match asset {
+            Asset::Native => None,
+            Asset::Token(mint) => Some(mint),
+        };
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::UpdateConfig,
+            *admin.key,
+            Some(daily_withdrawal_limit),
+            target_account,
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Procesar batch atómico de operaciones
+    fn process_execute_batch(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        operations: Vec<BatchOperation>,
+    ) -> ProgramResult {
+        msg!("Processing: Execute Batch of {} operations", operations.len());
+        if operations.is_empty() || operations.len() > MAX_BATCH_OPERATIONS {
+            return Err(VaultError::InvalidInstruction.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let performer = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_accounts = operations
+            .iter()
+            .map(|_| next_account_info(account_info_iter))
+            .collect::<Result<Vec<_>, _>>()?;
+        let recipient_accounts: Vec<&AccountInfo> = account_info_iter.collect();
+
+        Validator::validate_signer(performer)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        for (index, audit_account) in audit_accounts.iter().enumerate() {
+            let mut other_accounts = vec![performer, vault_account];
+            other_accounts.extend_from_slice(&audit_accounts[..index]);
+            other_accounts.extend_from_slice(&recipient_accounts);
+            Validator::validate_audit_account(audit_account, program_id, &other_accounts)?;
+        }
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let (address_book_address, _) =
+            Self::find_address_book_address(program_id, vault_account.key);
+        let address_book_account = recipient_accounts
+            .iter()
+            .find(|account| *account.key == address_book_address)
+            .copied();
+        let address_book = Self::load_address_book_for_policy(
+            program_id,
+            vault_account.key,
+            &vault,
+            address_book_account,
+        )?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        // El batch cuenta como una sola operación frente al rate limiting
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+
+        // Validar y aplicar todo sobre el estado en memoria: cualquier error aborta el batch
+        let mut transfers = Vec::new();
+        for operation in operations.iter() {
+            match operation {
+                BatchOperation::AddManager { new_manager } => {
+                    Self::apply_add_manager(&mut vault, performer.key, *new_manager)?
+                }
+                BatchOperation::RemoveManager { manager_to_remove } => {
+                    Self::apply_remove_manager(&mut vault, performer.key, manager_to_remove)?
+                }
+                BatchOperation::UpdateSecurityConfig { new_config } => {
+                    Self::apply_update_config(&mut vault, performer.key, new_config.clone())?
+                }
+                BatchOperation::UpdateSecurityConfigV1 { new_config } => {
+                    let new_config = new_config.apply_to(vault.config.clone());
+                    Self::apply_update_config(&mut vault, performer.key, new_config)?
+                }
+                BatchOperation::SetAssetLimits {
+                    asset,
+                    daily_withdrawal_limit,
+                    multisig_threshold,
+                } => Self::apply_set_asset_limits(
+                    &mut vault,
+                    performer.key,
+                    asset,
+                    *daily_withdrawal_limit,
+                    *multisig_threshold,
+                )?,
+                BatchOperation::Withdraw { amount, recipient } => {
+                    let recipient_account = recipient_accounts
+                        .iter()
+                        .find(|account| account.key == recipient)
+                        .ok_or(VaultError::InvalidAccount)?;
+                    // La configuración puede cambiar dentro del mismo batch
+                    let price_feed_account = vault.config.quote_limits.as_ref().and_then(|l| {
+                        recipient_accounts
+                            .iter()
+                            .find(|account| *account.key == l.price_feed)
+                            .copied()
+                    });
+                    let price =
+                        Self::load_price_feed(&vault, price_feed_account, current_timestamp)?;
+                    Self::apply_withdraw(
+                        &mut vault,
+                        performer.key,
+                        *amount,
+                        recipient,
+                        address_book.as_ref(),
+                        price.as_ref(),
+                        current_timestamp,
+                    )?;
+                    transfers.push((*recipient_account, *amount));
+                }
+            }
+        }
+
+        for (recipient_account, amount) in transfers {
+            Utils::transfer_lamports(vault_account, recipient_account, amount)?;
+        }
+
+        // Un id por operación; el primero identifica al grupo en el audit log
+        let correlation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        vault.operation_count = Validator::validate_no_overflow(
+            vault.operation_count,
+            operations.len() as u64 - 1,
+        )?;
+        Self::store_vault(vault_account, &vault)?;
+
+        for (index, (operation, audit_account)) in
+            operations.iter().zip(audit_accounts.iter()).enumerate()
+        {
+            let (amount, target_account) = match operation {
+                BatchOperation::AddManager { new_manager } => (None, Some(*new_manager)),
+                BatchOperation::RemoveManager { manager_to_remove } => {
+                    (None, Some(*manager_to_remove))
+                }
+                BatchOperation::UpdateSecurityConfig { .. }
+                | BatchOperation::UpdateSecurityConfigV1 { .. } => (None, None),
+                BatchOperation::SetAssetLimits {
+                    asset,
+                    daily_withdrawal_limit,
+                    ..
+                } => match asset {
+                    Asset::Native => (Some(*daily_withdrawal_limit), None),
+                    Asset::Token(mint) => (Some(*daily_withdrawal_limit), Some(*mint)),
+                },
+                BatchOperation::Withdraw { amount, recipient } => (Some(*amount), Some(*recipient)),
+            };
+            AuditManager::log_correlated_operation(
+                audit_account,
+                correlation_id + index as u64,
+                correlation_id,
+                operation.operation_type(),
+                *performer.key,
+                amount,
+                target_account,
+                0,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Agregar destinatario aprobado al address book (lo crea si no existe)
+    fn process_add_address_book_entry(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        address: Pubkey,
+        label: String,
+    ) -> ProgramResult {
+        msg!("Processing: Add Address Book Entry {} ({})", address, label);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let address_book_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[admin, vault_account, address_book_account, system_program],
+        )?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::AddAddressBookEntry)?;
+        if address == Pubkey::default() {
+            return Err(VaultError::InvalidPubkey.into());
+        }
+
+        let mut address_book = if address_book_account.data_is_empty() {
+            let seeds = AddressBook::get_seeds(vault_account.key);
+            let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+            Utils::create_pda_account(
+                admin,
+                address_book_account,
+                system_program,
+                program_id,
+                &seeds,
+                AddressBook::MAX_SIZE,
+            )?;
+            let bump = Utils::verify_pda(address_book_account.key, &seeds, program_id)?;
+            AddressBook::new(*vault_account.key, bump)
+        } else {
+            Self::load_address_book(program_id, vault_account.key, address_book_account)?
+        };
+
+        address_book
+            .add_entry(address, label, current_timestamp)
+            .map_err(|_| VaultError::InvalidAccount)?;
+        Utils::serialize_to_account(address_book_account, &address_book)?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::AddAddressBookEntry,
+            *admin.key,
+            None,
+            Some(address),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Remover destinatario del address book
+    fn process_remove_address_book_entry(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        address: Pubkey,
+    ) -> ProgramResult {
+        msg!("Processing: Remove Address Book Entry {}", address);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let address_book_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[admin, vault_account, address_book_account],
+        )?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(
+            &vault,
+            admin.key,
+            &OperationType::RemoveAddressBookEntry,
+        )?;
+
+        let mut address_book =
+            Self::load_address_book(program_id, vault_account.key, address_book_account)?;
+        address_book
+            .remove_entry(&address)
+            .map_err(|_| VaultError::RecipientNotAllowlisted)?;
+        Utils::serialize_to_account(address_book_account, &address_book)?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::RemoveAddressBookEntry,
+            *admin.key,
+            None,
+            Some(address),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Configurar la política para destinatarios fuera del address book
+    fn process_set_recipient_policy(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        policy: RecipientPolicy,
+    ) -> ProgramResult {
+        msg!("Processing: Set Recipient Policy {:?}", policy);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::UpdateConfig)?;
+        if let RecipientPolicy::TimeLockUnlisted { min_delay_seconds } = policy {
+            if min_delay_seconds <= vault.config.withdrawal_delay_seconds {
+                return Err(VaultError::DelayTooShort.into());
+            }
+            if min_delay_seconds >= OPERATION_EXPIRY_HOURS * 3600 {
+                return Err(VaultError::InvalidTimestamp.into());
+            }
+        }
+
+        vault.recipient_policy = policy;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::UpdateConfig,
+            *admin.key,
+            None,
+            None,
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Configurar cuota diaria y por transacción de un manager
+    fn process_set_manager_quota(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        manager: Pubkey,
+        daily_limit: u64,
+        per_transaction_limit: u64,
+    ) -> ProgramResult {
+        msg!(
+            "Processing: Set Manager Quota {} daily {} per transaction {}",
+            manager,
+            daily_limit,
+            per_transaction_limit
+        );
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::SetManagerQuota)?;
+        Validator::validate_manager_exists(&vault, &manager)?;
+        if per_transaction_limit > daily_limit {
+            return Err(VaultError::AmountTooLarge.into());
+        }
+
+        vault.reset_daily_limits_if_needed(current_timestamp);
+        vault
+            .set_manager_quota(manager, daily_limit, per_transaction_limit)
+            .map_err(|_| VaultError::ManagerNotFound)?;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::SetManagerQuota,
+            *admin.key,
+            Some(daily_limit),
+            Some(manager),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Proponer un nuevo admin (emergency contact)
+    fn process_propose_admin_recovery(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_admin: Pubkey,
+    ) -> ProgramResult {
+        msg!("Processing: Propose Admin Recovery {}", new_admin);
+        let (guardian, vault_account, audit_account) =
+            Self::admin_recovery_accounts(program_id, accounts)?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_authorization(&vault, guardian.key, &OperationType::AdminRecovery)?;
+        if !vault.emergency_contacts.contains(guardian.key) {
+            return Err(VaultError::EmergencyContactUnauthorized.into());
+        }
+        if vault.has_active_recovery(current_timestamp) {
+            return Err(VaultError::RecoveryInProgress.into());
+        }
+        if new_admin == *vault_account.key {
+            return Err(VaultError::InvalidPubkey.into());
+        }
+
+        vault
+            .propose_admin_recovery(*guardian.key, new_admin, current_timestamp)
+            .map_err(|_| VaultError::InvalidPubkey)?;
+        let status = Self::admin_recovery_status(&vault);
+        Self::log_admin_recovery_step(
+            vault_account,
+            audit_account,
+            &mut vault,
+            guardian,
+            Some(new_admin),
+            status,
+        )
+    }
+
+    /// Aprobar la propuesta de recuperación activa (emergency contact)
+    fn process_approve_admin_recovery(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_admin: Pubkey,
+    ) -> ProgramResult {
+        msg!("Processing: Approve Admin Recovery {}", new_admin);
+        let (guardian, vault_account, audit_account) =
+            Self::admin_recovery_accounts(program_id, accounts)?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_authorization(&vault, guardian.key, &OperationType::AdminRecovery)?;
+        if !vault.emergency_contacts.contains(guardian.key) {
+            return Err(VaultError::EmergencyContactUnauthorized.into());
+        }
+        if vault.admin_recovery.is_none() {
+            return Err(VaultError::OperationNotFound.into());
+        }
+        if !vault.has_active_recovery(current_timestamp) {
+            return Err(VaultError::OperationExpired.into());
+        }
+
+        vault
+            .approve_admin_recovery(*guardian.key, new_admin, current_timestamp)
+            .map_err(|e| {
+                msg!("Recovery approval rejected: {}", e);
+                VaultError::InvalidOperationType
+            })?;
+        let status = Self::admin_recovery_status(&vault);
+        Self::log_admin_recovery_step(
+            vault_account,
+            audit_account,
+            &mut vault,
+            guardian,
+            Some(new_admin),
+            status,
+        )
+    }
+
+    /// Cancelar la recuperación en curso (admin actual)
+    fn process_cancel_admin_recovery(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Processing: Cancel Admin Recovery");
+        let (admin, vault_account, audit_account) =
+            Self::admin_recovery_accounts(program_id, accounts)?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::CancelAdminRecovery)?;
+        let recovery = vault
+            .admin_recovery
+            .take()
+            .ok_or(VaultError::OperationNotFound)?;
+
+        Self::log_admin_recovery_step(
+            vault_account,
+            audit_account,
+            &mut vault,
+            admin,
+            Some(recovery.proposed_admin),
+            OperationStatus::Rejected,
+        )
+    }
+
+    /// Ejecutar la recuperación: quorum + time-lock cumplidos, firmada por el nuevo admin
+    fn process_execute_admin_recovery(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Processing: Execute Admin Recovery");
+        let (new_admin, vault_account, audit_account) =
+            Self::admin_recovery_accounts(program_id, accounts)?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        let recovery = vault
+            .admin_recovery
+            .clone()
+            .ok_or(VaultError::OperationNotFound)?;
+        if recovery.proposed_admin != *new_admin.key {
+            return Err(VaultError::Unauthorized.into());
+        }
+        // Umbral y contactos vigentes: pudieron cambiar durante el time-lock
+        if vault.current_recovery_approvals() < vault.recovery_threshold as usize {
+            return Err(VaultError::InsufficientSignatures.into());
+        }
+        match vault.recovery_executable_at() {
+            Some(executable_at) if current_timestamp >= executable_at => {}
+            _ => return Err(VaultError::TimeLockNotElapsed.into()),
+        }
+
+        let old_admin = vault
+            .rotate_admin(*new_admin.key)
+            .map_err(|_| VaultError::InvalidPubkey)?;
+        msg!("Admin rotated from {} to {}", old_admin, new_admin.key);
+        Self::log_admin_recovery_step(
+            vault_account,
+            audit_account,
+            &mut vault,
+            new_admin,
+            Some(*new_admin.key),
+            OperationStatus::Executed,
+        )
+    }
+
+    /// Configurar quorum y time-lock de la recuperación del admin
+    fn process_set_recovery_config(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        threshold: u8,
+        delay_seconds: i64,
+    ) -> ProgramResult {
+        msg!(
+            "Processing: Set Recovery Config threshold {} delay {}",
+            threshold,
+            delay_seconds
+        );
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::UpdateConfig)?;
+        if threshold == 0 || threshold as usize > MAX_EMERGENCY_CONTACTS {
+            return Err(VaultError::InvalidSecurityConfig.into());
+        }
+        if delay_seconds < MIN_RECOVERY_DELAY_SECONDS {
+            return Err(VaultError::DelayTooShort.into());
+        }
+
+        vault.recovery_threshold = threshold;
+        vault.recovery_delay_seconds = delay_seconds;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::UpdateConfig,
+            *admin.key,
+            Some(delay_seconds as u64),
+            None,
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Cuentas comunes de la recuperación: firmante, vault y audit log
+    fn admin_recovery_accounts<'a, 'b>(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<(&'a AccountInfo<'b>, &'a AccountInfo<'b>, &'a AccountInfo<'b>), ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+        let signer = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(signer)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[signer, vault_account])?;
+        Ok((signer, vault_account, audit_account))
+    }
+
+    /// Estado de la propuesta de recuperación para el audit log
+    fn admin_recovery_status(vault: &SecureVault) -> OperationStatus {
+        match vault.recovery_executable_at() {
+            Some(_) => OperationStatus::Approved,
+            None => OperationStatus::Pending,
+        }
+    }
+
+    /// Persistir el vault y auditar un paso de la recuperación
+    fn log_admin_recovery_step(
+        vault_account: &AccountInfo,
+        audit_account: &AccountInfo,
+        vault: &mut SecureVault,
+        performer: &AccountInfo,
+        target_account: Option<Pubkey>,
+        status: OperationStatus,
+    ) -> ProgramResult {
+        // Flujo de emergencia: no cuenta para el rate limiting, solo consume un id de auditoría
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, vault)?;
+
+        AuditManager::log_operation_with_status(
+            audit_account,
+            vault.operation_count,
+            OperationType::AdminRecovery,
+            *performer.key,
+            target_account,
+            status,
+        )?;
+        Ok(())
+    }
+
+    /// Configurar el dead-man's switch (admin)
+    fn process_set_dead_man_switch(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        config: Option<DeadManSwitch>,
+    ) -> ProgramResult {
+        msg!("Processing: Set Dead Man Switch {:?}", config);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::UpdateConfig)?;
+        if !config.iter().all(DeadManSwitch::is_valid) {
+            return Err(VaultError::InvalidSecurityConfig.into());
+        }
+
+        vault.dead_man_switch = config;
+        vault.inactivity_claim = None;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::UpdateConfig,
+            *admin.key,
+            None,
+            None,
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Iniciar el reclamo de un vault inactivo (beneficiario o emergency contact)
+    fn process_claim_inactive_vault(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Processing: Claim Inactive Vault");
+        let (claimant, vault_account, audit_account) =
+            Self::admin_recovery_accounts(program_id, accounts)?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        if !vault.is_inactive(current_timestamp) {
+            return Err(VaultError::VaultNotInactive.into());
+        }
+        vault
+            .start_inactivity_claim(*claimant.key, current_timestamp)
+            .map_err(|e| {
+                msg!("Inactivity claim rejected: {}", e);
+                VaultError::Unauthorized
+            })?;
+
+        // No actualiza `last_operation_timestamp`: el reclamo no cuenta como actividad
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_operation_with_status(
+            audit_account,
+            vault.operation_count,
+            OperationType::InactivityClaim,
+            *claimant.key,
+            Some(*claimant.key),
+            OperationStatus::Pending,
+        )?;
+        Ok(())
+    }
+
+    /// Ejecutar el reclamo por inactividad una vez cumplido el time-lock
+    fn process_execute_inactivity_claim(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Processing: Execute Inactivity Claim");
+        let account_info_iter = &mut accounts.iter();
+        let caller = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let claimant_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(caller)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[caller, vault_account, claimant_account],
+        )?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        // Una pausa o el disyuntor congelan también la liquidación del reclamo
+        if vault.circuit_breaker.trip.is_some() {
+            return Err(VaultError::CircuitBreakerTripped.into());
+        }
+        Validator::validate_not_paused(&vault, current_timestamp)?;
+        let claim = vault
+            .inactivity_claim
+            .clone()
+            .ok_or(VaultError::OperationNotFound)?;
+        if claim.claimant != *claimant_account.key {
+            return Err(VaultError::InvalidAccount.into());
+        }
+        // Cualquier operación autorizada posterior al reclamo lo invalida
+        if vault.last_operation_timestamp > claim.started_at {
+            return Err(VaultError::VaultNotInactive.into());
+        }
+        if current_timestamp < claim.executable_at {
+            return Err(VaultError::TimeLockNotElapsed.into());
+        }
+
+        // Solo SOL: los tokens custodiados quedan en las ATAs del vault
+        let amount = vault.sweep_inactive_funds();
+        msg!("Inactivity claim paid {} lamports to {}", amount, claim.claimant);
+        Utils::transfer_lamports(vault_account, claimant_account, amount)?;
+        Utils::verify_rent_exempt(vault_account, vault_account.data_len())?;
+
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_operation_with_status(
+            audit_account,
+            vault.operation_count,
+            OperationType::InactivityClaim,
+            *caller.key,
+            Some(claim.claimant),
+            OperationStatus::Executed,
+        )?;
+        Ok(())
+    }
+
+    /// Heartbeat de un firmante autorizado: reinicia el timeout y cancela el reclamo en curso
+    fn process_heartbeat(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        msg!("Processing: Heartbeat");
+        let account_info_iter = &mut accounts.iter();
+        let signer = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(signer)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[signer, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        // Sin rate limiting: la señal de vida nunca debe quedar bloqueada
+        Validator::validate_authorization(&vault, signer.key, &OperationType::Heartbeat)?;
+
+        let cancelled = vault.inactivity_claim.take();
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        match cancelled {
+            Some(claim) => {
+                msg!("Inactivity claim by {} cancelled", claim.claimant);
+                AuditManager::log_operation_with_status(
+                    audit_account,
+                    operation_id,
+                    OperationType::InactivityClaim,
+                    *signer.key,
+                    Some(claim.claimant),
+                    OperationStatus::Rejected,
+                )?;
+            }
+            None => {
+                AuditManager::log_successful_operation(
+                    audit_account,
+                    operation_id,
+                    OperationType::Heartbeat,
+                    *signer.key,
+                    None,
+                    None,
+                    0,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Configurar pesos de firmantes y umbrales del multisig
+    fn process_set_multisig_policy(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        policy: MultisigPolicy,
+    ) -> ProgramResult {
+        msg!("Processing: Set Multisig Policy {:?}", policy);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::UpdateConfig)?;
+        SecurityManager::validate_multisig_policy(&vault, &policy)?;
+
+        vault.multisig_policy = policy;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::UpdateConfig,
+            *admin.key,
+            None,
+            None,
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Aplicar un cambio sobre la tabla de roles personalizados
+    fn process_manage_roles<F>(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        target_account: Option<Pubkey>,
+        apply: F,
+    ) -> ProgramResult
+    where
+        F: FnOnce(&mut SecureVault) -> Result<(), &'static str>,
+    {
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::ManageRoles)?;
+
+        apply(&mut vault).map_err(|e| {
+            msg!("Role update rejected: {}", e);
+            VaultError::InvalidRole
+        })?;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::ManageRoles,
+            *admin.key,
+            None,
+            target_account,
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Crear calendario de vesting (PDA) y reservar su total en el vault
+    #[allow(clippy::too_many_arguments)]
+    fn process_create_vesting_schedule(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        beneficiary: Pubkey,
+        total_amount: u64,
+        start_timestamp: i64,
+        cliff_timestamp: i64,
+        end_timestamp: i64,
+    ) -> ProgramResult {
+        msg!("Processing: Create Vesting Schedule {} for {}", total_amount, beneficiary);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let schedule_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[admin, vault_account, schedule_account, system_program],
+        )?;
+        Validator::validate_account_not_initialized(schedule_account)?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_not_paused(&vault, current_timestamp)?;
+        Validator::validate_authorization(
+            &vault,
+            admin.key,
+            &OperationType::CreateVestingSchedule,
+        )?;
+
+        let schedule_id = Validator::validate_no_overflow(vault.vesting_schedule_count, 1)?;
+        let mut schedule = VestingSchedule::new(
+            *vault_account.key,
+            schedule_id,
+            beneficiary,
+            total_amount,
+            start_timestamp,
+            cliff_timestamp,
+            end_timestamp,
+        )
+        .map_err(|e| {
+            msg!("Vesting schedule rejected: {}", e);
+            VaultError::InvalidTimestamp
+        })?;
+        vault
+            .reserve_vesting(total_amount)
+            .map_err(|_| VaultError::InsufficientBalance)?;
+
+        let seeds = VestingSchedule::get_seeds(vault_account.key, schedule_id);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::create_pda_account(
+            admin,
+            schedule_account,
+            system_program,
+            program_id,
+            &seeds,
+            VestingSchedule::MAX_SIZE,
+        )?;
+        schedule.set_bump(Utils::verify_pda(schedule_account.key, &seeds, program_id)?);
+        Utils::serialize_to_account(schedule_account, &schedule)?;
+
+        vault.vesting_schedule_count = schedule_id;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::CreateVestingSchedule,
+            *admin.key,
+            Some(total_amount),
+            Some(beneficiary),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Reclamar lo liberado de un calendario de vesting (firma el beneficiario)
+    fn process_claim_vested(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        schedule_id: u64,
+    ) -> ProgramResult {
+        msg!("Processing: Claim Vested {}", schedule_id);
+        let account_info_iter = &mut accounts.iter();
+        let beneficiary = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let schedule_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(beneficiary)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[beneficiary, vault_account, schedule_account],
+        )?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_not_paused(&vault, current_timestamp)?;
+        let mut schedule = Self::load_vesting_schedule(
+            program_id,
+            vault_account.key,
+            schedule_account,
+            schedule_id,
+        )?;
+        if schedule.beneficiary != *beneficiary.key {
+            return Err(VaultError::Unauthorized.into());
+        }
+
+        let amount = schedule.claimable_amount(current_timestamp);
+        if amount == 0 {
+            return Err(VaultError::AmountTooSmall.into());
+        }
+        schedule
+            .record_claim(amount)
+            .map_err(|_| VaultError::ArithmeticOverflow)?;
+        vault
+            .record_vesting_claim(amount)
+            .map_err(|_| VaultError::InsufficientBalance)?;
+
+        Utils::transfer_lamports(vault_account, beneficiary, amount)?;
+        Utils::serialize_to_account(schedule_account, &schedule)?;
+
+        // Los reclamos no cuentan para el rate limiting, solo consumen un id de auditoría
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            vault.operation_count,
+            OperationType::ClaimVested,
+            *beneficiary.key,
+            Some(amount),
+            Some(*schedule_account.key),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Crear pago recurrente y su operación pendiente de aprobación por multisig
+    #[allow(clippy::too_many_arguments)]
+    fn process_create_recurring_payment(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        recipient: Pubkey,
+        amount: u64,
+        interval_seconds: i64,
+        total_payments: u32,
+        first_payment_at: i64,
+        delay_seconds: i64,
+    ) -> ProgramResult {
+        msg!(
+            "Processing: Create Recurring Payment {} x{} to {}",
+            amount,
+            total_payments,
+            recipient
+        );
+        let account_info_iter = &mut accounts.iter();
+        let requester = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let pending_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let payment_account = next_account_info(account_info_iter)?;
+        let address_book_account = next_account_info(account_info_iter).ok();
+
+        Validator::validate_signer(requester)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[requester, vault_account, pending_account, system_program, payment_account],
+        )?;
+        Validator::validate_account_not_initialized(payment_account)?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_not_paused(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, requester.key, &OperationType::RecurringPayment)?;
+
+        // Destinatarios no listados: rechazados o con delay mínimo según política
+        let address_book = Self::load_address_book_for_policy(
+            program_id,
+            vault_account.key,
+            &vault,
+            address_book_account,
+        )?;
+        if Validator::validate_allowlisted_recipient(&vault, address_book.as_ref(), &recipient)
+            .is_err()
+        {
+            match vault.recipient_policy {
+                RecipientPolicy::TimeLockUnlisted { min_delay_seconds } => {
+                    if delay_seconds < min_delay_seconds {
+                        return Err(VaultError::DelayTooShort.into());
+                    }
+                }
+                _ => return Err(VaultError::RecipientNotAllowlisted.into()),
+            }
+        }
+
+        let payment_id = Validator::validate_no_overflow(vault.recurring_payment_count, 1)?;
+        let mut payment = RecurringPayment::new(
+            *vault_account.key,
+            payment_id,
+            recipient,
+            amount,
+            interval_seconds,
+            total_payments,
+            first_payment_at,
+        )
+        .map_err(|e| {
+            msg!("Recurring payment rejected: {}", e);
+            VaultError::InvalidOperationType
+        })?;
+
+        let seeds = RecurringPayment::get_seeds(vault_account.key, payment_id);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::create_pda_account(
+            requester,
+            payment_account,
+            system_program,
+            program_id,
+            &seeds,
+            RecurringPayment::MAX_SIZE,
+        )?;
+        payment.set_bump(Utils::verify_pda(payment_account.key, &seeds, program_id)?);
+        Utils::serialize_to_account(payment_account, &payment)?;
+
+        vault.recurring_payment_count = payment_id;
+        Self::store_vault(vault_account, &vault)?;
+
+        // La aprobación sigue el flujo normal de operaciones pendientes
+        Self::process_create_timelock_operation(
+            program_id,
+            &accounts[..5],
+            OperationType::RecurringPayment,
+            amount,
+            *payment_account.key,
+            delay_seconds,
+        )
+    }
+
+    /// Disparar un pago recurrente vencido (sin permisos: cualquiera puede firmar)
+    fn process_execute_recurring_payment(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        payment_id: u64,
+    ) -> ProgramResult {
+        msg!("Processing: Execute Recurring Payment {}", payment_id);
+        let account_info_iter = &mut accounts.iter();
+        let caller = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let payment_account = next_account_info(account_info_iter)?;
+        let recipient_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        let price_feed_account = next_account_info(account_info_iter).ok();
+
+        Validator::validate_signer(caller)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[vault_account, payment_account, recipient_account],
+        )?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        Validator::validate_not_paused(&vault, current_timestamp)?;
+        let mut payment =
+            Self::load_recurring_payment(program_id, vault_account.key, payment_account)?;
+        if payment.id != payment_id {
+            return Err(VaultError::OperationNotFound.into());
+        }
+        if *recipient_account.key != payment.recipient {
+            return Err(VaultError::InvalidAccount.into());
+        }
+
+        if !payment.is_due(current_timestamp) {
+            return Err(VaultError::TimeLockNotElapsed.into());
+        }
+
+        // Cada pago consume los límites de retiro como cualquier otro retiro
+        Validator::validate_window_limits(
+            &vault,
+            &Asset::Native,
+            payment.amount,
+            current_timestamp,
+        )?;
+        vault
+            .can_withdraw(&Asset::Native, payment.amount, current_timestamp)
+            .map_err(|e| {
+                msg!("Recurring payment rejected: {}", e);
+                VaultError::DailyLimitExceeded
+            })?;
+        let price = Self::load_price_feed(&vault, price_feed_account, current_timestamp)?;
+        Validator::validate_quote_daily_limit(
+            &vault,
+            price.as_ref(),
+            payment.amount,
+            current_timestamp,
+        )?;
+        vault.reset_daily_limits_if_needed(current_timestamp);
+        vault
+            .record_withdrawal(&Asset::Native, payment.amount)
+            .map_err(|_| VaultError::InsufficientBalance)?;
+        Self::check_circuit_breaker(&mut vault, current_timestamp);
+        payment
+            .record_payment()
+            .map_err(|_| VaultError::ArithmeticOverflow)?;
+
+        Utils::transfer_lamports(vault_account, recipient_account, payment.amount)?;
+        Utils::serialize_to_account(payment_account, &payment)?;
+
+        // Disparo sin permisos: no cuenta para el rate limiting
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_correlated_operation(
+            audit_account,
+            vault.operation_count,
+            payment.id,
+            OperationType::RecurringPayment,
+            *caller.key,
+            Some(payment.amount),
+            Some(payment.recipient),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Devolver fondos a un depositante (aprobado por el admin)
+    fn process_refund_depositor(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        depositor: Pubkey,
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Processing: Refund Depositor {} amount {}", depositor, amount);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let ledger_account = next_account_info(account_info_iter)?;
+        let depositor_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[admin, vault_account, ledger_account, depositor_account],
+        )?;
+        if *depositor_account.key != depositor {
+            return Err(VaultError::InvalidAccount.into());
+        }
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::RefundDepositor)?;
+        let mut ledger =
+            Self::load_depositor_ledger(program_id, vault_account.key, &depositor, ledger_account)?;
+
+        Self::apply_depositor_refund(&mut vault, &mut ledger, amount, current_timestamp)?;
+        Utils::transfer_lamports(vault_account, depositor_account, amount)?;
+        Utils::serialize_to_account(ledger_account, &ledger)?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::RefundDepositor,
+            *admin.key,
+            Some(amount),
+            Some(depositor),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Retiro del propio balance por el depositante (solo en modo reembolsable)
+    fn process_withdraw_deposit(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Processing: Withdraw Deposit {}", amount);
+        let account_info_iter = &mut accounts.iter();
+        let depositor = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let ledger_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(depositor)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[depositor, vault_account, ledger_account],
+        )?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        if !vault.config.refundable_mode {
+            return Err(VaultError::Unauthorized.into());
+        }
+        let current_timestamp = Utils::get_current_timestamp()?;
+        let mut ledger = Self::load_depositor_ledger(
+            program_id,
+            vault_account.key,
+            depositor.key,
+            ledger_account,
+        )?;
+
+        Self::apply_depositor_refund(&mut vault, &mut ledger, amount, current_timestamp)?;
+        Utils::transfer_lamports(vault_account, depositor, amount)?;
+        Utils::serialize_to_account(ledger_account, &ledger)?;
+
+        // Autoservicio: no cuenta para el rate limiting, solo consume un id de auditoría
+        vault.operation_count = Validator::validate_no_overflow(vault.operation_count, 1)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            vault.operation_count,
+            OperationType::DepositorWithdraw,
+            *depositor.key,
+            Some(amount),
+            Some(*depositor.key),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Aplicar una devolución al depositante: balance propio, límites diarios del vault
+    fn apply_depositor_refund(
+        vault: &mut SecureVault,
+        ledger: &mut DepositorLedger,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        Validator::validate_not_paused(vault, current_timestamp)?;
+        Validator::validate_amount(amount, Some(1), None)?;
+        if amount > ledger.balance() {
+            return Err(VaultError::InsufficientBalance);
+        }
+        Validator::validate_sufficient_balance(vault, &Asset::Native, amount)?;
+        Validator::validate_window_limits(vault, &Asset::Native, amount, current_timestamp)?;
+
+        vault.reset_daily_limits_if_needed(current_timestamp);
+        vault
+            .record_withdrawal(&Asset::Native, amount)
+            .map_err(|_| VaultError::InsufficientBalance)?;
+        Self::check_circuit_breaker(vault, current_timestamp);
+        ledger
+            .record_refund(amount)
+            .map_err(|_| VaultError::InsufficientBalance)
+    }
+
+    /// Cargar y verificar el registro (PDA) de un depositante
+    fn load_depositor_ledger(
+        program_id: &Pubkey,
+        vault: &Pubkey,
+        depositor: &Pubkey,
+        ledger_account: &AccountInfo,
+    ) -> Result<DepositorLedger, VaultError> {
+        let seeds = DepositorLedger::get_seeds(vault, depositor);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::verify_pda(ledger_account.key, &seeds, program_id)?;
+        Validator::validate_account_owner(ledger_account, program_id)?;
+
+        let ledger: DepositorLedger = Utils::deserialize_from_account(ledger_account)?;
+        if ledger.discriminator != DEPOSITOR_LEDGER_DISCRIMINATOR
+            || ledger.vault != *vault
+            || ledger.depositor != *depositor
+        {
+            return Err(VaultError::InvalidAccount);
+        }
+        Ok(ledger)
+    }
+
+    /// Crear o reconfigurar un compartimento del vault
+    fn process_configure_compartment(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        name: String,
+        managers: Vec<Pubkey>,
+        daily_withdrawal_limit: u64,
+    ) -> ProgramResult {
+        msg!("Processing: Configure Compartment {}", name);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::ManageCompartments)?;
+
+        vault.reset_daily_limits_if_needed(current_timestamp);
+        vault
+            .configure_compartment(name, managers, daily_withdrawal_limit)
+            .map_err(|e| {
+                msg!("Compartment rejected: {}", e);
+                VaultError::InvalidSecurityConfig
+            })?;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::ManageCompartments,
+            *admin.key,
+            Some(daily_withdrawal_limit),
+            None,
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Mover balance entre compartimentos (o desde/hacia el fondo general)
+    fn process_transfer_between_compartments(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        from: Option<String>,
+        to: Option<String>,
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Processing: Transfer Between Compartments {:?} -> {:?}: {}", from, to, amount);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::ManageCompartments)?;
+        Validator::validate_amount(amount, Some(1), None)?;
+
+        vault
+            .transfer_between_compartments(from.as_deref(), to.as_deref(), amount)
+            .map_err(|e| {
+                msg!("Compartment transfer rejected: {}", e);
+                VaultError::InsufficientBalance
+            })?;
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::ManageCompartments,
+            *admin.key,
+            Some(amount),
+            None,
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Retirar desde un compartimento (managers del compartimento o admin)
+    fn process_withdraw_from_compartment(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        compartment: String,
+        amount: u64,
+        recipient: Pubkey,
+    ) -> ProgramResult {
+        msg!("Processing: Withdraw {} from {} to {}", amount, compartment, recipient);
+        let account_info_iter = &mut accounts.iter();
+        let performer = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let recipient_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+        let address_book_account = next_account_info(account_info_iter).ok();
+        let price_feed_account = next_account_info(account_info_iter).ok();
+
+        Validator::validate_signer(performer)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(
+            audit_account,
+            program_id,
+            &[performer, vault_account, recipient_account],
+        )?;
+        if *recipient_account.key != recipient {
+            return Err(VaultError::InvalidAccount.into());
+        }
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let address_book = Self::load_address_book_for_policy(
+            program_id,
+            vault_account.key,
+            &vault,
+            address_book_account,
+        )?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        let price = Self::load_price_feed(&vault, price_feed_account, current_timestamp)?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_compartment_withdrawal(
+            &vault,
+            performer.key,
+            &compartment,
+            amount,
+            current_timestamp,
+        )?;
+        Validator::validate_quote_limits(&vault, price.as_ref(), amount, current_timestamp)?;
+        Validator::validate_allowlisted_recipient(&vault, address_book.as_ref(), &recipient)?;
+
+        vault.reset_daily_limits_if_needed(current_timestamp);
+        vault
+            .record_compartment_withdrawal(&compartment, amount)
+            .map_err(|_| VaultError::InsufficientBalance)?;
+        Self::check_circuit_breaker(&mut vault, current_timestamp);
+        vault
+            .record_manager_spend(performer.key, amount)
+            .map_err(|_| VaultError::ArithmeticOverflow)?;
+        Utils::transfer_lamports(vault_account, recipient_account, amount)?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::CompartmentWithdraw,
+            *performer.key,
+            Some(amount),
+            Some(recipient),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Cargar el vault verificando owner, discriminador, PDA (re-derivado de sus semillas y
+    /// bump) y consistencia del estado
+    fn load_vault(
+        program_id: &Pubkey,
+        vault_account: &AccountInfo,
+    ) -> Result<SecureVault, VaultError> {
+        Validator::validate_account_owner(vault_account, program_id)?;
+        let vault: SecureVault = Utils::deserialize_from_account(vault_account)?;
+        if vault.discriminator != VAULT_DISCRIMINATOR {
+            return Err(VaultError::InvalidAccount);
+        }
+
+        let seeds = vault.get_signer_seeds();
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        let expected = Pubkey::create_program_address(&seeds, program_id)
+            .map_err(|_| VaultError::PDAAddressMismatch)?;
+        if expected != *vault_account.key {
+            msg!("Vault PDA expected {}, got {}", expected, vault_account.key);
+            return Err(VaultError::PDAAddressMismatch);
+        }
+        if !vault.is_valid_state() {
+            return Err(VaultError::InvalidVaultState);
+        }
+        Ok(vault)
+    }
+
+    /// Persistir el vault solo si tras la mutación sigue cumpliendo `is_valid_state`
+    fn store_vault(vault_account: &AccountInfo, vault: &SecureVault) -> Result<(), VaultError> {
+        if !vault.is_valid_state() {
+            msg!("Vault state invariants violated, aborting");
+            return Err(VaultError::InvalidVaultState);
+        }
+        Utils::serialize_to_account(vault_account, vault)
+    }
+
+    /// Cargar y verificar un pago recurrente (PDA) del vault
+    fn load_recurring_payment(
+        program_id: &Pubkey,
+        vault: &Pubkey,
+        payment_account: &AccountInfo,
+    ) -> Result<RecurringPayment, VaultError> {
+        Validator::validate_account_owner(payment_account, program_id)?;
+        let payment: RecurringPayment = Utils::deserialize_from_account(payment_account)?;
+        if payment.discriminator != RECURRING_PAYMENT_DISCRIMINATOR || payment.vault != *vault {
+            return Err(VaultError::InvalidAccount);
+        }
+
+        let seeds = RecurringPayment::get_seeds(vault, payment.id);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::verify_pda(payment_account.key, &seeds, program_id)?;
+        Ok(payment)
+    }
+
+    /// Cargar y verificar un calendario de vesting (PDA) del vault
+    fn load_vesting_schedule(
+        program_id: &Pubkey,
+        vault: &Pubkey,
+        schedule_account: &AccountInfo,
+        schedule_id: u64,
+    ) -> Result<VestingSchedule, VaultError> {
+        let seeds = VestingSchedule::get_seeds(vault, schedule_id);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::verify_pda(schedule_account.key, &seeds, program_id)?;
+        Validator::validate_account_owner(schedule_account, program_id)?;
+
+        let schedule: VestingSchedule = Utils::deserialize_from_account(schedule_account)?;
+        if schedule.discriminator != VESTING_DISCRIMINATOR
+            || schedule.vault != *vault
+            || schedule.id != schedule_id
+        {
+            return Err(VaultError::InvalidAccount);
+        }
+        Ok(schedule)
+    }
+
+    /// Cargar y verificar una operación pendiente (PDA) del vault
+    fn load_pending_operation(
+        program_id: &Pubkey,
+        vault: &Pubkey,
+        pending_account: &AccountInfo,
+        operation_id: u64,
+    ) -> Result<PendingOperation, VaultError> {
+        let seeds = VaultUtils::generate_operation_seeds(vault, operation_id);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::verify_pda(pending_account.key, &seeds, program_id)?;
+        Validator::validate_account_owner(pending_account, program_id)?;
+        if pending_account.data_is_empty() || pending_account.lamports() == 0 {
+            return Err(VaultError::OperationNotFound);
+        }
+
+        let operation: PendingOperation = Utils::deserialize_from_account(pending_account)?;
+        if operation.id != operation_id {
+            return Err(VaultError::OperationNotFound);
+        }
+        Ok(operation)
+    }
+
+    /// Cerrar una operación pendiente con su estado final, devolviendo la renta
+    fn close_pending_operation(
+        pending_account: &AccountInfo,
+        operation: &mut PendingOperation,
+        final_status: OperationStatus,
+        rent_recipient: &AccountInfo,
+    ) -> Result<(), VaultError> {
+        if *rent_recipient.key != operation.performer {
+            return Err(VaultError::InvalidAccount);
+        }
+
+        // El estado final impide reutilizar la cuenta aunque se le vuelvan a depositar lamports
+        operation.status = final_status;
+        Utils::serialize_to_account(pending_account, operation)?;
+        Utils::transfer_lamports(pending_account, rent_recipient, pending_account.lamports())
+    }
+
+    /// Derivar el PDA del address book de un vault
+    fn find_address_book_address(program_id: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+        let seeds = AddressBook::get_seeds(vault);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::find_program_address(&seeds, program_id)
+    }
+
+    /// Cargar y verificar el address book (PDA) de un vault
+    fn load_address_book(
+        program_id: &Pubkey,
+        vault: &Pubkey,
+        address_book_account: &AccountInfo,
+    ) -> Result<AddressBook, VaultError> {
+        let seeds = AddressBook::get_seeds(vault);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::verify_pda(address_book_account.key, &seeds, program_id)?;
+        Validator::validate_account_owner(address_book_account, program_id)?;
+
+        let address_book: AddressBook = Utils::deserialize_from_account(address_book_account)?;
+        if address_book.discriminator != ADDRESS_BOOK_DISCRIMINATOR
+            || address_book.vault != *vault
+        {
+            return Err(VaultError::InvalidAccount);
+        }
+        Ok(address_book)
+    }
+
+    /// Cargar el address book solo si la política del vault lo exige
+    fn load_address_book_for_policy(
+        program_id: &Pubkey,
+        vault_key: &Pubkey,
+        vault: &SecureVault,
+        address_book_account: Option<&AccountInfo>,
+    ) -> Result<Option<AddressBook>, VaultError> {
+        if vault.recipient_policy == RecipientPolicy::Disabled {
+            return Ok(None);
+        }
+
+        // Sin address book creado, ningún destinatario está aprobado
+        match address_book_account {
+            Some(account) if !account.data_is_empty() => {
+                Self::load_address_book(program_id, vault_key, account).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Destinatario no listado que la política solo admite vía operación pendiente
+    fn is_time_locked_recipient(
+        vault: &SecureVault,
+        address_book: Option<&AddressBook>,
+        recipient: &Pubkey,
+    ) -> bool {
+        matches!(vault.recipient_policy, RecipientPolicy::TimeLockUnlisted { .. })
+            && Validator::validate_allowlisted_recipient(vault, address_book, recipient).is_err()
+    }
+
+    /// Buscar por dirección una cuenta opcional entre las cuentas restantes
+    fn find_account<'a, 'b>(
+        accounts: &'b [AccountInfo<'a>],
+        key: &Pubkey,
+    ) -> Option<&'b AccountInfo<'a>> {
+        accounts.iter().find(|account| account.key == key)
+    }
+
+    /// Buscar por dirección la cuenta de precio de los límites en moneda de cotización
+    fn find_price_feed<'a, 'b>(
+        vault: &SecureVault,
+        accounts: &'b [AccountInfo<'a>],
+    ) -> Option<&'b AccountInfo<'a>> {
+        let limits = vault.config.quote_limits.as_ref()?;
+        Self::find_account(accounts, &limits.price_feed)
+    }
+
+    /// Ejecutar un retiro de tokens aprobado hacia `destination` (token account destino);
+    /// el mint, la ATA del vault y el token program se buscan por dirección
+    fn execute_token_withdraw<'a>(
+        vault: &mut SecureVault,
+        vault_account: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        token_accounts: &[AccountInfo<'a>],
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        let mint_key = Validator::unpack_token_account(destination)?.mint;
+        let asset = Asset::Token(mint_key);
+        let mint = Self::find_account(token_accounts, &mint_key)
+            .ok_or(VaultError::InvalidMintAccount)?;
+        let vault_token_account = Self::find_account(
+            token_accounts,
+            &VaultUtils::get_vault_token_address(vault_account.key, &mint_key),
+        )
+        .ok_or(VaultError::InvalidTokenAccount)?;
+        let token_program = Self::find_account(token_accounts, &spl_token::id())
+            .ok_or(VaultError::IncorrectProgramId)?;
+
+        Validator::validate_window_limits(vault, &asset, amount, current_timestamp)?;
+        vault
+            .can_withdraw(&asset, amount, current_timestamp)
+            .map_err(|e| {
+                msg!("Token withdrawal rejected: {}", e);
+                VaultError::DailyLimitExceeded
+            })?;
+        let decimals = Validator::validate_mint_account(mint)?;
+        Validator::validate_token_account(vault_token_account, &mint_key, vault_account.key)?;
+
+        vault.reset_daily_limits_if_needed(current_timestamp);
+        vault
+            .record_withdrawal(&asset, amount)
+            .map_err(|_| VaultError::InsufficientBalance)?;
+
+        let signer_seeds = vault.get_signer_seeds();
+        let signer_seeds: Vec<&[u8]> = signer_seeds.iter().map(|s| s.as_slice()).collect();
+        Utils::transfer_tokens(
+            token_program,
+            vault_token_account,
+            mint,
+            destination,
+            vault_account,
+            amount,
+            decimals,
+            Some(&signer_seeds),
+        )
+    }
+
+    /// Leer el precio del oráculo solo si el vault tiene límites en moneda de cotización
+    fn load_price_feed(
+        vault: &SecureVault,
+        price_feed_account: Option<&AccountInfo>,
+        current_timestamp: i64,
+    ) -> Result<Option<PriceFeed>, VaultError> {
+        let limits = match &vault.config.quote_limits {
+            Some(limits) => limits,
+            None => return Ok(None),
+        };
+        let account = price_feed_account.ok_or(VaultError::InvalidOracle)?;
+        Validator::validate_price_feed(account, limits, current_timestamp).map(Some)
+    }
+
+    /// Evaluar el disyuntor tras un retiro nativo
+    fn check_circuit_breaker(vault: &mut SecureVault, current_timestamp: i64) {
+        if !vault.trip_circuit_breaker_if_needed(current_timestamp) {
+            return;
+        }
+        if let Some(trip) = &vault.circuit_breaker.trip {
+            msg!(
+                "Circuit breaker tripped ({:?}): outflow {} of TVL {}, vault paused",
+                trip.anomaly_type,
+                trip.window_outflow,
+                trip.tvl
+            );
+        }
+    }
+
+    /// Registrar la operación en los contadores del vault y devolver su id
+    fn record_operation(
+        vault: &mut SecureVault,
+        current_timestamp: i64,
+    ) -> Result<u64, VaultError> {
+        vault
+            .record_operation(current_timestamp)
+            .map_err(|_| VaultError::ArithmeticOverflow)
+    }
+
+    /// Agregar manager sobre el estado en memoria
+    fn apply_add_manager(
+        vault: &mut SecureVault,
+        performer: &Pubkey,
+        new_manager: Pubkey,
+    ) -> Result<(), VaultError> {
+        Validator::validate_authorization(vault, performer, &OperationType::AddManager)?;
+        Self::add_manager_checked(vault, new_manager)
+    }
+
+    /// Agregar manager respetando el máximo configurado
+    fn add_manager_checked(vault: &mut SecureVault, new_manager: Pubkey) -> Result<(), VaultError> {
+        Validator::validate_manager_not_exists(vault, &new_manager)?;
+        if vault.managers.len() >= vault.config.max_managers as usize {
+            return Err(VaultError::MaxManagersReached);
+        }
+
+        vault
+            .add_manager(new_manager)
+            .map_err(|_| VaultError::InvalidAccount)
+    }
+
+    /// Remover manager sobre el estado en memoria
+    fn apply_remove_manager(
+        vault: &mut SecureVault,
+        performer: &Pubkey,
+        manager_to_remove: &Pubkey,
+    ) -> Result<(), VaultError> {
+        Validator::validate_authorization(vault, performer, &OperationType::RemoveManager)?;
+        Self::remove_manager_checked(vault, manager_to_remove)
+    }
+
+    /// Remover manager validando que los umbrales de multisig sigan siendo alcanzables
+    fn remove_manager_checked(
+        vault: &mut SecureVault,
+        manager_to_remove: &Pubkey,
+    ) -> Result<(), VaultError> {
+        Validator::validate_manager_exists(vault, manager_to_remove)?;
+        if vault.managers.len() == 1 {
+            return Err(VaultError::CannotRemoveLastManager);
+        }
+
+        vault
+            .remove_manager(*manager_to_remove)
+            .map_err(|_| VaultError::ManagerNotFound)?;
+        let policy = vault.multisig_policy.clone();
+        SecurityManager::validate_multisig_policy(vault, &policy)
+    }
+
+    /// Reemplazar la configuración de seguridad sobre el estado en memoria
+    fn apply_update_config(
+        vault: &mut SecureVault,
+        performer: &Pubkey,
+        new_config: SecurityConfig,
+    ) -> Result<(), VaultError> {
+        Validator::validate_authorization(vault, performer, &OperationType::UpdateConfig)?;
+        Validator::validate_security_config(&new_config)?;
+        if vault.managers.len() > new_config.max_managers as usize {
+            return Err(VaultError::InvalidSecurityConfig);
+        }
+        let total_weight = vault.total_signing_weight();
+        if new_config
+            .withdrawal_delay_tiers
+            .iter()
+            .any(|tier| tier.required_weight as u32 > total_weight)
+        {
+            return Err(VaultError::InvalidSecurityConfig);
+        }
+
+        vault.config = new_config;
+        Ok(())
+    }
+
+    /// Configurar límites de un activo sobre el estado en memoria
+    fn apply_set_asset_limits(
+        vault: &mut SecureVault,
+        performer: &Pubkey,
+        asset: &Asset,
+        daily_withdrawal_limit: u64,
+        multisig_threshold: u64,
+    ) -> Result<(), VaultError> {
+        Validator::validate_authorization(vault, performer, &OperationType::UpdateConfig)?;
+        Validator::validate_amount(daily_withdrawal_limit, Some(1), None)?;
+        Validator::validate_amount(multisig_threshold, Some(1), None)?;
+
+        vault
+            .set_asset_limits(asset, daily_withdrawal_limit, multisig_threshold)
+            .map_err(|_| VaultError::InvalidMintAccount)
+    }
+
+    /// Dar de baja un mint sin balance para liberar su entrada
+    fn process_remove_token_asset(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        mint: Pubkey,
+    ) -> ProgramResult {
+        msg!("Processing: Remove Token Asset {}", mint);
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let vault_account = next_account_info(account_info_iter)?;
+        let audit_account = next_account_info(account_info_iter)?;
+
+        Validator::validate_signer(admin)?;
+        Validator::validate_account_owner(vault_account, program_id)?;
+        Validator::validate_audit_account(audit_account, program_id, &[admin, vault_account])?;
+
+        let mut vault: SecureVault = Self::load_vault(program_id, vault_account)?;
+        let current_timestamp = Utils::get_current_timestamp()?;
+        SecurityManager::check_rate_limits(&vault, current_timestamp)?;
+        Validator::validate_authorization(&vault, admin.key, &OperationType::UpdateConfig)?;
+        vault.remove_token(&mint).map_err(|e| {
+            msg!("Token asset not removed: {}", e);
+            VaultError::InvalidMintAccount
+        })?;
+
+        let operation_id = Self::record_operation(&mut vault, current_timestamp)?;
+        Self::store_vault(vault_account, &vault)?;
+
+        AuditManager::log_successful_operation(
+            audit_account,
+            operation_id,
+            OperationType::UpdateConfig,
+            *admin.key,
+            None,
+            Some(mint),
+            0,
+        )?;
+        Ok(())
+    }
+
+    /// Validar y debitar un retiro de SOL sobre el estado en memoria
+    fn apply_withdraw(
+        vault: &mut SecureVault,
+        performer: &Pubkey,
+        amount: u64,
+        recipient: &Pubkey,
+        address_book: Option<&AddressBook>,
+        price: Option<&PriceFeed>,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        Validator::validate_withdrawal_request(
+            vault,
+            performer,
+            amount,
+            recipient,
+            current_timestamp,
+        )?;
+        Validator::validate_quote_limits(vault, price, amount, current_timestamp)?;
+        Validator::validate_allowlisted_recipient(vault, address_book, recipient)?;
+
+        vault.reset_daily_limits_if_needed(current_timestamp);
+        vault
+            .record_withdrawal(&Asset::Native, amount)
+            .map_err(|_| VaultError::InsufficientBalance)?;
+        Self::check_circuit_breaker(vault, current_timestamp);
+        vault
+            .record_manager_spend(performer, amount)
+            .map_err(|_| VaultError::ArithmeticOverflow)
+    }
+}
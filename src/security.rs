@@ -1,3 +1,4 @@
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::AccountInfo,
     pubkey::Pubkey,
@@ -6,8 +7,12 @@ use solana_program::{
 };
 
 use crate::{
-    state::{SecureVault, OperationType, SecurityConfig, PendingOperation},
+    state::{
+        MultisigPolicy, PriceFeed, SecureVault, OperationType, SecurityConfig, PendingOperation,
+        EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS, MAX_MANAGER, MAX_OPERATION_THRESHOLDS,
+    },
     error::VaultError,
+    utils::constants::OPERATION_EXPIRY_HOURS,
 };
 
 pub struct SecurityManager;
@@ -31,8 +36,18 @@ impl SecurityManager {
         operation: &OperationType,
         amount: Option<u64>,
     ) -> bool {
-        // TODO: Implementar
-        todo!()
+        match operation {
+            OperationType::Withdraw => amount
+                .map(|a| a >= vault.config.require_multisig_for_large_amounts)
+                .unwrap_or(false),
+            // Se aprueban una sola vez: siempre con el umbral completo
+            OperationType::RecurringPayment => true,
+            // Solo se programa para levantar el disyuntor
+            OperationType::Resume => true,
+            // Solo pasa por operación pendiente desde el umbral de multisig del mint
+            OperationType::WithdrawToken => true,
+            _ => false,
+        }
     }
 
     /// Calcular delay requerido para la operación
@@ -41,8 +56,20 @@ impl SecurityManager {
         operation: &OperationType,
         amount: Option<u64>,
     ) -> i64 {
-        // TODO: Implementar
-        todo!()
+        match operation {
+            // Con tramos configurados, los montos bajo el primero son inmediatos
+            OperationType::Withdraw if !vault.config.withdrawal_delay_tiers.is_empty() => amount
+                .and_then(|a| vault.config.delay_tier_for(a))
+                .map(|tier| tier.delay_seconds)
+                .unwrap_or(0),
+            OperationType::Withdraw | OperationType::WithdrawToken => {
+                vault.config.withdrawal_delay_seconds
+            }
+            OperationType::AddEmergencyContact | OperationType::RemoveEmergencyContact => {
+                EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS
+            }
+            _ => 0,
+        }
     }
 
     /// Verificar límites de rate limiting
@@ -50,8 +77,17 @@ impl SecurityManager {
         vault: &SecureVault,
         current_timestamp: i64,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        let elapsed = current_timestamp.saturating_sub(vault.last_operation_timestamp);
+        if elapsed < vault.config.min_time_between_operations {
+            return Err(VaultError::RateLimited);
+        }
+
+        let mut temp_vault = vault.clone();
+        temp_vault.reset_daily_limits_if_needed(current_timestamp);
+        if temp_vault.daily_operation_count >= vault.config.max_daily_operations {
+            return Err(VaultError::RateLimited);
+        }
+        Ok(())
     }
 
     /// Obtener firmantes requeridos para operación
@@ -60,8 +96,73 @@ impl SecurityManager {
         operation: &OperationType,
         amount: Option<u64>,
     ) -> Vec<Pubkey> {
-        // TODO: Implementar
-        todo!()
+        // Cualquier cuenta con peso puede firmar; el umbral decide cuándo alcanza
+        std::iter::once(vault.admin)
+            .chain(vault.managers.iter().copied())
+            .filter(|signer| vault.signer_weight(signer) > 0)
+            .collect()
+    }
+
+    /// Verificar si un retiro nativo supera el umbral de multisig en moneda de cotización
+    pub fn exceeds_quote_threshold(
+        vault: &SecureVault,
+        price: Option<&PriceFeed>,
+        amount: u64,
+    ) -> Result<bool, VaultError> {
+        let limits = match &vault.config.quote_limits {
+            Some(limits) => limits,
+            None => return Ok(false),
+        };
+        let value = price
+            .ok_or(VaultError::InvalidOracle)?
+            .quote_value(amount)
+            .ok_or(VaultError::ArithmeticOverflow)?;
+        Ok(value >= limits.multisig_threshold)
+    }
+
+    /// Calcular el peso acumulado requerido para ejecutar la operación
+    pub fn get_required_weight(
+        vault: &SecureVault,
+        operation: &OperationType,
+        amount: Option<u64>,
+        price: Option<&PriceFeed>,
+    ) -> u16 {
+        let policy = &vault.multisig_policy;
+        if *operation == OperationType::Resume {
+            return Self::circuit_breaker_quorum(vault);
+        }
+        let threshold = policy.threshold_for(operation);
+        // Sin precio válido se asume monto grande: exigir el umbral completo
+        let quote_large = *operation == OperationType::Withdraw
+            && Self::exceeds_quote_threshold(vault, price, amount.unwrap_or(0)).unwrap_or(true);
+        let required = if quote_large || Self::requires_multisig(vault, operation, amount) {
+            threshold
+        } else {
+            threshold.min(policy.admin_weight)
+        };
+        match operation {
+            OperationType::Withdraw => amount
+                .and_then(|a| vault.config.delay_tier_for(a))
+                .map_or(required, |tier| required.max(tier.required_weight)),
+            _ => required,
+        }
+    }
+
+    /// Peso requerido para levantar el disyuntor: el umbral de `Resume` y al menos mayoría
+    pub fn circuit_breaker_quorum(vault: &SecureVault) -> u16 {
+        let majority = (vault.total_signing_weight() / 2 + 1).min(u16::MAX as u32) as u16;
+        vault
+            .multisig_policy
+            .threshold_for(&OperationType::Resume)
+            .max(majority)
+    }
+
+    /// Verificar si un retiro nativo cae en un tramo que exige operación time-locked
+    pub fn requires_time_lock(vault: &SecureVault, amount: u64) -> bool {
+        vault
+            .config
+            .delay_tier_for(amount)
+            .is_some_and(|tier| tier.delay_seconds > 0 || tier.required_weight > 0)
     }
 
     /// Verificar si la operación está en modo de emergencia
@@ -94,8 +195,56 @@ impl SecurityManager {
 
     /// Validar configuración de seguridad
     pub fn validate_security_config(config: &SecurityConfig) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if !config.is_valid() {
+            return Err(VaultError::InvalidSecurityConfig);
+        }
+        // La operación time-locked debe poder ejecutarse antes de expirar
+        let max_delay = OPERATION_EXPIRY_HOURS * 3600;
+        if config
+            .withdrawal_delay_tiers
+            .iter()
+            .any(|tier| tier.delay_seconds >= max_delay)
+        {
+            return Err(VaultError::InvalidSecurityConfig);
+        }
+        Ok(())
+    }
+
+    /// Validar una política de multisig ponderado contra los firmantes actuales
+    pub fn validate_multisig_policy(
+        vault: &SecureVault,
+        policy: &MultisigPolicy,
+    ) -> Result<(), VaultError> {
+        if policy.admin_weight == 0
+            || policy.signer_weights.len() > MAX_MANAGER + 1
+            || policy.thresholds.len() > MAX_OPERATION_THRESHOLDS
+        {
+            return Err(VaultError::InvalidSecurityConfig);
+        }
+
+        for (i, entry) in policy.signer_weights.iter().enumerate() {
+            let is_signer = entry.signer == vault.admin || vault.managers.contains(&entry.signer);
+            let duplicated = policy.signer_weights[..i]
+                .iter()
+                .any(|w| w.signer == entry.signer);
+            if !is_signer || duplicated {
+                return Err(VaultError::InvalidSecurityConfig);
+            }
+        }
+
+        // Cada umbral debe ser alcanzable con los firmantes actuales
+        let mut candidate = vault.clone();
+        candidate.multisig_policy = policy.clone();
+        let total_weight = candidate.total_signing_weight();
+        for (i, entry) in policy.thresholds.iter().enumerate() {
+            let duplicated = policy.thresholds[..i]
+                .iter()
+                .any(|t| t.operation_type == entry.operation_type);
+            if entry.threshold == 0 || entry.threshold as u32 > total_weight || duplicated {
+                return Err(VaultError::InvalidSecurityConfig);
+            }
+        }
+        Ok(())
     }
 }
 
@@ -129,7 +278,7 @@ pub struct SecurityRecommendation {
 }
 
 /// Tipos de anomalías de seguridad
-#[derive(Debug, Clone, PartialEq)]
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub enum AnomalyType {
     UnusualTransactionPattern,
     HighFrequencyOperations,
@@ -206,4 +355,79 @@ impl SecurityReport {
         // TODO: Implementar
         todo!()
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{tests::test_vault, DelayTier, QuoteLimits};
+
+    const LARGE: u64 = 1_000_000_000;
+    const SMALL: u64 = 100_000_000;
+
+    fn withdraw_weight(vault: &SecureVault, amount: u64, price: Option<&PriceFeed>) -> u16 {
+        SecurityManager::get_required_weight(vault, &OperationType::Withdraw, Some(amount), price)
+    }
+
+    #[test]
+    fn small_withdrawals_need_only_admin_weight() {
+        let mut vault = test_vault(Pubkey::new_unique());
+        vault.config.require_multisig_for_large_amounts = LARGE;
+        let threshold = vault.multisig_policy.threshold_for(&OperationType::Withdraw);
+        assert!(threshold > vault.multisig_policy.admin_weight);
+
+        assert_eq!(withdraw_weight(&vault, SMALL, None), vault.multisig_policy.admin_weight);
+        assert_eq!(withdraw_weight(&vault, LARGE - 1, None), vault.multisig_policy.admin_weight);
+        assert_eq!(withdraw_weight(&vault, LARGE, None), threshold);
+    }
+
+    #[test]
+    fn delay_tier_weight_raises_required_weight() {
+        let mut vault = test_vault(Pubkey::new_unique());
+        vault.config.require_multisig_for_large_amounts = LARGE;
+        vault.config.withdrawal_delay_tiers = vec![DelayTier {
+            min_amount: SMALL,
+            delay_seconds: 600,
+            required_weight: 10,
+        }];
+
+        let admin_weight = vault.multisig_policy.admin_weight;
+        assert_eq!(withdraw_weight(&vault, SMALL - 1, None), admin_weight);
+        assert_eq!(withdraw_weight(&vault, SMALL, None), 10);
+        assert_eq!(withdraw_weight(&vault, LARGE, None), 10);
+    }
+
+    #[test]
+    fn quote_threshold_requires_full_weight_without_price() {
+        let mut vault = test_vault(Pubkey::new_unique());
+        vault.config.require_multisig_for_large_amounts = u64::MAX;
+        vault.config.quote_limits = Some(QuoteLimits {
+            price_feed: Pubkey::new_unique(),
+            daily_withdrawal_limit: u64::MAX,
+            multisig_threshold: 1_000_000_000, // 1000 unidades de cotización
+            max_staleness_seconds: 60,
+            max_confidence_bps: 100,
+        });
+        let threshold = vault.multisig_policy.threshold_for(&OperationType::Withdraw);
+        let admin_weight = vault.multisig_policy.admin_weight;
+        let price = PriceFeed::new(150, 0, 0, 0);
+
+        assert_eq!(withdraw_weight(&vault, SMALL, None), threshold);
+        assert_eq!(withdraw_weight(&vault, SMALL, Some(&price)), admin_weight);
+        // 10 SOL a 150 superan el umbral de 1000
+        assert_eq!(withdraw_weight(&vault, 10 * LARGE, Some(&price)), threshold);
+    }
+
+    #[test]
+    fn resume_requires_majority_of_signing_weight() {
+        let mut vault = test_vault(Pubkey::new_unique());
+        for _ in 0..4 {
+            vault.add_manager(Pubkey::new_unique()).unwrap();
+        }
+        let majority = (vault.total_signing_weight() / 2 + 1) as u16;
+        let weight =
+            SecurityManager::get_required_weight(&vault, &OperationType::Resume, None, None);
+        assert_eq!(weight, majority.max(vault.multisig_policy.admin_weight));
+        assert!(weight as u32 * 2 > vault.total_signing_weight());
+    }
+}
@@ -1,14 +1,57 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{clock::Clock, pubkey::Pubkey, sysvar::Sysvar};
 
+use crate::security::AnomalyType;
+
 pub const SECONDS_PER_DAY: i64 = 86400;
+pub const SECONDS_PER_HOUR: i64 = 3600;
+pub const SECONDS_PER_WEEK: i64 = 7 * SECONDS_PER_DAY;
+pub const SECONDS_PER_MONTH: i64 = 30 * SECONDS_PER_DAY;
 pub const MAX_MANAGER: usize = 10;
 pub const VAULT_DISCRIMINATOR: [u8; 8] = *b"SECVAULT";
 pub const MAX_EMERGENCY_CONTACTS: usize = 5;
+pub const MAX_TOKEN_MINTS: usize = 5;
+pub const MAX_ADDRESS_BOOK_ENTRIES: usize = 20;
+pub const MAX_LABEL_LENGTH: usize = 32;
+pub const ADDRESS_BOOK_DISCRIMINATOR: [u8; 8] = *b"ADDRBOOK";
+pub const VESTING_DISCRIMINATOR: [u8; 8] = *b"VESTSCHD";
+pub const RECURRING_PAYMENT_DISCRIMINATOR: [u8; 8] = *b"RECURPAY";
+pub const DEPOSITOR_LEDGER_DISCRIMINATOR: [u8; 8] = *b"DEPOSITR";
+pub const MIN_RECURRING_INTERVAL_SECONDS: i64 = 3600;
+pub const MAX_COMPARTMENTS: usize = 4;
+pub const MAX_COMPARTMENT_MANAGERS: usize = 3;
+pub const MAX_COMPARTMENT_NAME_LENGTH: usize = 32;
+pub const WITHDRAWAL_WINDOW_BUCKETS: usize = 24;
+pub const MAX_DELAY_TIERS: usize = 8;
+pub const MAX_OUTFLOW_BPS: u16 = 10_000;
+pub const MAX_CUSTOM_ROLES: usize = 8;
+pub const MAX_ROLE_ASSIGNMENTS: usize = 16;
+pub const MAX_ROLE_NAME_LENGTH: usize = 32;
+pub const DEFAULT_ADMIN_WEIGHT: u16 = 3;
+pub const DEFAULT_MANAGER_WEIGHT: u16 = 1;
+pub const MAX_OPERATION_THRESHOLDS: usize = 16;
+pub const DEFAULT_RECOVERY_THRESHOLD: u8 = 2;
+pub const DEFAULT_RECOVERY_DELAY_SECONDS: i64 = 7 * SECONDS_PER_DAY;
+pub const MIN_RECOVERY_DELAY_SECONDS: i64 = SECONDS_PER_DAY;
+pub const RECOVERY_PROPOSAL_EXPIRY_SECONDS: i64 = 7 * SECONDS_PER_DAY;
+pub const ADMIN_PROPOSAL_EXPIRY_SECONDS: i64 = 7 * SECONDS_PER_DAY;
+pub const MIN_INACTIVITY_TIMEOUT_SECONDS: i64 = 30 * SECONDS_PER_DAY;
+pub const MIN_INACTIVITY_CLAIM_DELAY_SECONDS: i64 = 7 * SECONDS_PER_DAY;
+pub const MAX_TIMED_PAUSE_SECONDS: i64 = 72 * SECONDS_PER_HOUR;
+pub const TIMED_PAUSE_COOLDOWN_SECONDS: i64 = SECONDS_PER_DAY;
+pub const EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS: i64 = 12 * 3600;
+pub const PRICE_FEED_DISCRIMINATOR: [u8; 8] = *b"PRICEFED";
+pub const QUOTE_DECIMALS: i32 = 6;
+pub const LAMPORTS_DECIMALS: i32 = 9;
+pub const MAX_CONFIDENCE_BPS: u16 = 10_000;
 
 pub const VAULT_SEED: &[u8] = b"secure_vault";
 pub const AUDIT_SEED: &[u8] = b"audit";
 pub const PENDING_OP_SEED: &[u8] = b"pending_op";
+pub const ADDRESS_BOOK_SEED: &[u8] = b"address_book";
+pub const VESTING_SEED: &[u8] = b"vesting";
+pub const RECURRING_PAYMENT_SEED: &[u8] = b"recurring";
+pub const DEPOSITOR_SEED: &[u8] = b"depositor";
 
 pub const MAX_SEED_LENGTH: usize = 32;
 pub const MAX_SEED_COUNT: usize = 16;
@@ -30,6 +73,284 @@ pub struct SecureVault {
     pub emergency_contacts: Vec<Pubkey>,
     pub created_at: i64,
     pub bump: u8,
+    pub config: SecurityConfig,
+    pub token_balances: Vec<TokenBalance>,
+    pub daily_operation_count: u32,
+    pub recipient_policy: RecipientPolicy,
+    pub pending_operation_count: u64,
+    pub manager_quotas: Vec<ManagerQuota>,
+    pub custom_roles: Vec<CustomRole>,
+    pub role_assignments: Vec<RoleAssignment>,
+    pub multisig_policy: MultisigPolicy,
+    pub seed_authority: Pubkey, // Admin original: las semillas del PDA no cambian al rotar admin
+    pub recovery_threshold: u8,
+    pub recovery_delay_seconds: i64,
+    pub admin_recovery: Option<AdminRecovery>,
+    pub pending_admin: Option<PendingAdmin>,
+    pub vesting_schedule_count: u64,
+    pub vesting_reserved: u64, // Lamports comprometidos en vesting aún no reclamados
+    pub recurring_payment_count: u64,
+    pub compartments: Vec<Compartment>,
+    pub withdrawal_window: WithdrawalWindow,
+    pub outflow_counters: OutflowCounters,
+    pub circuit_breaker: CircuitBreakerState,
+    pub dead_man_switch: Option<DeadManSwitch>,
+    pub inactivity_claim: Option<InactivityClaim>,
+    pub timed_pause: Option<TimedPause>, // Se conserva al expirar para aplicar el cooldown
+}
+
+/// Pausa temporal: el vault se reanuda solo al llegar a `expires_at`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TimedPause {
+    pub paused_by: Pubkey,
+    pub reason: PauseReason,
+    pub paused_at: i64,
+    pub expires_at: i64,
+}
+
+/// Motivo declarado de una pausa temporal
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum PauseReason {
+    SuspectedKeyCompromise,
+    SuspiciousActivity,
+    ExternalIncident,
+    Maintenance,
+    Other,
+}
+
+/// Dead-man's switch: tras el timeout sin operaciones, el beneficiario o los emergency
+/// contacts pueden reclamar los fondos mediante un time-lock cancelable con heartbeat
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct DeadManSwitch {
+    pub inactivity_timeout_seconds: i64, // Medido desde `last_operation_timestamp`
+    pub claim_delay_seconds: i64,
+    pub beneficiary: Option<Pubkey>,
+}
+
+/// Reclamo en curso sobre un vault inactivo
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct InactivityClaim {
+    pub claimant: Pubkey, // Recibe los fondos al ejecutarse
+    pub started_at: i64,
+    pub executable_at: i64,
+}
+
+/// Estado del disyuntor por salidas sobre el TVL
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub struct CircuitBreakerState {
+    pub trip: Option<CircuitBreakerTrip>,
+    pub baseline_hour: i64,   // Hora del último levantamiento; las salidas previas no cuentan
+    pub baseline_offset: u64, // Salidas ya registradas en esa hora al levantarlo
+}
+
+/// Anomalía registrada al dispararse el disyuntor
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct CircuitBreakerTrip {
+    pub anomaly_type: AnomalyType,
+    pub tripped_at: i64,
+    pub window_outflow: u64,
+    pub tvl: u64,
+}
+
+/// Ventana móvil de retiros nativos: un bucket por hora de las últimas 24 horas
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub struct WithdrawalWindow {
+    pub buckets: [u64; WITHDRAWAL_WINDOW_BUCKETS],
+    pub current_hour: i64, // Hora (timestamp / 3600) del bucket más reciente
+}
+
+/// Acumulado de retiros nativos dentro de un período fijo (hora, semana o mes)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub struct PeriodCounter {
+    pub period: i64, // Índice del período (timestamp / duración)
+    pub withdrawn: u64,
+}
+
+/// Contadores de salida por tramo; el diario sigue en `daily_withdrawn_amount`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub struct OutflowCounters {
+    pub hourly: PeriodCounter,
+    pub weekly: PeriodCounter,
+    pub monthly: PeriodCounter,
+}
+
+/// Tramo de límite de retiros que bloqueó una operación
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LimitTier {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// Compartimento del vault ("opex", "payroll", ...) con balance, managers y límite propios
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct Compartment {
+    pub name: String,
+    pub balance: u64, // Parte de `total_balance` asignada a este compartimento
+    pub managers: Vec<Pubkey>,
+    pub daily_withdrawal_limit: u64,
+    pub daily_withdrawn_amount: u64,
+}
+
+/// Transferencia de admin propuesta, pendiente de aceptación por la nueva clave
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct PendingAdmin {
+    pub new_admin: Pubkey,
+    pub proposed_at: i64,
+    pub expires_at: i64,
+}
+
+/// Propuesta de recuperación del admin por quorum de emergency contacts
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct AdminRecovery {
+    pub proposed_admin: Pubkey,
+    pub proposed_at: i64,
+    pub approvals: Vec<Pubkey>,
+    pub quorum_reached_at: Option<i64>, // Inicio del time-lock
+}
+
+/// Política de multisig ponderado: pesos por firmante y umbral por tipo de operación
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct MultisigPolicy {
+    pub admin_weight: u16,
+    pub manager_weight: u16,
+    pub signer_weights: Vec<SignerWeight>, // Sobrescriben el peso por rol
+    pub thresholds: Vec<OperationThreshold>, // Sin entrada: umbral = admin_weight
+}
+
+/// Peso explícito de un firmante
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct SignerWeight {
+    pub signer: Pubkey,
+    pub weight: u16,
+}
+
+/// Peso acumulado necesario para ejecutar un tipo de operación
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct OperationThreshold {
+    pub operation_type: OperationType,
+    pub threshold: u16,
+}
+
+/// Bitmap de permisos: un bit por variante de `OperationType`
+pub type Permissions = u32;
+
+/// Rol personalizado con nombre y bitmap de permisos
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct CustomRole {
+    pub id: u8,
+    pub name: String,
+    pub permissions: Permissions,
+}
+
+/// Asignación de un rol personalizado a una cuenta
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct RoleAssignment {
+    pub account: Pubkey,
+    pub role_id: u8,
+}
+
+/// Cuota de gasto de un manager para retiros de SOL (en lamports)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ManagerQuota {
+    pub manager: Pubkey,
+    pub daily_limit: u64,
+    pub per_transaction_limit: u64,
+    pub daily_spent: u64,
+}
+
+/// Política para retiros a destinatarios fuera del address book
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum RecipientPolicy {
+    Disabled,                                     // Cualquier destinatario
+    RejectUnlisted,                               // Solo destinatarios aprobados
+    TimeLockUnlisted { min_delay_seconds: i64 }, // No listados solo vía operación pendiente
+}
+
+/// Address book del vault (PDA): destinatarios aprobados para retiros
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct AddressBook {
+    pub discriminator: [u8; 8],
+    pub vault: Pubkey,
+    pub entries: Vec<AddressBookEntry>,
+    pub bump: u8,
+}
+
+/// Calendario de vesting lineal (PDA) pagado en lamports desde el vault
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct VestingSchedule {
+    pub discriminator: [u8; 8],
+    pub vault: Pubkey,
+    pub id: u64,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_timestamp: i64,
+    pub cliff_timestamp: i64,
+    pub end_timestamp: i64,
+    pub bump: u8,
+}
+
+/// Pago recurrente (PDA): `amount` a `recipient` cada `interval_seconds`, `total_payments` veces
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct RecurringPayment {
+    pub discriminator: [u8; 8],
+    pub vault: Pubkey,
+    pub id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub interval_seconds: i64,
+    pub total_payments: u32,
+    pub payments_made: u32,
+    pub next_payment_at: i64,
+    pub status: OperationStatus, // Pending hasta aprobarse por multisig, Executed al completarse
+    pub bump: u8,
+}
+
+/// Registro por depositante (PDA): lo depositado y lo devuelto en lamports
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct DepositorLedger {
+    pub discriminator: [u8; 8],
+    pub vault: Pubkey,
+    pub depositor: Pubkey,
+    pub total_deposited: u64,
+    pub total_refunded: u64,
+    pub bump: u8,
+}
+
+/// Destinatario aprobado con etiqueta legible
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct AddressBookEntry {
+    pub address: Pubkey,
+    pub label: String,
+    pub added_at: i64,
+}
+
+/// Activo custodiado por el vault
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum Asset {
+    Native,        // SOL (lamports)
+    Token(Pubkey), // Mint SPL
+}
+
+/// Vista del ledger de un activo: balance, límites y contador diario
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetLedger {
+    pub balance: u64,
+    pub daily_withdrawal_limit: u64,
+    pub daily_withdrawn_amount: u64,
+    pub multisig_threshold: u64,
+}
+
+/// Balance de un mint SPL custodiado por el vault (en ATAs del PDA)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TokenBalance {
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub daily_withdrawal_limit: u64,
+    pub daily_withdrawn_amount: u64,
+    pub multisig_threshold: u64,
 }
 
 /// Registro de auditoría para cada operación
@@ -45,10 +366,13 @@ pub struct AuditLog {
     pub error_code: Option<u32>,
     pub gas_used: u64,
     pub bump: u8,
+    pub correlation_id: Option<u64>,
+    pub operation_status: Option<OperationStatus>,
+    pub pause: Option<TimedPause>, // Motivo y expiración de una pausa temporal
 }
 
 /// Configuración de seguridad del vault
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct SecurityConfig {
     pub max_daily_operations: u32,
     pub min_time_between_operations: i64,
@@ -56,6 +380,59 @@ pub struct SecurityConfig {
     pub auto_pause_on_suspicious_activity: bool,
     pub max_managers: u8,
     pub withdrawal_delay_seconds: i64,
+    pub quote_limits: Option<QuoteLimits>,
+    pub refundable_mode: bool, // Depositantes pueden retirar su propio balance
+    pub limit_window: LimitWindow,
+    pub hourly_withdrawal_limit: u64, // Lamports; u64::MAX desactiva el tramo
+    pub weekly_withdrawal_limit: u64,
+    pub monthly_withdrawal_limit: u64,
+    pub withdrawal_delay_tiers: Vec<DelayTier>, // Vacío: se usa `withdrawal_delay_seconds`
+    // Solo actúa con `auto_pause_on_suspicious_activity` activo
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+}
+
+/// Disyuntor: pausa el vault si las salidas nativas de la ventana superan un % del TVL
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct CircuitBreakerConfig {
+    pub window_hours: u8, // Hasta `WITHDRAWAL_WINDOW_BUCKETS`
+    pub max_outflow_bps: u16,
+}
+
+/// Tramo de delay por monto: retiros desde `min_amount` pasan por una operación time-locked
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct DelayTier {
+    pub min_amount: u64,
+    pub delay_seconds: i64,
+    pub required_weight: u16, // Peso mínimo de firmas; 0 conserva el de la política
+}
+
+/// Cómo se computa el límite diario de retiros nativos
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum LimitWindow {
+    CalendarDay, // Reinicio a medianoche UTC
+    Rolling24h,  // Suma de las últimas 24 horas
+}
+
+/// Límites del activo nativo en moneda de cotización (unidades de `QUOTE_DECIMALS`)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct QuoteLimits {
+    pub price_feed: Pubkey,
+    pub daily_withdrawal_limit: u64,
+    pub multisig_threshold: u64,
+    pub max_staleness_seconds: i64,
+    pub max_confidence_bps: u16,
+}
+
+/// Cuenta de precio del oráculo: precio del SOL en la moneda de cotización. El programa no
+/// interpreta cuentas de Pyth ni Switchboard: un adaptador escribe este layout a partir del
+/// oráculo real y el admin fija su dirección en `QuoteLimits::price_feed`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct PriceFeed {
+    pub discriminator: [u8; 8],
+    pub price: i64,
+    pub confidence: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
 }
 
 /// Operación pendiente (para time-locked operations)
@@ -71,6 +448,11 @@ pub struct PendingOperation {
     pub received_signatures: Vec<Pubkey>,
     pub created_at: i64,
     pub bump: u8,
+    pub required_weight: u16,
+    pub received_weight: u16,
+    pub status: OperationStatus,
+    pub rejected_by: Vec<Pubkey>,
+    pub rejected_weight: u16,
 }
 
 /// Tipos de operaciones del sistema
@@ -85,6 +467,27 @@ pub enum OperationType {
     UpdateConfig,
     TransferAdmin,
     EmergencyWithdraw,
+    DepositToken,  // Todos
+    WithdrawToken, // un Manager, Admin
+    AddAddressBookEntry,
+    RemoveAddressBookEntry,
+    SetManagerQuota,
+    ManageRoles,
+    AdminRecovery, // Emergency contacts (quorum)
+    AddEmergencyContact,
+    RemoveEmergencyContact,
+    CreateVestingSchedule,
+    ClaimVested, // Beneficiario del calendario
+    RecurringPayment,
+    ManageCompartments,
+    CompartmentWithdraw, // Managers del compartimento
+    RefundDepositor,
+    DepositorWithdraw, // Depositante, solo en modo reembolsable
+    InactivityClaim,   // Beneficiario o emergency contacts, tras el timeout de inactividad
+    Heartbeat,         // Admin, managers
+    IndefinitePause,   // Admin; los demás usan la pausa temporal
+    ExtendPause,       // Admin
+    CancelAdminRecovery, // Admin
 }
 
 /// Roles del sistema
@@ -99,15 +502,65 @@ pub enum Role {
 /// Estado de una operación
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub enum OperationStatus {
-    Pending,
-    Approved,
-    Rejected,
+    Pending,  // Acumulando firmas
+    Approved, // Peso suficiente, esperando el time-lock
+    Rejected, // Bloqueada por peso de rechazo
     Executed,
     Expired,
 }
 
+// Cada operación ocupa un bit del bitmap de permisos
+const _: () = assert!(OperationType::COUNT <= Permissions::BITS);
+
+impl OperationType {
+    /// Cantidad de variantes; `CancelAdminRecovery` debe seguir siendo la última
+    pub const COUNT: u32 = OperationType::CancelAdminRecovery as u32 + 1;
+
+    /// Bit de esta operación dentro de un bitmap de `Permissions`
+    pub fn permission_bit(&self) -> Permissions {
+        1 << (self.clone() as u32)
+    }
+
+    /// Construir un bitmap a partir de una lista de operaciones
+    pub fn permissions_of(operations: &[OperationType]) -> Permissions {
+        operations.iter().fold(0, |acc, op| acc | op.permission_bit())
+    }
+}
+
+impl Role {
+    /// Permisos que solo el admin puede ejercer (no asignables a roles personalizados)
+    pub const RESERVED_PERMISSIONS: Permissions =
+        1 << OperationType::TransferAdmin as u32 | 1 << OperationType::ManageRoles as u32;
+
+    /// Bitmap de permisos de los roles base
+    pub fn permissions(&self) -> Permissions {
+        match self {
+            Role::Admin => Permissions::MAX,
+            Role::Manager => OperationType::permissions_of(&[
+                OperationType::Deposit,
+                OperationType::DepositToken,
+                OperationType::Withdraw,
+                OperationType::WithdrawToken,
+                OperationType::CompartmentWithdraw,
+                OperationType::Heartbeat,
+            ]),
+            Role::User => OperationType::permissions_of(&[
+                OperationType::Deposit,
+                OperationType::DepositToken,
+            ]),
+            Role::EmergencyContact => OperationType::permissions_of(&[
+                OperationType::Deposit,
+                OperationType::DepositToken,
+                OperationType::EmergencyPause,
+                OperationType::AdminRecovery,
+                OperationType::InactivityClaim,
+            ]),
+        }
+    }
+}
+
 impl SecureVault {
-    pub const MAX_SIZE: usize = 1024; // Tamaño máximo en bytes
+    pub const MAX_SIZE: usize = 6144; // Tamaño máximo en bytes
 
     /// Crear un nuevo vault
     pub fn new(admin: Pubkey) -> Self {
@@ -128,6 +581,30 @@ impl SecureVault {
             emergency_contacts: Vec::new(),
             created_at: current_time,
             bump: 0,
+            config: SecurityConfig::default(),
+            token_balances: Vec::new(),
+            daily_operation_count: 0,
+            recipient_policy: RecipientPolicy::Disabled,
+            pending_operation_count: 0,
+            manager_quotas: Vec::new(),
+            custom_roles: Vec::new(),
+            role_assignments: Vec::new(),
+            multisig_policy: MultisigPolicy::default(),
+            seed_authority: admin,
+            recovery_threshold: DEFAULT_RECOVERY_THRESHOLD,
+            recovery_delay_seconds: DEFAULT_RECOVERY_DELAY_SECONDS,
+            admin_recovery: None,
+            pending_admin: None,
+            vesting_schedule_count: 0,
+            vesting_reserved: 0,
+            recurring_payment_count: 0,
+            compartments: Vec::new(),
+            withdrawal_window: WithdrawalWindow::default(),
+            outflow_counters: OutflowCounters::default(),
+            circuit_breaker: CircuitBreakerState::default(),
+            dead_man_switch: None,
+            inactivity_claim: None,
+            timed_pause: None,
         }
     }
 
@@ -136,7 +613,14 @@ impl SecureVault {
     }
 
     pub fn get_seeds(&self) -> Vec<Vec<u8>> {
-        vec![b"secure_vault".to_vec(), self.admin.to_bytes().to_vec()]
+        vec![b"secure_vault".to_vec(), self.seed_authority.to_bytes().to_vec()]
+    }
+
+    /// Semillas completas (incluyendo bump) para firmar CPIs como el PDA
+    pub fn get_signer_seeds(&self) -> Vec<Vec<u8>> {
+        let mut seeds = self.get_seeds();
+        seeds.push(vec![self.bump]);
+        seeds
     }
 
     /// Verificar si una cuenta tiene el rol especificado
@@ -170,57 +654,572 @@ impl SecureVault {
             && self.emergency_contacts.len() <= MAX_EMERGENCY_CONTACTS
             && self.daily_withdrawn_amount <= self.daily_withdrawal_limit
             && self.min_signatures_required > 0
+            && (self.circuit_breaker.trip.is_none() || self.is_paused)
+            && (self.inactivity_claim.is_none() || self.dead_man_switch.is_some())
+            && self.token_balances.len() <= MAX_TOKEN_MINTS
+            && self
+                .token_balances
+                .iter()
+                .all(|t| t.daily_withdrawn_amount <= t.daily_withdrawal_limit)
+            && self
+                .manager_quotas
+                .iter()
+                .all(|q| self.managers.contains(&q.manager) && q.daily_spent <= q.daily_limit)
+            && self.custom_roles.len() <= MAX_CUSTOM_ROLES
+            && self.role_assignments.len() <= MAX_ROLE_ASSIGNMENTS
+            && self
+                .role_assignments
+                .iter()
+                .all(|a| self.custom_roles.iter().any(|r| r.id == a.role_id))
+            && self.compartments.len() <= MAX_COMPARTMENTS
+            && self.compartments.iter().enumerate().all(|(i, c)| {
+                c.managers.len() <= MAX_COMPARTMENT_MANAGERS
+                    && c.daily_withdrawn_amount <= c.daily_withdrawal_limit
+                    && !self.compartments[..i].iter().any(|o| o.name == c.name)
+            })
+            && self
+                .compartments
+                .iter()
+                .try_fold(self.vesting_reserved, |acc, c| acc.checked_add(c.balance))
+                .is_some_and(|committed| committed <= self.total_balance)
+    }
+
+    /// Balance nativo disponible (excluye lo comprometido en vesting y en compartimentos)
+    pub fn available_native_balance(&self) -> u64 {
+        let allocated = self
+            .compartments
+            .iter()
+            .fold(0u64, |acc, c| acc.saturating_add(c.balance));
+        self.total_balance
+            .saturating_sub(self.vesting_reserved)
+            .saturating_sub(allocated)
+    }
+
+    pub fn get_compartment(&self, name: &str) -> Option<&Compartment> {
+        self.compartments.iter().find(|c| c.name == name)
+    }
+
+    /// Crear o reconfigurar un compartimento (managers y límite diario)
+    pub fn configure_compartment(
+        &mut self,
+        name: String,
+        managers: Vec<Pubkey>,
+        daily_withdrawal_limit: u64,
+    ) -> Result<(), &'static str> {
+        if name.is_empty() || name.len() > MAX_COMPARTMENT_NAME_LENGTH {
+            return Err("Invalid compartment name");
+        }
+        if managers.len() > MAX_COMPARTMENT_MANAGERS
+            || managers.iter().any(|m| *m == Pubkey::default())
+            || managers
+                .iter()
+                .enumerate()
+                .any(|(i, m)| managers[..i].contains(m))
+        {
+            return Err("Invalid compartment managers");
+        }
+
+        if let Some(compartment) = self.compartments.iter_mut().find(|c| c.name == name) {
+            compartment.managers = managers;
+            compartment.daily_withdrawal_limit = daily_withdrawal_limit;
+            compartment.daily_withdrawn_amount = compartment
+                .daily_withdrawn_amount
+                .min(daily_withdrawal_limit);
+            return Ok(());
+        }
+        if self.compartments.len() >= MAX_COMPARTMENTS {
+            return Err("Maximo numero de compartimentos alcanzado");
+        }
+        self.compartments.push(Compartment {
+            name,
+            balance: 0,
+            managers,
+            daily_withdrawal_limit,
+            daily_withdrawn_amount: 0,
+        });
+        Ok(())
+    }
+
+    /// Mover balance entre compartimentos; `None` es el fondo general del vault
+    pub fn transfer_between_compartments(
+        &mut self,
+        from: Option<&str>,
+        to: Option<&str>,
+        amount: u64,
+    ) -> Result<(), &'static str> {
+        if from == to {
+            return Err("Source and destination are the same");
+        }
+        if let Some(name) = from {
+            let source = self
+                .compartments
+                .iter_mut()
+                .find(|c| c.name == name)
+                .ok_or("Compartment not found")?;
+            source.balance = source
+                .balance
+                .checked_sub(amount)
+                .ok_or("Insufficient compartment balance")?;
+        } else if amount > self.available_native_balance() {
+            return Err("Insufficient unallocated balance");
+        }
+
+        if let Some(name) = to {
+            let destination = self
+                .compartments
+                .iter_mut()
+                .find(|c| c.name == name)
+                .ok_or("Compartment not found")?;
+            destination.balance = destination
+                .balance
+                .checked_add(amount)
+                .ok_or("Compartment balance overflow")?;
+        }
+        Ok(())
+    }
+
+    /// Verificar un retiro desde un compartimento (balance y límite propios)
+    pub fn can_withdraw_from_compartment(
+        &self,
+        name: &str,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), &'static str> {
+        let mut temp_vault = self.clone();
+        temp_vault.reset_daily_limits_if_needed(current_timestamp);
+        let compartment = temp_vault
+            .get_compartment(name)
+            .ok_or("Compartment not found")?;
+        if amount > compartment.balance {
+            return Err("Insufficient compartment balance");
+        }
+        if compartment.daily_withdrawn_amount.saturating_add(amount)
+            > compartment.daily_withdrawal_limit
+        {
+            return Err("Compartment daily limit exceeded");
+        }
+        Ok(())
+    }
+
+    /// Registrar un retiro desde un compartimento (también cuenta en el ledger nativo)
+    pub fn record_compartment_withdrawal(
+        &mut self,
+        name: &str,
+        amount: u64,
+    ) -> Result<(), &'static str> {
+        let compartment = self
+            .compartments
+            .iter_mut()
+            .find(|c| c.name == name)
+            .ok_or("Compartment not found")?;
+        compartment.balance = compartment
+            .balance
+            .checked_sub(amount)
+            .ok_or("Insufficient compartment balance")?;
+        compartment.daily_withdrawn_amount = compartment
+            .daily_withdrawn_amount
+            .checked_add(amount)
+            .ok_or("Daily counter overflow")?;
+        self.record_withdrawal(&Asset::Native, amount)
+    }
+
+    /// Reservar fondos para un nuevo calendario de vesting
+    pub fn reserve_vesting(&mut self, amount: u64) -> Result<(), &'static str> {
+        if amount > self.available_native_balance() {
+            return Err("Insufficient unreserved balance");
+        }
+        self.vesting_reserved = self
+            .vesting_reserved
+            .checked_add(amount)
+            .ok_or("Vesting reserve overflow")?;
+        Ok(())
+    }
+
+    /// Disparar el disyuntor si las salidas nativas de la ventana superan el % del TVL;
+    /// pausa el vault y registra la anomalía. Devuelve si se disparó
+    pub fn trip_circuit_breaker_if_needed(&mut self, current_timestamp: i64) -> bool {
+        let breaker = match &self.config.circuit_breaker {
+            Some(breaker) if self.config.auto_pause_on_suspicious_activity => breaker.clone(),
+            _ => return false,
+        };
+        if self.circuit_breaker.trip.is_some() {
+            return false;
+        }
+
+        self.withdrawal_window.advance(current_timestamp);
+        let outflow = self.withdrawal_window.total_since(
+            breaker.window_hours,
+            self.circuit_breaker.baseline_hour,
+            self.circuit_breaker.baseline_offset,
+        );
+        // TVL al inicio de la ventana: balance actual más lo que salió en ella
+        let tvl = self.total_balance.saturating_add(outflow);
+        if outflow as u128 * MAX_OUTFLOW_BPS as u128
+            <= breaker.max_outflow_bps as u128 * tvl as u128
+        {
+            return false;
+        }
+
+        self.is_paused = true;
+        self.circuit_breaker.trip = Some(CircuitBreakerTrip {
+            anomaly_type: AnomalyType::LargeAmountTransfer,
+            tripped_at: current_timestamp,
+            window_outflow: outflow,
+            tvl,
+        });
+        true
+    }
+
+    /// Levantar la pausa del disyuntor; las salidas anteriores dejan de contar
+    pub fn lift_circuit_breaker(&mut self, current_timestamp: i64) -> Result<(), &'static str> {
+        if self.circuit_breaker.trip.is_none() {
+            return Err("Circuit breaker not tripped");
+        }
+
+        self.withdrawal_window.advance(current_timestamp);
+        self.circuit_breaker = CircuitBreakerState {
+            trip: None,
+            baseline_hour: self.withdrawal_window.current_hour,
+            baseline_offset: self.withdrawal_window.current_bucket(),
+        };
+        self.is_paused = false;
+        Ok(())
+    }
+
+    /// Pagar un reclamo de vesting: sale del balance y de la reserva
+    pub fn record_vesting_claim(&mut self, amount: u64) -> Result<(), &'static str> {
+        self.vesting_reserved = self
+            .vesting_reserved
+            .checked_sub(amount)
+            .ok_or("Claim exceeds vesting reserve")?;
+        self.total_balance = self
+            .total_balance
+            .checked_sub(amount)
+            .ok_or("Insufficient balance")?;
+        Ok(())
     }
 
-    /// Resetear límites diarios si es necesario
+    /// Resetear límites diarios si es necesario; en modo `Rolling24h` el acumulado nativo
+    /// refleja la suma de las últimas 24 horas en lugar del día calendario
     pub fn reset_daily_limits_if_needed(&mut self, current_timestamp: i64) {
+        self.withdrawal_window.advance(current_timestamp);
+        self.outflow_counters.roll(current_timestamp);
+
         let current_day = current_timestamp / SECONDS_PER_DAY;
         let last_reset_day = self.last_reset_day / SECONDS_PER_DAY;
 
         if current_day > last_reset_day {
             self.daily_withdrawn_amount = 0;
+            for token in self.token_balances.iter_mut() {
+                token.daily_withdrawn_amount = 0;
+            }
+            for quota in self.manager_quotas.iter_mut() {
+                quota.daily_spent = 0;
+            }
+            for compartment in self.compartments.iter_mut() {
+                compartment.daily_withdrawn_amount = 0;
+            }
+            self.daily_operation_count = 0;
             self.last_reset_day = current_timestamp;
         }
+        if self.config.limit_window == LimitWindow::Rolling24h {
+            // Acotado al límite vigente: bajarlo no debe dejar el vault en estado inválido
+            self.daily_withdrawn_amount = self
+                .withdrawal_window
+                .total()
+                .min(self.daily_withdrawal_limit);
+        }
     }
 
+    /// Verificar autorización según la tabla de permisos (roles base + roles personalizados)
     pub fn check_authorization(&self, perfomer: &Pubkey, operation: &OperationType) -> bool {
-        match operation {
-            OperationType::Deposit => true,
+        self.get_permissions(perfomer) & operation.permission_bit() != 0
+    }
 
-            OperationType::Withdraw => self.admin == *perfomer || self.managers.contains(perfomer),
+    /// Obtener el bitmap de permisos efectivo de una cuenta
+    pub fn get_permissions(&self, account: &Pubkey) -> Permissions {
+        let base = [Role::Admin, Role::Manager, Role::User, Role::EmergencyContact]
+            .into_iter()
+            .filter(|role| self.has_role(account, role.clone()))
+            .fold(0, |acc, role| acc | role.permissions());
 
-            OperationType::EmergencyPause => {
-                self.admin == *perfomer || self.emergency_contacts.contains(perfomer)
-            }
+        self.role_assignments
+            .iter()
+            .filter(|a| a.account == *account)
+            .filter_map(|a| self.custom_roles.iter().find(|r| r.id == a.role_id))
+            .fold(base, |acc, role| acc | role.permissions)
+    }
+
+    /// Obtener un rol personalizado por nombre
+    pub fn get_custom_role(&self, name: &str) -> Option<&CustomRole> {
+        self.custom_roles.iter().find(|r| r.name == name)
+    }
+
+    /// Crear un rol personalizado o actualizar sus permisos
+    pub fn upsert_custom_role(
+        &mut self,
+        name: String,
+        permissions: Permissions,
+    ) -> Result<u8, &'static str> {
+        if name.is_empty() || name.len() > MAX_ROLE_NAME_LENGTH {
+            return Err("Invalid role name");
+        }
+        if permissions & Role::RESERVED_PERMISSIONS != 0 {
+            return Err("Permission reserved to admin");
+        }
+
+        if let Some(role) = self.custom_roles.iter_mut().find(|r| r.name == name) {
+            role.permissions = permissions;
+            return Ok(role.id);
+        }
+
+        if self.custom_roles.len() >= MAX_CUSTOM_ROLES {
+            return Err("Maximo numero de roles alcanzado");
+        }
+        let id = (0..=u8::MAX)
+            .find(|id| !self.custom_roles.iter().any(|r| r.id == *id))
+            .ok_or("No role id available")?;
+        self.custom_roles.push(CustomRole {
+            id,
+            name,
+            permissions,
+        });
+        Ok(id)
+    }
+
+    /// Eliminar un rol personalizado junto con sus asignaciones
+    pub fn remove_custom_role(&mut self, name: &str) -> Result<(), &'static str> {
+        let pos = self
+            .custom_roles
+            .iter()
+            .position(|r| r.name == name)
+            .ok_or("Role not found")?;
+        let role = self.custom_roles.remove(pos);
+        self.role_assignments.retain(|a| a.role_id != role.id);
+        Ok(())
+    }
+
+    /// Asignar un rol personalizado a una cuenta
+    pub fn assign_role(&mut self, account: Pubkey, name: &str) -> Result<(), &'static str> {
+        let role_id = self.get_custom_role(name).ok_or("Role not found")?.id;
+        if self
+            .role_assignments
+            .iter()
+            .any(|a| a.account == account && a.role_id == role_id)
+        {
+            return Err("Role already assigned");
+        }
+        if self.role_assignments.len() >= MAX_ROLE_ASSIGNMENTS {
+            return Err("Maximo numero de asignaciones alcanzado");
+        }
+
+        self.role_assignments.push(RoleAssignment { account, role_id });
+        Ok(())
+    }
+
+    /// Revocar un rol personalizado de una cuenta
+    pub fn revoke_role(&mut self, account: &Pubkey, name: &str) -> Result<(), &'static str> {
+        let role_id = self.get_custom_role(name).ok_or("Role not found")?.id;
+        let pos = self
+            .role_assignments
+            .iter()
+            .position(|a| a.account == *account && a.role_id == role_id)
+            .ok_or("Role not assigned")?;
+        self.role_assignments.remove(pos);
+        Ok(())
+    }
 
-            OperationType::Resume
-            | OperationType::AddManager
-            | OperationType::RemoveManager
-            | OperationType::UpdateConfig
-            | OperationType::TransferAdmin
-            | OperationType::EmergencyWithdraw => self.admin == *perfomer,
+    /// Registrar una operación privilegiada y devolver su id
+    pub fn record_operation(&mut self, current_timestamp: i64) -> Result<u64, &'static str> {
+        self.reset_daily_limits_if_needed(current_timestamp);
+        self.operation_count = self.operation_count.checked_add(1).ok_or("Counter overflow")?;
+        self.daily_operation_count = self.daily_operation_count.saturating_add(1);
+        self.last_operation_timestamp = current_timestamp;
+        Ok(self.operation_count)
+    }
+
+    /// Obtener la vista del ledger de un activo (SOL nativo o mint SPL)
+    pub fn get_asset_ledger(&self, asset: &Asset) -> Option<AssetLedger> {
+        match asset {
+            // Lo reservado para vesting no es retirable
+            Asset::Native => Some(AssetLedger {
+                balance: self.available_native_balance(),
+                daily_withdrawal_limit: self.daily_withdrawal_limit,
+                daily_withdrawn_amount: self.daily_withdrawn_amount,
+                multisig_threshold: self.config.require_multisig_for_large_amounts,
+            }),
+            Asset::Token(mint) => self.get_token_balance(mint).map(|t| AssetLedger {
+                balance: t.balance,
+                daily_withdrawal_limit: t.daily_withdrawal_limit,
+                daily_withdrawn_amount: t.daily_withdrawn_amount,
+                multisig_threshold: t.multisig_threshold,
+            }),
         }
     }
 
-    pub fn can_withdraw(&self, amount: u64, current_timestamp: i64) -> Result<(), &'static str> {
+    pub fn can_withdraw(
+        &self,
+        asset: &Asset,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), &'static str> {
         // Capa 1
-        if self.is_paused {
+        if self.is_paused_at(current_timestamp) {
             return Err("Vault is paused");
         }
 
         // Capa 2
-        if amount > self.total_balance {
+        let ledger = match self.get_asset_ledger(asset) {
+            Some(ledger) => ledger,
+            None => return Err("Asset not held by vault"),
+        };
+        if amount > ledger.balance {
             return Err("Insufficient balance");
         }
 
         // Capa 3
+        match self.exceeded_limit_tier(asset, amount, current_timestamp) {
+            Some(LimitTier::Hourly) => Err("Hourly withdrawal limit exceeded"),
+            Some(LimitTier::Daily) => Err("Daily withdrwal limit exceeded"),
+            Some(LimitTier::Weekly) => Err("Weekly withdrawal limit exceeded"),
+            Some(LimitTier::Monthly) => Err("Monthly withdrawal limit exceeded"),
+            None => Ok(()),
+        }
+    }
 
+    /// Primer tramo de límite (hora, día, semana, mes) que el retiro superaría
+    pub fn exceeded_limit_tier(
+        &self,
+        asset: &Asset,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Option<LimitTier> {
         let mut temp_vault = self.clone();
         temp_vault.reset_daily_limits_if_needed(current_timestamp);
-        if temp_vault.daily_withdrawn_amount + amount > temp_vault.daily_withdrawal_limit {
-            return Err("Daily withdrwal limit exceeded");
+
+        let exceeds = |withdrawn: u64, limit: u64| withdrawn.saturating_add(amount) > limit;
+        let counters = &temp_vault.outflow_counters;
+        let config = &temp_vault.config;
+        // Los tramos hora/semana/mes solo aplican al activo nativo
+        let native = *asset == Asset::Native;
+
+        if native && exceeds(counters.hourly.withdrawn, config.hourly_withdrawal_limit) {
+            return Some(LimitTier::Hourly);
         }
+        let daily_exceeded = temp_vault
+            .get_asset_ledger(asset)
+            .map(|l| exceeds(l.daily_withdrawn_amount, l.daily_withdrawal_limit))
+            .unwrap_or(false);
+        if daily_exceeded {
+            return Some(LimitTier::Daily);
+        }
+        if native && exceeds(counters.weekly.withdrawn, config.weekly_withdrawal_limit) {
+            return Some(LimitTier::Weekly);
+        }
+        if native && exceeds(counters.monthly.withdrawn, config.monthly_withdrawal_limit) {
+            return Some(LimitTier::Monthly);
+        }
+        None
+    }
+
+    /// Obtener el balance registrado de un mint
+    pub fn get_token_balance(&self, mint: &Pubkey) -> Option<&TokenBalance> {
+        self.token_balances.iter().find(|t| t.mint == *mint)
+    }
+
+    /// Acreditar tokens depositados; solo se aceptan mints registrados por el admin
+    pub fn credit_token(&mut self, mint: Pubkey, amount: u64) -> Result<(), &'static str> {
+        let token = self
+            .token_balances
+            .iter_mut()
+            .find(|t| t.mint == mint)
+            .ok_or("Mint not registered")?;
+        token.balance = token.balance.checked_add(amount).ok_or("Balance overflow")?;
+        Ok(())
+    }
+
+    /// Registrar un mint nuevo, sin límites configurados (retiros bloqueados)
+    fn register_token(&mut self, mint: Pubkey) -> Result<(), &'static str> {
+        if self.token_balances.len() >= MAX_TOKEN_MINTS {
+            return Err("Maximo numero de mints alcanzado");
+        }
+        self.token_balances.push(TokenBalance {
+            mint,
+            balance: 0,
+            daily_withdrawal_limit: 0,
+            daily_withdrawn_amount: 0,
+            multisig_threshold: 0,
+        });
+        Ok(())
+    }
+
+    /// Dar de baja un mint sin balance, liberando su entrada
+    pub fn remove_token(&mut self, mint: &Pubkey) -> Result<(), &'static str> {
+        let index = self
+            .token_balances
+            .iter()
+            .position(|t| t.mint == *mint)
+            .ok_or("Asset not held by vault")?;
+        if self.token_balances[index].balance > 0 {
+            return Err("Token balance not empty");
+        }
+        self.token_balances.remove(index);
+        Ok(())
+    }
+
+    /// Debitar un retiro del activo y acumular su contador diario
+    pub fn record_withdrawal(&mut self, asset: &Asset, amount: u64) -> Result<(), &'static str> {
+        let (balance, withdrawn) = match asset {
+            Asset::Native => (&mut self.total_balance, &mut self.daily_withdrawn_amount),
+            Asset::Token(mint) => {
+                let token = self
+                    .token_balances
+                    .iter_mut()
+                    .find(|t| t.mint == *mint)
+                    .ok_or("Asset not held by vault")?;
+                (&mut token.balance, &mut token.daily_withdrawn_amount)
+            }
+        };
+
+        *balance = balance.checked_sub(amount).ok_or("Insufficient balance")?;
+        *withdrawn = withdrawn.checked_add(amount).ok_or("Daily counter overflow")?;
+        // Se registra siempre, para poder cambiar de modo sin perder historial
+        if *asset == Asset::Native {
+            self.withdrawal_window.record(amount)?;
+            self.outflow_counters.record(amount)?;
+        }
+        Ok(())
+    }
 
+    /// Configurar límite diario y umbral de multisig de un activo
+    pub fn set_asset_limits(
+        &mut self,
+        asset: &Asset,
+        daily_withdrawal_limit: u64,
+        multisig_threshold: u64,
+    ) -> Result<(), &'static str> {
+        match asset {
+            Asset::Native => {
+                self.daily_withdrawal_limit = daily_withdrawal_limit;
+                self.daily_withdrawn_amount =
+                    self.daily_withdrawn_amount.min(daily_withdrawal_limit);
+                self.config.require_multisig_for_large_amounts = multisig_threshold;
+            }
+            Asset::Token(mint) => {
+                if self.get_token_balance(mint).is_none() {
+                    self.register_token(*mint)?;
+                }
+                let token = self
+                    .token_balances
+                    .iter_mut()
+                    .find(|t| t.mint == *mint)
+                    .ok_or("Asset not held by vault")?;
+                token.daily_withdrawal_limit = daily_withdrawal_limit;
+                token.daily_withdrawn_amount =
+                    token.daily_withdrawn_amount.min(daily_withdrawal_limit);
+                token.multisig_threshold = multisig_threshold;
+            }
+        }
         Ok(())
     }
 
@@ -243,14 +1242,671 @@ impl SecureVault {
         Ok(())
     }
 
-    pub fn remove_manager(&mut self, manager_to_remove: Pubkey) -> Result<(), &'static str> {
-        if let Some(pos) = self.managers.iter().position(|&x| x == manager_to_remove) {
-            self.managers.remove(pos);
-            Ok(())
-        } else {
+    /// Agregar contacto de emergencia (no puede ser el admin ni estar repetido)
+    pub fn add_emergency_contact(&mut self, contact: Pubkey) -> Result<(), &'static str> {
+        if self.emergency_contacts.len() >= MAX_EMERGENCY_CONTACTS {
+            return Err("Maximo numero de contactos de emergencia alcanzado");
+        }
+        if contact == Pubkey::default() || contact == self.admin {
+            return Err("El admin no puede ser contacto de emergencia");
+        }
+        if self.emergency_contacts.contains(&contact) {
+            return Err("Ya existe este contacto de emergencia");
+        }
+        self.emergency_contacts.push(contact);
+        Ok(())
+    }
+
+    /// Quitar contacto de emergencia y su aprobación de una recuperación en curso
+    pub fn remove_emergency_contact(&mut self, contact: &Pubkey) -> Result<(), &'static str> {
+        let pos = self
+            .emergency_contacts
+            .iter()
+            .position(|c| c == contact)
+            .ok_or("Contacto de emergencia no encontrado")?;
+        self.emergency_contacts.remove(pos);
+
+        let threshold = self.recovery_threshold as usize;
+        if let Some(recovery) = self.admin_recovery.as_mut() {
+            recovery.approvals.retain(|a| a != contact);
+            if recovery.approvals.len() < threshold {
+                recovery.quorum_reached_at = None;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove_manager(&mut self, manager_to_remove: Pubkey) -> Result<(), &'static str> {
+        if let Some(pos) = self.managers.iter().position(|&x| x == manager_to_remove) {
+            self.managers.remove(pos);
+            self.manager_quotas.retain(|q| q.manager != manager_to_remove);
+            self.multisig_policy
+                .signer_weights
+                .retain(|w| w.signer != manager_to_remove);
+            Ok(())
+        } else {
             Err("Manager not found")
         }
     }
+
+    /// Peso de firma de una cuenta según la política de multisig (0 si no puede firmar)
+    pub fn signer_weight(&self, signer: &Pubkey) -> u16 {
+        let policy = &self.multisig_policy;
+        if let Some(entry) = policy.signer_weights.iter().find(|w| w.signer == *signer) {
+            return entry.weight;
+        }
+
+        if self.admin == *signer {
+            policy.admin_weight
+        } else if self.managers.contains(signer) {
+            policy.manager_weight
+        } else {
+            0
+        }
+    }
+
+    /// Peso total alcanzable sumando admin y managers
+    pub fn total_signing_weight(&self) -> u32 {
+        std::iter::once(&self.admin)
+            .chain(self.managers.iter())
+            .map(|signer| self.signer_weight(signer) as u32)
+            .sum()
+    }
+
+    /// Obtener la cuota de gasto de un manager
+    pub fn get_manager_quota(&self, manager: &Pubkey) -> Option<&ManagerQuota> {
+        self.manager_quotas.iter().find(|q| q.manager == *manager)
+    }
+
+    /// Configurar la cuota de gasto de un manager existente
+    pub fn set_manager_quota(
+        &mut self,
+        manager: Pubkey,
+        daily_limit: u64,
+        per_transaction_limit: u64,
+    ) -> Result<(), &'static str> {
+        if !self.managers.contains(&manager) {
+            return Err("Manager not found");
+        }
+        if per_transaction_limit > daily_limit {
+            return Err("Per-transaction limit above daily limit");
+        }
+
+        if let Some(quota) = self.manager_quotas.iter_mut().find(|q| q.manager == manager) {
+            quota.daily_limit = daily_limit;
+            quota.per_transaction_limit = per_transaction_limit;
+            quota.daily_spent = quota.daily_spent.min(daily_limit);
+        } else {
+            self.manager_quotas.push(ManagerQuota {
+                manager,
+                daily_limit,
+                per_transaction_limit,
+                daily_spent: 0,
+            });
+        }
+        Ok(())
+    }
+
+    /// Verificar que un retiro cabe en la cuota del ejecutor (admin y managers sin cuota: libres)
+    pub fn check_manager_quota(
+        &self,
+        performer: &Pubkey,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), &'static str> {
+        let mut temp_vault = self.clone();
+        temp_vault.reset_daily_limits_if_needed(current_timestamp);
+
+        let quota = match temp_vault.get_manager_quota(performer) {
+            Some(quota) => quota,
+            None => return Ok(()),
+        };
+        if amount > quota.per_transaction_limit {
+            return Err("Per-transaction quota exceeded");
+        }
+        if quota.daily_spent.saturating_add(amount) > quota.daily_limit {
+            return Err("Daily quota exceeded");
+        }
+        Ok(())
+    }
+
+    /// Proponer un nuevo admin (el proponente cuenta como primera aprobación); una propuesta
+    /// que no alcanzó quorum en `RECOVERY_PROPOSAL_EXPIRY_SECONDS` puede reemplazarse
+    pub fn propose_admin_recovery(
+        &mut self,
+        guardian: Pubkey,
+        proposed_admin: Pubkey,
+        current_timestamp: i64,
+    ) -> Result<(), &'static str> {
+        if self.has_active_recovery(current_timestamp) {
+            return Err("Recovery already in progress");
+        }
+        if proposed_admin == Pubkey::default()
+            || proposed_admin == self.admin
+            || self.managers.contains(&proposed_admin)
+        {
+            return Err("Invalid proposed admin");
+        }
+
+        self.admin_recovery = Some(AdminRecovery {
+            proposed_admin,
+            proposed_at: current_timestamp,
+            approvals: Vec::new(),
+            quorum_reached_at: None,
+        });
+        self.approve_admin_recovery(guardian, proposed_admin, current_timestamp)
+    }
+
+    /// Aprobar la propuesta de recuperación; inicia el time-lock al alcanzar quorum
+    pub fn approve_admin_recovery(
+        &mut self,
+        guardian: Pubkey,
+        proposed_admin: Pubkey,
+        current_timestamp: i64,
+    ) -> Result<(), &'static str> {
+        if !self.emergency_contacts.contains(&guardian) {
+            return Err("Not an emergency contact");
+        }
+        if !self.has_active_recovery(current_timestamp) {
+            return Err("No recovery in progress");
+        }
+        let threshold = self.recovery_threshold as usize;
+        let recovery = self.admin_recovery.as_mut().ok_or("No recovery in progress")?;
+        if recovery.proposed_admin != proposed_admin {
+            return Err("Proposal mismatch");
+        }
+        if recovery.approvals.contains(&guardian) {
+            return Err("Already approved");
+        }
+
+        recovery.approvals.push(guardian);
+        if recovery.quorum_reached_at.is_none() && recovery.approvals.len() >= threshold {
+            recovery.quorum_reached_at = Some(current_timestamp);
+        }
+        Ok(())
+    }
+
+    /// Recuperación en curso: con quorum, o aún dentro del plazo para alcanzarlo
+    pub fn has_active_recovery(&self, current_timestamp: i64) -> bool {
+        self.admin_recovery.as_ref().is_some_and(|recovery| {
+            recovery.quorum_reached_at.is_some()
+                || current_timestamp
+                    < recovery
+                        .proposed_at
+                        .saturating_add(RECOVERY_PROPOSAL_EXPIRY_SECONDS)
+        })
+    }
+
+    /// Aprobaciones de la recuperación que siguen siendo emergency contacts
+    pub fn current_recovery_approvals(&self) -> usize {
+        self.admin_recovery.as_ref().map_or(0, |recovery| {
+            recovery
+                .approvals
+                .iter()
+                .filter(|a| self.emergency_contacts.contains(a))
+                .count()
+        })
+    }
+
+    /// Momento a partir del cual la recuperación puede ejecutarse
+    pub fn recovery_executable_at(&self) -> Option<i64> {
+        self.admin_recovery
+            .as_ref()
+            .and_then(|r| r.quorum_reached_at)
+            .map(|t| t.saturating_add(self.recovery_delay_seconds))
+    }
+
+    /// Pausa vigente: indefinida (admin o disyuntor) o temporal aún no expirada
+    pub fn is_paused_at(&self, current_timestamp: i64) -> bool {
+        self.is_paused
+            || self
+                .timed_pause
+                .as_ref()
+                .is_some_and(|pause| current_timestamp < pause.expires_at)
+    }
+
+    /// Iniciar una pausa temporal; tras expirar, la siguiente exige esperar el cooldown
+    pub fn start_timed_pause(
+        &mut self,
+        paused_by: Pubkey,
+        reason: PauseReason,
+        duration_seconds: i64,
+        current_timestamp: i64,
+    ) -> Result<(), &'static str> {
+        if duration_seconds <= 0 || duration_seconds > MAX_TIMED_PAUSE_SECONDS {
+            return Err("Invalid pause duration");
+        }
+        if let Some(previous) = &self.timed_pause {
+            if current_timestamp < previous.expires_at {
+                return Err("Timed pause already active");
+            }
+            if current_timestamp < previous.expires_at.saturating_add(TIMED_PAUSE_COOLDOWN_SECONDS)
+            {
+                return Err("Timed pause cooldown active");
+            }
+        }
+
+        self.timed_pause = Some(TimedPause {
+            paused_by,
+            reason,
+            paused_at: current_timestamp,
+            expires_at: current_timestamp.saturating_add(duration_seconds),
+        });
+        Ok(())
+    }
+
+    /// Extender la pausa temporal vigente (solo admin)
+    pub fn extend_timed_pause(
+        &mut self,
+        extension_seconds: i64,
+        current_timestamp: i64,
+    ) -> Result<(), &'static str> {
+        if extension_seconds <= 0 {
+            return Err("Invalid pause duration");
+        }
+        let pause = self
+            .timed_pause
+            .as_mut()
+            .filter(|pause| current_timestamp < pause.expires_at)
+            .ok_or("No active timed pause")?;
+        pause.expires_at = pause.expires_at.saturating_add(extension_seconds);
+        Ok(())
+    }
+
+    /// Levantar cualquier pausa; la temporal queda registrada como expirada ahora
+    pub fn resume(&mut self, current_timestamp: i64) {
+        self.is_paused = false;
+        if let Some(pause) = self.timed_pause.as_mut() {
+            pause.expires_at = pause.expires_at.min(current_timestamp);
+        }
+    }
+
+    /// Verificar si venció el timeout de inactividad del dead-man's switch
+    pub fn is_inactive(&self, current_timestamp: i64) -> bool {
+        self.dead_man_switch.as_ref().is_some_and(|switch| {
+            current_timestamp.saturating_sub(self.last_operation_timestamp)
+                >= switch.inactivity_timeout_seconds
+        })
+    }
+
+    /// Iniciar el reclamo de fondos de un vault inactivo
+    pub fn start_inactivity_claim(
+        &mut self,
+        claimant: Pubkey,
+        current_timestamp: i64,
+    ) -> Result<(), &'static str> {
+        let switch = self
+            .dead_man_switch
+            .as_ref()
+            .ok_or("Dead-man's switch not configured")?;
+        let eligible = switch.beneficiary == Some(claimant)
+            || self.emergency_contacts.contains(&claimant);
+        if !eligible {
+            return Err("Claimant not eligible");
+        }
+        // Un reclamo anterior a actividad posterior ya no es válido y puede reemplazarse
+        if self
+            .inactivity_claim
+            .as_ref()
+            .is_some_and(|claim| claim.started_at >= self.last_operation_timestamp)
+        {
+            return Err("Inactivity claim already in progress");
+        }
+        if !self.is_inactive(current_timestamp) {
+            return Err("Vault not inactive");
+        }
+
+        self.inactivity_claim = Some(InactivityClaim {
+            claimant,
+            started_at: current_timestamp,
+            executable_at: current_timestamp.saturating_add(switch.claim_delay_seconds),
+        });
+        Ok(())
+    }
+
+    /// Liquidar el reclamo: todo el SOL salvo lo reservado para vesting, compartimentos incluidos;
+    /// los saldos de tokens no se liquidan
+    pub fn sweep_inactive_funds(&mut self) -> u64 {
+        let amount = self.total_balance.saturating_sub(self.vesting_reserved);
+        self.total_balance -= amount;
+        for compartment in self.compartments.iter_mut() {
+            compartment.balance = 0;
+        }
+        self.inactivity_claim = None;
+        amount
+    }
+
+    /// Proponer transferencia de admin con plazo de aceptación
+    pub fn propose_admin(
+        &mut self,
+        new_admin: Pubkey,
+        current_timestamp: i64,
+    ) -> Result<(), &'static str> {
+        if new_admin == Pubkey::default() || new_admin == self.admin {
+            return Err("Invalid new admin");
+        }
+        if self.managers.contains(&new_admin) {
+            return Err("El admin no puede ser manager");
+        }
+
+        self.pending_admin = Some(PendingAdmin {
+            new_admin,
+            proposed_at: current_timestamp,
+            expires_at: current_timestamp.saturating_add(ADMIN_PROPOSAL_EXPIRY_SECONDS),
+        });
+        Ok(())
+    }
+
+    /// Aceptar la transferencia propuesta (solo la nueva clave y antes del plazo)
+    pub fn accept_admin(
+        &mut self,
+        new_admin: &Pubkey,
+        current_timestamp: i64,
+    ) -> Result<Pubkey, &'static str> {
+        let pending = self.pending_admin.as_ref().ok_or("No admin proposal")?;
+        if pending.new_admin != *new_admin {
+            return Err("Not the proposed admin");
+        }
+        if current_timestamp > pending.expires_at {
+            return Err("Admin proposal expired");
+        }
+
+        self.rotate_admin(*new_admin)
+    }
+
+    /// Rotar el admin, limpiando permisos y pesos asociados al admin anterior
+    pub fn rotate_admin(&mut self, new_admin: Pubkey) -> Result<Pubkey, &'static str> {
+        if new_admin == Pubkey::default() || new_admin == self.admin {
+            return Err("Invalid new admin");
+        }
+        if self.managers.contains(&new_admin) {
+            return Err("El admin no puede ser manager");
+        }
+
+        let old_admin = self.admin;
+        self.admin = new_admin;
+        self.role_assignments.retain(|a| a.account != old_admin);
+        self.multisig_policy
+            .signer_weights
+            .retain(|w| w.signer != old_admin);
+        self.admin_recovery = None;
+        self.pending_admin = None;
+        Ok(old_admin)
+    }
+
+    /// Acumular un retiro en la cuota diaria del ejecutor
+    pub fn record_manager_spend(
+        &mut self,
+        performer: &Pubkey,
+        amount: u64,
+    ) -> Result<(), &'static str> {
+        if let Some(quota) = self.manager_quotas.iter_mut().find(|q| q.manager == *performer) {
+            quota.daily_spent = quota.daily_spent.checked_add(amount).ok_or("Quota overflow")?;
+        }
+        Ok(())
+    }
+}
+
+impl VestingSchedule {
+    pub const MAX_SIZE: usize = 8 + 32 + 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// Crear calendario validando start <= cliff <= end y start < end
+    pub fn new(
+        vault: Pubkey,
+        id: u64,
+        beneficiary: Pubkey,
+        total_amount: u64,
+        start_timestamp: i64,
+        cliff_timestamp: i64,
+        end_timestamp: i64,
+    ) -> Result<Self, &'static str> {
+        if beneficiary == Pubkey::default() || total_amount == 0 {
+            return Err("Invalid vesting parameters");
+        }
+        if start_timestamp >= end_timestamp
+            || cliff_timestamp < start_timestamp
+            || cliff_timestamp > end_timestamp
+        {
+            return Err("Invalid vesting timestamps");
+        }
+
+        Ok(Self {
+            discriminator: VESTING_DISCRIMINATOR,
+            vault,
+            id,
+            beneficiary,
+            total_amount,
+            claimed_amount: 0,
+            start_timestamp,
+            cliff_timestamp,
+            end_timestamp,
+            bump: 0,
+        })
+    }
+
+    pub fn get_seeds(vault_pubkey: &Pubkey, schedule_id: u64) -> Vec<Vec<u8>> {
+        vec![
+            VESTING_SEED.to_vec(),
+            vault_pubkey.to_bytes().to_vec(),
+            schedule_id.to_le_bytes().to_vec(),
+        ]
+    }
+
+    pub fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+
+    /// Monto liberado a la fecha: nada antes del cliff, lineal desde `start` hasta `end`
+    pub fn vested_amount(&self, current_timestamp: i64) -> u64 {
+        if current_timestamp < self.cliff_timestamp {
+            return 0;
+        }
+        if current_timestamp >= self.end_timestamp {
+            return self.total_amount;
+        }
+
+        let elapsed = (current_timestamp - self.start_timestamp) as u128;
+        let duration = (self.end_timestamp - self.start_timestamp) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
+    }
+
+    /// Monto liberado pendiente de reclamar
+    pub fn claimable_amount(&self, current_timestamp: i64) -> u64 {
+        self.vested_amount(current_timestamp)
+            .saturating_sub(self.claimed_amount)
+    }
+
+    /// Registrar un reclamo
+    pub fn record_claim(&mut self, amount: u64) -> Result<(), &'static str> {
+        let claimed = self
+            .claimed_amount
+            .checked_add(amount)
+            .ok_or("Claim overflow")?;
+        if claimed > self.total_amount {
+            return Err("Claim exceeds vesting total");
+        }
+        self.claimed_amount = claimed;
+        Ok(())
+    }
+}
+
+impl RecurringPayment {
+    pub const MAX_SIZE: usize = 8 + 32 + 8 + 32 + 8 + 8 + 4 + 4 + 8 + 1 + 1;
+
+    /// Crear pago recurrente pendiente de aprobación
+    pub fn new(
+        vault: Pubkey,
+        id: u64,
+        recipient: Pubkey,
+        amount: u64,
+        interval_seconds: i64,
+        total_payments: u32,
+        first_payment_at: i64,
+    ) -> Result<Self, &'static str> {
+        if recipient == Pubkey::default() || amount == 0 || total_payments == 0 {
+            return Err("Invalid recurring payment parameters");
+        }
+        if interval_seconds < MIN_RECURRING_INTERVAL_SECONDS {
+            return Err("Recurring interval too short");
+        }
+
+        Ok(Self {
+            discriminator: RECURRING_PAYMENT_DISCRIMINATOR,
+            vault,
+            id,
+            recipient,
+            amount,
+            interval_seconds,
+            total_payments,
+            payments_made: 0,
+            next_payment_at: first_payment_at,
+            status: OperationStatus::Pending,
+            bump: 0,
+        })
+    }
+
+    pub fn get_seeds(vault_pubkey: &Pubkey, payment_id: u64) -> Vec<Vec<u8>> {
+        vec![
+            RECURRING_PAYMENT_SEED.to_vec(),
+            vault_pubkey.to_bytes().to_vec(),
+            payment_id.to_le_bytes().to_vec(),
+        ]
+    }
+
+    pub fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+
+    /// Activar tras la aprobación por multisig
+    pub fn approve(&mut self) -> Result<(), &'static str> {
+        if self.status != OperationStatus::Pending {
+            return Err("Recurring payment not pending approval");
+        }
+        self.status = OperationStatus::Approved;
+        Ok(())
+    }
+
+    /// Verificar si corresponde un pago
+    pub fn is_due(&self, current_timestamp: i64) -> bool {
+        self.status == OperationStatus::Approved
+            && self.payments_made < self.total_payments
+            && current_timestamp >= self.next_payment_at
+    }
+
+    /// Registrar un pago; mantiene la cadencia original aunque el disparo llegue tarde
+    pub fn record_payment(&mut self) -> Result<(), &'static str> {
+        self.payments_made = self.payments_made.checked_add(1).ok_or("Payment overflow")?;
+        self.next_payment_at = self
+            .next_payment_at
+            .checked_add(self.interval_seconds)
+            .ok_or("Schedule overflow")?;
+        if self.payments_made >= self.total_payments {
+            self.status = OperationStatus::Executed;
+        }
+        Ok(())
+    }
+}
+
+impl DepositorLedger {
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+
+    pub fn new(vault: Pubkey, depositor: Pubkey, bump: u8) -> Self {
+        Self {
+            discriminator: DEPOSITOR_LEDGER_DISCRIMINATOR,
+            vault,
+            depositor,
+            total_deposited: 0,
+            total_refunded: 0,
+            bump,
+        }
+    }
+
+    pub fn get_seeds(vault_pubkey: &Pubkey, depositor: &Pubkey) -> Vec<Vec<u8>> {
+        vec![
+            DEPOSITOR_SEED.to_vec(),
+            vault_pubkey.to_bytes().to_vec(),
+            depositor.to_bytes().to_vec(),
+        ]
+    }
+
+    /// Balance del depositante aún no devuelto
+    pub fn balance(&self) -> u64 {
+        self.total_deposited.saturating_sub(self.total_refunded)
+    }
+
+    pub fn record_deposit(&mut self, amount: u64) -> Result<(), &'static str> {
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(amount)
+            .ok_or("Deposit overflow")?;
+        Ok(())
+    }
+
+    /// Registrar una devolución (nunca más de lo depositado)
+    pub fn record_refund(&mut self, amount: u64) -> Result<(), &'static str> {
+        if amount > self.balance() {
+            return Err("Refund exceeds depositor balance");
+        }
+        self.total_refunded += amount;
+        Ok(())
+    }
+}
+
+impl AddressBook {
+    pub const MAX_SIZE: usize =
+        8 + 32 + 4 + MAX_ADDRESS_BOOK_ENTRIES * (32 + 4 + MAX_LABEL_LENGTH + 8) + 1;
+
+    /// Crear address book vacío para un vault
+    pub fn new(vault: Pubkey, bump: u8) -> Self {
+        Self {
+            discriminator: ADDRESS_BOOK_DISCRIMINATOR,
+            vault,
+            entries: Vec::new(),
+            bump,
+        }
+    }
+
+    pub fn get_seeds(vault_pubkey: &Pubkey) -> Vec<Vec<u8>> {
+        vec![ADDRESS_BOOK_SEED.to_vec(), vault_pubkey.to_bytes().to_vec()]
+    }
+
+    /// Verificar si el destinatario está aprobado
+    pub fn contains(&self, address: &Pubkey) -> bool {
+        self.entries.iter().any(|e| e.address == *address)
+    }
+
+    pub fn add_entry(
+        &mut self,
+        address: Pubkey,
+        label: String,
+        current_timestamp: i64,
+    ) -> Result<(), &'static str> {
+        if self.entries.len() >= MAX_ADDRESS_BOOK_ENTRIES {
+            return Err("Maximo numero de destinatarios alcanzado");
+        }
+        if label.len() > MAX_LABEL_LENGTH {
+            return Err("Etiqueta demasiado larga");
+        }
+        if self.contains(&address) {
+            return Err("Ya existe este destinatario");
+        }
+
+        self.entries.push(AddressBookEntry {
+            address,
+            label,
+            added_at: current_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn remove_entry(&mut self, address: &Pubkey) -> Result<(), &'static str> {
+        if let Some(pos) = self.entries.iter().position(|e| e.address == *address) {
+            self.entries.remove(pos);
+            Ok(())
+        } else {
+            Err("Destinatario no encontrado")
+        }
+    }
 }
 
 impl AuditLog {
@@ -274,6 +1930,9 @@ impl AuditLog {
             error_code: None,
             gas_used: 0,
             bump: 0,
+            correlation_id: None,
+            operation_status: None,
+            pause: None,
         }
     }
 
@@ -314,6 +1973,50 @@ impl AuditLog {
         self.target_account = Some(target);
         self
     }
+
+    /// Registrar el estado de la operación pendiente asociada
+    pub fn with_operation_status(mut self, status: OperationStatus) -> Self {
+        self.operation_status = Some(status);
+        self
+    }
+
+    /// Vincular la entrada a un grupo de operaciones (p.ej. un batch)
+    pub fn with_correlation_id(mut self, correlation_id: u64) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    /// Registrar la pausa temporal vigente tras la operación
+    pub fn with_pause(mut self, pause: TimedPause) -> Self {
+        self.pause = Some(pause);
+        self
+    }
+}
+
+impl Default for MultisigPolicy {
+    fn default() -> Self {
+        Self {
+            admin_weight: DEFAULT_ADMIN_WEIGHT,
+            manager_weight: DEFAULT_MANAGER_WEIGHT,
+            signer_weights: Vec::new(),
+            // Retiros time-locked: admin + al menos un manager
+            thresholds: vec![OperationThreshold {
+                operation_type: OperationType::Withdraw,
+                threshold: DEFAULT_ADMIN_WEIGHT + DEFAULT_MANAGER_WEIGHT,
+            }],
+        }
+    }
+}
+
+impl MultisigPolicy {
+    /// Umbral de peso para un tipo de operación
+    pub fn threshold_for(&self, operation_type: &OperationType) -> u16 {
+        self.thresholds
+            .iter()
+            .find(|t| t.operation_type == *operation_type)
+            .map(|t| t.threshold)
+            .unwrap_or(self.admin_weight)
+    }
 }
 
 impl SecurityConfig {
@@ -326,6 +2029,14 @@ impl SecurityConfig {
             max_managers: 5,
             withdrawal_delay_seconds: 3600,
             auto_pause_on_suspicious_activity: false,
+            quote_limits: None,
+            refundable_mode: false,
+            limit_window: LimitWindow::CalendarDay,
+            hourly_withdrawal_limit: u64::MAX,
+            weekly_withdrawal_limit: u64::MAX,
+            monthly_withdrawal_limit: u64::MAX,
+            withdrawal_delay_tiers: Vec::new(),
+            circuit_breaker: None,
         }
     }
 
@@ -338,20 +2049,221 @@ impl SecurityConfig {
             max_managers: 3,
             withdrawal_delay_seconds: 3600,
             auto_pause_on_suspicious_activity: true,
+            quote_limits: None,
+            refundable_mode: false,
+            limit_window: LimitWindow::Rolling24h,
+            hourly_withdrawal_limit: u64::MAX,
+            weekly_withdrawal_limit: u64::MAX,
+            monthly_withdrawal_limit: u64::MAX,
+            withdrawal_delay_tiers: Vec::new(),
+            circuit_breaker: Some(CircuitBreakerConfig {
+                window_hours: 24,
+                max_outflow_bps: 2_000, // 20% del TVL
+            }),
         }
     }
 
     /// Validar que la configuración es válida
     pub fn is_valid(&self) -> bool {
-        if self.max_daily_operations <= 5000 {
+        if self.max_daily_operations == 0 || self.max_daily_operations > 5000 {
             return false;
         }
-        true
+        self.min_time_between_operations >= 0
+            && self.require_multisig_for_large_amounts > 0
+            && self.max_managers as usize <= MAX_MANAGER
+            && self.withdrawal_delay_seconds >= 0
+            && self.quote_limits.iter().all(QuoteLimits::is_valid)
+            && self.hourly_withdrawal_limit > 0
+            && self.weekly_withdrawal_limit > 0
+            && self.monthly_withdrawal_limit > 0
+            && self.has_valid_delay_tiers()
+            && self.circuit_breaker.iter().all(CircuitBreakerConfig::is_valid)
+    }
+
+    /// Tramos ordenados por monto creciente, con delays no decrecientes
+    fn has_valid_delay_tiers(&self) -> bool {
+        let tiers = &self.withdrawal_delay_tiers;
+        tiers.len() <= MAX_DELAY_TIERS
+            && tiers.iter().all(|t| t.min_amount > 0 && t.delay_seconds >= 0)
+            && tiers.windows(2).all(|pair| {
+                pair[0].min_amount < pair[1].min_amount
+                    && pair[0].delay_seconds <= pair[1].delay_seconds
+            })
+    }
+
+    /// Tramo de delay aplicable a un retiro nativo: el de mayor `min_amount` alcanzado
+    pub fn delay_tier_for(&self, amount: u64) -> Option<&DelayTier> {
+        self.withdrawal_delay_tiers
+            .iter()
+            .rev()
+            .find(|tier| amount >= tier.min_amount)
+    }
+}
+
+impl DeadManSwitch {
+    /// Validar timeout, time-lock y beneficiario
+    pub fn is_valid(&self) -> bool {
+        self.inactivity_timeout_seconds >= MIN_INACTIVITY_TIMEOUT_SECONDS
+            && self.claim_delay_seconds >= MIN_INACTIVITY_CLAIM_DELAY_SECONDS
+            && self.beneficiary != Some(Pubkey::default())
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Validar ventana y porcentaje del disyuntor
+    pub fn is_valid(&self) -> bool {
+        self.window_hours > 0
+            && self.window_hours as usize <= WITHDRAWAL_WINDOW_BUCKETS
+            && self.max_outflow_bps > 0
+            && self.max_outflow_bps <= MAX_OUTFLOW_BPS
+    }
+}
+
+impl PeriodCounter {
+    /// Reiniciar el acumulado si el timestamp cae en un período posterior
+    pub fn roll(&mut self, current_timestamp: i64, period_seconds: i64) {
+        let period = current_timestamp / period_seconds;
+        if period > self.period {
+            self.period = period;
+            self.withdrawn = 0;
+        }
+    }
+}
+
+impl OutflowCounters {
+    /// Avanzar cada tramo a su período actual
+    pub fn roll(&mut self, current_timestamp: i64) {
+        self.hourly.roll(current_timestamp, SECONDS_PER_HOUR);
+        self.weekly.roll(current_timestamp, SECONDS_PER_WEEK);
+        self.monthly.roll(current_timestamp, SECONDS_PER_MONTH);
+    }
+
+    /// Acumular un retiro nativo en todos los tramos
+    pub fn record(&mut self, amount: u64) -> Result<(), &'static str> {
+        for counter in [&mut self.hourly, &mut self.weekly, &mut self.monthly] {
+            counter.withdrawn = counter
+                .withdrawn
+                .checked_add(amount)
+                .ok_or("Outflow counter overflow")?;
+        }
+        Ok(())
+    }
+}
+
+impl WithdrawalWindow {
+    /// Avanzar hasta la hora actual descartando los buckets fuera de la ventana
+    pub fn advance(&mut self, current_timestamp: i64) {
+        let hour = current_timestamp / SECONDS_PER_HOUR;
+        if hour <= self.current_hour {
+            return;
+        }
+
+        let elapsed = (hour - self.current_hour).min(WITHDRAWAL_WINDOW_BUCKETS as i64);
+        for offset in 1..=elapsed {
+            let index = (self.current_hour + offset).rem_euclid(WITHDRAWAL_WINDOW_BUCKETS as i64);
+            self.buckets[index as usize] = 0;
+        }
+        self.current_hour = hour;
+    }
+
+    /// Acumular un retiro en el bucket de la hora actual
+    pub fn record(&mut self, amount: u64) -> Result<(), &'static str> {
+        let index = self.current_hour.rem_euclid(WITHDRAWAL_WINDOW_BUCKETS as i64) as usize;
+        self.buckets[index] = self.buckets[index]
+            .checked_add(amount)
+            .ok_or("Window bucket overflow")?;
+        Ok(())
+    }
+
+    /// Retirado en las últimas `hours` horas, sin contar lo anterior a `since_hour` ni los
+    /// primeros `since_offset` lamports de esa hora
+    pub fn total_since(&self, hours: u8, since_hour: i64, since_offset: u64) -> u64 {
+        let first = (self.current_hour - hours as i64 + 1).max(since_hour);
+        (first..=self.current_hour).fold(0u64, |acc, hour| {
+            let index = hour.rem_euclid(WITHDRAWAL_WINDOW_BUCKETS as i64) as usize;
+            let amount = if hour == since_hour {
+                self.buckets[index].saturating_sub(since_offset)
+            } else {
+                self.buckets[index]
+            };
+            acc.saturating_add(amount)
+        })
+    }
+
+    /// Retirado en la hora actual
+    pub fn current_bucket(&self) -> u64 {
+        self.buckets[self.current_hour.rem_euclid(WITHDRAWAL_WINDOW_BUCKETS as i64) as usize]
+    }
+
+    /// Total retirado en las últimas 24 horas
+    pub fn total(&self) -> u64 {
+        self.buckets
+            .iter()
+            .fold(0u64, |acc, amount| acc.saturating_add(*amount))
+    }
+}
+
+impl QuoteLimits {
+    /// Validar que los límites en moneda de cotización son coherentes
+    pub fn is_valid(&self) -> bool {
+        self.price_feed != Pubkey::default()
+            && self.daily_withdrawal_limit > 0
+            && self.multisig_threshold > 0
+            && self.max_staleness_seconds > 0
+            && self.max_confidence_bps > 0
+            && self.max_confidence_bps <= MAX_CONFIDENCE_BPS
+    }
+}
+
+impl PriceFeed {
+    pub const LEN: usize = 8 + 8 + 8 + 4 + 8;
+
+    pub fn new(price: i64, confidence: u64, exponent: i32, publish_time: i64) -> Self {
+        Self {
+            discriminator: PRICE_FEED_DISCRIMINATOR,
+            price,
+            confidence,
+            exponent,
+            publish_time,
+        }
+    }
+
+    /// Valor de un monto en lamports expresado en unidades de `QUOTE_DECIMALS`
+    pub fn quote_value(&self, lamports: u64) -> Option<u64> {
+        let price = u128::try_from(self.price).ok()?;
+        let value = (lamports as u128).checked_mul(price)?;
+        let scale = QUOTE_DECIMALS + self.exponent - LAMPORTS_DECIMALS;
+        let value = if scale >= 0 {
+            value.checked_mul(10u128.checked_pow(scale as u32)?)?
+        } else {
+            value / 10u128.checked_pow(scale.unsigned_abs())?
+        };
+        u64::try_from(value).ok()
+    }
+
+    /// Intervalo de confianza relativo al precio, en puntos básicos
+    pub fn confidence_bps(&self) -> Option<u64> {
+        let price = u64::try_from(self.price).ok().filter(|p| *p > 0)?;
+        let scaled = (self.confidence as u128).checked_mul(MAX_CONFIDENCE_BPS as u128)?;
+        u64::try_from(scaled / price as u128).ok()
+    }
+
+    /// Datos de una cuenta de oráculo simulada para pruebas locales
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn mock_account_data(
+        price: i64,
+        confidence: u64,
+        exponent: i32,
+        publish_time: i64,
+    ) -> Vec<u8> {
+        Self::new(price, confidence, exponent, publish_time)
+            .try_to_vec()
+            .expect("PriceFeed serialization cannot fail")
     }
 }
 
 impl PendingOperation {
-    pub const MAX_SIZE: usize = 513;
+    pub const MAX_SIZE: usize = 1536; // Admite admin + MAX_MANAGER aprobaciones y rechazos
 
     /// Crear nueva operación pendiente
     pub fn new(
@@ -375,9 +2287,20 @@ impl PendingOperation {
             received_signatures: Vec::new(),
             created_at: current_timestamp,
             bump: 0,
+            required_weight: 0,
+            received_weight: 0,
+            status: OperationStatus::Pending,
+            rejected_by: Vec::new(),
+            rejected_weight: 0,
         }
     }
 
+    /// Fijar el peso acumulado necesario para ejecutar
+    pub fn with_required_weight(mut self, required_weight: u16) -> Self {
+        self.required_weight = required_weight;
+        self
+    }
+
     pub fn set_bump(&mut self, bump: u8) {
         self.bump = bump;
     }
@@ -395,23 +2318,77 @@ impl PendingOperation {
         current_timestamp >= self.scheduled_execution && self.has_sufficient_signatures()
     }
 
-    /// Agregar firma a la operación
-    pub fn add_signature(&mut self, signer: Pubkey) -> bool {
-        if !self.required_signatures.contains(&signer) {
+    /// Verificar si la cuenta ya votó (aprobación o rechazo)
+    pub fn has_voted(&self, signer: &Pubkey) -> bool {
+        self.received_signatures.contains(signer) || self.rejected_by.contains(signer)
+    }
+
+    /// Agregar firma a la operación acumulando su peso
+    pub fn add_signature(&mut self, signer: Pubkey, weight: u16) -> bool {
+        if !self.is_active() || !self.required_signatures.contains(&signer) {
+            return false;
+        }
+
+        if self.has_voted(&signer) || weight == 0 {
             return false;
         }
 
-        if self.received_signatures.contains(&signer) {
+        self.received_signatures.push(signer);
+        self.received_weight = self.received_weight.saturating_add(weight);
+        if self.has_sufficient_signatures() {
+            self.status = OperationStatus::Approved;
+        }
+        true
+    }
+
+    /// Agregar firma sin peso (layout v1): aprueba cuando firman todos los requeridos
+    pub fn add_legacy_signature(&mut self, signer: Pubkey) -> bool {
+        if !self.is_active()
+            || !self.required_signatures.contains(&signer)
+            || self.has_voted(&signer)
+        {
             return false;
         }
 
         self.received_signatures.push(signer);
+        let all_signed = self
+            .required_signatures
+            .iter()
+            .all(|required| self.received_signatures.contains(required));
+        if all_signed {
+            self.received_weight = self.received_weight.max(self.required_weight);
+            self.status = OperationStatus::Approved;
+        }
+        true
+    }
+
+    /// Agregar rechazo; bloquea la operación si el peso restante ya no alcanza el umbral
+    pub fn add_rejection(&mut self, signer: Pubkey, weight: u16, total_weight: u32) -> bool {
+        if !self.is_active() || !self.required_signatures.contains(&signer) {
+            return false;
+        }
+
+        if self.has_voted(&signer) || weight == 0 {
+            return false;
+        }
+
+        self.rejected_by.push(signer);
+        self.rejected_weight = self.rejected_weight.saturating_add(weight);
+        let reachable = total_weight.saturating_sub(self.rejected_weight as u32);
+        if reachable < self.required_weight as u32 {
+            self.status = OperationStatus::Rejected;
+        }
         true
     }
 
-    /// Verificar si tiene suficientes firmas
+    /// Verificar si el peso acumulado alcanza el umbral
     pub fn has_sufficient_signatures(&self) -> bool {
-        self.received_signatures.len() >= self.required_signatures.len()
+        self.received_weight >= self.required_weight
+    }
+
+    /// Verificar si la operación sigue abierta (ni ejecutada, rechazada ni expirada)
+    pub fn is_active(&self) -> bool {
+        matches!(self.status, OperationStatus::Pending | OperationStatus::Approved)
     }
 
     /// Verificar si la operación ha expirado
@@ -420,3 +2397,305 @@ impl PendingOperation {
         current_timestamp > expiry_time
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use solana_program::{entrypoint::SUCCESS, program_stubs};
+
+    /// Sysvar `Clock` fijo en 0 para los constructores que lo leen
+    struct ClockStub;
+
+    impl program_stubs::SyscallStubs for ClockStub {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Clock) = Clock::default() };
+            SUCCESS
+        }
+    }
+
+    pub(crate) fn test_vault(admin: Pubkey) -> SecureVault {
+        program_stubs::set_syscall_stubs(Box::new(ClockStub));
+        SecureVault::new(admin)
+    }
+
+    fn weighted_operation(signers: &[Pubkey], required_weight: u16) -> PendingOperation {
+        program_stubs::set_syscall_stubs(Box::new(ClockStub));
+        let (performer, target) = (Pubkey::new_unique(), Pubkey::new_unique());
+        PendingOperation::new(1, OperationType::Withdraw, performer, 1, target, 0, signers.to_vec())
+            .with_required_weight(required_weight)
+    }
+
+    #[test]
+    fn operation_types_map_to_distinct_permission_bits() {
+        let operations: Vec<OperationType> = (0..OperationType::COUNT)
+            .map(|index| OperationType::try_from_slice(&[index as u8]).unwrap())
+            .collect();
+        // `COUNT` cubre todas las variantes
+        assert!(OperationType::try_from_slice(&[OperationType::COUNT as u8]).is_err());
+
+        let mut seen: Permissions = 0;
+        for operation in &operations {
+            let bit = operation.permission_bit();
+            assert_eq!(bit.count_ones(), 1, "{:?}", operation);
+            assert_eq!(seen & bit, 0, "{:?} shares a permission bit", operation);
+            seen |= bit;
+        }
+        assert_eq!(OperationType::permissions_of(&operations), seen);
+    }
+
+    fn vesting_schedule(total_amount: u64) -> VestingSchedule {
+        let (vault, beneficiary) = (Pubkey::new_unique(), Pubkey::new_unique());
+        VestingSchedule::new(vault, 1, beneficiary, total_amount, 1_000, 1_250, 2_000).unwrap()
+    }
+
+    #[test]
+    fn vesting_rejects_inconsistent_timestamps() {
+        let vault = Pubkey::new_unique();
+        let beneficiary = Pubkey::new_unique();
+        assert!(VestingSchedule::new(vault, 1, beneficiary, 100, 2_000, 2_000, 2_000).is_err());
+        assert!(VestingSchedule::new(vault, 1, beneficiary, 100, 1_000, 999, 2_000).is_err());
+        assert!(VestingSchedule::new(vault, 1, beneficiary, 100, 1_000, 2_001, 2_000).is_err());
+        assert!(VestingSchedule::new(vault, 1, beneficiary, 0, 1_000, 1_000, 2_000).is_err());
+        assert!(VestingSchedule::new(vault, 1, beneficiary, 100, 1_000, 2_000, 2_000).is_ok());
+    }
+
+    #[test]
+    fn vested_amount_is_zero_before_cliff() {
+        let schedule = vesting_schedule(1_000);
+        assert_eq!(schedule.vested_amount(0), 0);
+        assert_eq!(schedule.vested_amount(1_000), 0);
+        assert_eq!(schedule.vested_amount(1_249), 0);
+    }
+
+    #[test]
+    fn vested_amount_is_linear_from_start_after_cliff() {
+        let schedule = vesting_schedule(1_000);
+        // En el cliff se libera de golpe lo acumulado desde `start`
+        assert_eq!(schedule.vested_amount(1_250), 250);
+        assert_eq!(schedule.vested_amount(1_500), 500);
+        assert_eq!(schedule.vested_amount(1_999), 999);
+        assert_eq!(schedule.vested_amount(2_000), 1_000);
+        assert_eq!(schedule.vested_amount(i64::MAX), 1_000);
+    }
+
+    #[test]
+    fn vested_amount_rounds_down_without_overflow() {
+        let schedule = vesting_schedule(u64::MAX);
+        assert_eq!(schedule.vested_amount(1_500), u64::MAX / 2);
+        assert_eq!(schedule.vested_amount(2_000), u64::MAX);
+
+        let schedule = vesting_schedule(3);
+        assert_eq!(schedule.vested_amount(1_500), 1);
+    }
+
+    #[test]
+    fn claims_are_bounded_by_vested_and_total_amount() {
+        let mut schedule = vesting_schedule(1_000);
+        assert_eq!(schedule.claimable_amount(1_500), 500);
+        schedule.record_claim(500).unwrap();
+        assert_eq!(schedule.claimable_amount(1_500), 0);
+        assert_eq!(schedule.claimable_amount(2_000), 500);
+        assert!(schedule.record_claim(501).is_err());
+        schedule.record_claim(500).unwrap();
+        assert_eq!(schedule.claimable_amount(i64::MAX), 0);
+    }
+
+    #[test]
+    fn signatures_accumulate_weight_until_threshold() {
+        let [admin, manager, other] = [(); 3].map(|_| Pubkey::new_unique());
+        let mut operation = weighted_operation(&[admin, manager, other], 3);
+
+        assert!(operation.add_signature(admin, 2));
+        assert_eq!(operation.status, OperationStatus::Pending);
+        assert!(!operation.is_ready_to_execute(0));
+        assert!(operation.add_signature(manager, 1));
+        assert_eq!(operation.received_weight, 3);
+        assert_eq!(operation.status, OperationStatus::Approved);
+        assert!(operation.is_ready_to_execute(0));
+    }
+
+    #[test]
+    fn duplicate_outsider_and_zero_weight_votes_are_ignored() {
+        let [admin, manager] = [(); 2].map(|_| Pubkey::new_unique());
+        let mut operation = weighted_operation(&[admin, manager], 3);
+
+        assert!(operation.add_signature(admin, 2));
+        assert!(!operation.add_signature(admin, 2));
+        assert!(!operation.add_rejection(admin, 2, 3));
+        assert!(!operation.add_signature(Pubkey::new_unique(), 5));
+        assert!(!operation.add_signature(manager, 0));
+        assert_eq!(operation.received_signatures, vec![admin]);
+        assert_eq!(operation.received_weight, 2);
+        assert_eq!(operation.status, OperationStatus::Pending);
+    }
+
+    #[test]
+    fn legacy_signatures_approve_once_every_required_signer_signed() {
+        let [admin, manager] = [(); 2].map(|_| Pubkey::new_unique());
+        let mut operation = weighted_operation(&[admin, manager], 3);
+
+        assert!(operation.add_signature(admin, 2));
+        assert!(!operation.add_legacy_signature(admin));
+        assert!(!operation.add_legacy_signature(Pubkey::new_unique()));
+        assert_eq!(operation.status, OperationStatus::Pending);
+        assert!(operation.add_legacy_signature(manager));
+        assert_eq!(operation.received_signatures, vec![admin, manager]);
+        assert_eq!(operation.status, OperationStatus::Approved);
+        assert!(operation.is_ready_to_execute(0));
+    }
+
+    #[test]
+    fn rejection_blocks_once_threshold_is_unreachable() {
+        let [admin, first, second] = [(); 3].map(|_| Pubkey::new_unique());
+        let mut operation = weighted_operation(&[admin, first, second], 3);
+        let total_weight = 4;
+
+        // Peso alcanzable 3: todavía puede aprobarse
+        assert!(operation.add_rejection(first, 1, total_weight));
+        assert_eq!(operation.status, OperationStatus::Pending);
+        // Peso alcanzable 2 < 3
+        assert!(operation.add_rejection(second, 1, total_weight));
+        assert_eq!(operation.status, OperationStatus::Rejected);
+        assert!(!operation.add_signature(admin, 2));
+        assert!(!operation.is_ready_to_execute(i64::MAX));
+    }
+
+    #[test]
+    fn withdrawal_window_rolls_hourly_buckets() {
+        let mut window = WithdrawalWindow::default();
+        let hour = 1_000;
+        window.advance(hour * SECONDS_PER_HOUR);
+        window.record(10).unwrap();
+        window.advance((hour + 1) * SECONDS_PER_HOUR + 59);
+        window.record(20).unwrap();
+        window.record(5).unwrap();
+        assert_eq!(window.current_bucket(), 25);
+        assert_eq!(window.total(), 35);
+
+        // Volver atrás no modifica la ventana
+        window.advance(hour * SECONDS_PER_HOUR);
+        assert_eq!(window.current_hour, hour + 1);
+
+        // La hora inicial sale de la ventana tras 24 horas
+        window.advance((hour + 24) * SECONDS_PER_HOUR);
+        assert_eq!(window.total(), 25);
+        assert_eq!(window.current_bucket(), 0);
+        window.advance((hour + 25) * SECONDS_PER_HOUR);
+        assert_eq!(window.total(), 0);
+
+        window.record(7).unwrap();
+        window.advance((hour + 100) * SECONDS_PER_HOUR);
+        assert_eq!(window.total(), 0);
+    }
+
+    #[test]
+    fn withdrawal_window_totals_since_a_point() {
+        let mut window = WithdrawalWindow::default();
+        let hour = 500;
+        for (offset, amount) in [10, 20, 30].into_iter().enumerate() {
+            window.advance((hour + offset as i64) * SECONDS_PER_HOUR);
+            window.record(amount).unwrap();
+        }
+
+        assert_eq!(window.total_since(1, 0, 0), 30);
+        assert_eq!(window.total_since(2, 0, 0), 50);
+        assert_eq!(window.total_since(24, 0, 0), 60);
+        assert_eq!(window.total_since(24, hour + 1, 0), 50);
+        assert_eq!(window.total_since(24, hour + 1, 5), 45);
+        assert_eq!(window.total_since(24, hour + 1, 25), 30);
+    }
+
+    /// Vault con un retiro nativo de `withdrawn` ya registrado en `timestamp`
+    fn vault_with_outflow(withdrawn: u64, timestamp: i64) -> SecureVault {
+        let mut vault = test_vault(Pubkey::new_unique());
+        vault.total_balance = 1_000_000;
+        vault.config.hourly_withdrawal_limit = u64::MAX;
+        vault.config.weekly_withdrawal_limit = u64::MAX;
+        vault.config.monthly_withdrawal_limit = u64::MAX;
+        vault.reset_daily_limits_if_needed(timestamp);
+        vault.record_withdrawal(&Asset::Native, withdrawn).unwrap();
+        vault
+    }
+
+    #[test]
+    fn rolling_window_counter_is_clamped_to_a_lowered_limit() {
+        let start = 10 * SECONDS_PER_DAY;
+        let mut vault = test_vault(Pubkey::new_unique());
+        vault.config.limit_window = LimitWindow::Rolling24h;
+        vault.total_balance = 1_000_000;
+        vault.daily_withdrawal_limit = 100;
+        vault.reset_daily_limits_if_needed(start);
+        vault.record_withdrawal(&Asset::Native, 80).unwrap();
+
+        vault.set_asset_limits(&Asset::Native, 50, 1_000).unwrap();
+        vault.reset_daily_limits_if_needed(start + SECONDS_PER_HOUR);
+        assert_eq!(vault.daily_withdrawn_amount, 50);
+        assert!(vault.is_valid_state());
+        let tier = vault.exceeded_limit_tier(&Asset::Native, 1, start + SECONDS_PER_HOUR);
+        assert_eq!(tier, Some(LimitTier::Daily));
+
+        // La salida abandona la ventana al cumplir 24 horas
+        vault.reset_daily_limits_if_needed(start + SECONDS_PER_DAY - 1);
+        assert_eq!(vault.daily_withdrawn_amount, 50);
+        vault.reset_daily_limits_if_needed(start + SECONDS_PER_DAY + SECONDS_PER_HOUR);
+        assert_eq!(vault.daily_withdrawn_amount, 0);
+        assert!(vault.is_valid_state());
+    }
+
+    #[test]
+    fn hourly_limit_tier_resets_next_hour() {
+        let start = 10 * SECONDS_PER_DAY;
+        let mut vault = vault_with_outflow(60, start);
+        vault.config.hourly_withdrawal_limit = 100;
+
+        assert_eq!(vault.exceeded_limit_tier(&Asset::Native, 40, start), None);
+        let tier = vault.exceeded_limit_tier(&Asset::Native, 41, start + SECONDS_PER_HOUR - 1);
+        assert_eq!(tier, Some(LimitTier::Hourly));
+        let tier = vault.exceeded_limit_tier(&Asset::Native, 41, start + SECONDS_PER_HOUR);
+        assert_eq!(tier, None);
+    }
+
+    #[test]
+    fn daily_limit_tier_applies_to_native_and_tokens() {
+        let start = 10 * SECONDS_PER_DAY;
+        let mut vault = vault_with_outflow(60, start);
+        vault.daily_withdrawal_limit = 100;
+        let tier = vault.exceeded_limit_tier(&Asset::Native, 41, start + SECONDS_PER_HOUR);
+        assert_eq!(tier, Some(LimitTier::Daily));
+        let tier = vault.exceeded_limit_tier(&Asset::Native, 41, start + SECONDS_PER_DAY);
+        assert_eq!(tier, None);
+
+        // Los tramos hora/semana/mes no aplican a tokens
+        let mint = Pubkey::new_unique();
+        vault.config.hourly_withdrawal_limit = 1;
+        vault.set_asset_limits(&Asset::Token(mint), 100, 100).unwrap();
+        vault.credit_token(mint, 1_000).unwrap();
+        vault.record_withdrawal(&Asset::Token(mint), 60).unwrap();
+        assert_eq!(vault.exceeded_limit_tier(&Asset::Token(mint), 40, start), None);
+        let tier = vault.exceeded_limit_tier(&Asset::Token(mint), 41, start);
+        assert_eq!(tier, Some(LimitTier::Daily));
+    }
+
+    #[test]
+    fn weekly_and_monthly_limit_tiers_reset_with_their_period() {
+        let week_start = 100 * SECONDS_PER_WEEK;
+        let mut vault = vault_with_outflow(60, week_start);
+        vault.config.weekly_withdrawal_limit = 100;
+        let tier = vault.exceeded_limit_tier(&Asset::Native, 41, week_start + 6 * SECONDS_PER_DAY);
+        assert_eq!(tier, Some(LimitTier::Weekly));
+        let tier = vault.exceeded_limit_tier(&Asset::Native, 41, week_start + SECONDS_PER_WEEK);
+        assert_eq!(tier, None);
+
+        let month_start = 100 * SECONDS_PER_MONTH;
+        let mut vault = vault_with_outflow(60, month_start);
+        vault.config.monthly_withdrawal_limit = 100;
+        let late = month_start + SECONDS_PER_MONTH - 1;
+        assert_eq!(
+            vault.exceeded_limit_tier(&Asset::Native, 41, late),
+            Some(LimitTier::Monthly)
+        );
+        assert_eq!(vault.exceeded_limit_tier(&Asset::Native, 40, late), None);
+        let tier = vault.exceeded_limit_tier(&Asset::Native, 41, month_start + SECONDS_PER_MONTH);
+        assert_eq!(tier, None);
+    }
+}
@@ -6,7 +6,10 @@ use solana_program::{
 
 use crate::{
     error::VaultError,
-    state::{AuditLog, PendingOperation, SecureVault, MAX_SEED_COUNT, MAX_SEED_LENGTH},
+    state::{
+        AuditLog, PendingOperation, SecureVault, MAX_SEED_COUNT, MAX_SEED_LENGTH, PENDING_OP_SEED,
+        VAULT_SEED,
+    },
 };
 
 /// Utilidades generales del sistema
@@ -36,6 +39,54 @@ impl Utils {
         Ok(())
     }
 
+    /// Transferir tokens SPL (transfer_checked), firmando opcionalmente como PDA
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_tokens<'a>(
+        token_program: &AccountInfo<'a>,
+        source: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        amount: u64,
+        decimals: u8,
+        signer_seeds: Option<&[&[u8]]>,
+    ) -> Result<(), VaultError> {
+        let transfer_instruction = spl_token::instruction::transfer_checked(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )
+        .map_err(|_| VaultError::TokenTransferFailed)?;
+
+        let account_infos = [
+            source.clone(),
+            mint.clone(),
+            destination.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ];
+
+        match signer_seeds {
+            Some(seeds) => invoke_signed(&transfer_instruction, &account_infos, &[seeds]),
+            None => invoke(&transfer_instruction, &account_infos),
+        }
+        .map_err(|_| VaultError::TokenTransferFailed)?;
+
+        msg!(
+            "Transfered {} tokens of mint {} from {} to {}",
+            amount,
+            mint.key,
+            source.key,
+            destination.key
+        );
+        Ok(())
+    }
+
     /// Crear cuenta con PDA
     pub fn create_pda_account<'a>(
         payer: &AccountInfo<'a>,
@@ -86,28 +137,39 @@ impl Utils {
         account: &AccountInfo,
         data: &T,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        let mut account_data = account
+            .try_borrow_mut_data()
+            .map_err(|_| VaultError::SerializationError)?;
+
+        data.serialize(&mut &mut account_data[..])
+            .map_err(|_| VaultError::SerializationError)
     }
 
     /// Deserializar datos de cuenta
     pub fn deserialize_from_account<T: BorshDeserialize>(
         account: &AccountInfo,
     ) -> Result<T, VaultError> {
-        // TODO: Implementar
-        todo!()
+        let account_data = account
+            .try_borrow_data()
+            .map_err(|_| VaultError::DeserializationError)?;
+
+        // Las cuentas se reservan con tamaño máximo: ignorar bytes sobrantes
+        T::deserialize(&mut &account_data[..]).map_err(|_| VaultError::DeserializationError)
     }
 
     /// Verificar que la cuenta es rent exempt
     pub fn verify_rent_exempt(account: &AccountInfo, data_len: usize) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if account.lamports() < Self::calculate_rent_exempt_minimum(data_len)? {
+            return Err(VaultError::NotRentExempt);
+        }
+        Ok(())
     }
 
     /// Calcular rent mínimo requerido
     pub fn calculate_rent_exempt_minimum(data_len: usize) -> Result<u64, VaultError> {
-        // TODO: Implementar
-        todo!()
+        Rent::get()
+            .map(|rent| rent.minimum_balance(data_len))
+            .map_err(|_| VaultError::NotRentExempt)
     }
 
     /// Generar PDA con bump seed
@@ -146,8 +208,9 @@ impl Utils {
 
     /// Obtener timestamp actual
     pub fn get_current_timestamp() -> Result<i64, VaultError> {
-        // TODO: Implementar
-        todo!()
+        Clock::get()
+            .map(|clock| clock.unix_timestamp)
+            .map_err(|_| VaultError::ClockUnavailable)
     }
 
     /// Calcular hash de datos
@@ -233,14 +296,16 @@ pub struct VaultUtils;
 impl VaultUtils {
     /// Generar semillas para vault PDA
     pub fn generate_vault_seeds(admin: &Pubkey) -> Vec<Vec<u8>> {
-        // TODO: Implementar
-        todo!()
+        vec![VAULT_SEED.to_vec(), admin.to_bytes().to_vec()]
     }
 
     /// Generar semillas para operación pendiente
     pub fn generate_operation_seeds(vault: &Pubkey, operation_id: u64) -> Vec<Vec<u8>> {
-        // TODO: Implementar
-        todo!()
+        vec![
+            PENDING_OP_SEED.to_vec(),
+            vault.to_bytes().to_vec(),
+            operation_id.to_le_bytes().to_vec(),
+        ]
     }
 
     /// Generar semillas para audit log
@@ -249,6 +314,11 @@ impl VaultUtils {
         todo!()
     }
 
+    /// Obtener la ATA del vault para un mint
+    pub fn get_vault_token_address(vault: &Pubkey, mint: &Pubkey) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address(vault, mint)
+    }
+
     /// Calcular espacio requerido para vault
     pub fn calculate_vault_space(max_managers: u8) -> usize {
         // TODO: Implementar
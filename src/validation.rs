@@ -1,14 +1,24 @@
 use solana_program::{
     account_info::AccountInfo,
     clock::Clock,
+    msg,
     program_error::ProgramError,
+    program_pack::Pack,
     pubkey::Pubkey,
     sysvar::Sysvar,
 };
 
+use spl_token::state::{Account as TokenAccount, Mint};
+
 use crate::{
-    state::{SecureVault, OperationType, SecurityConfig, PendingOperation},
+    state::{
+        AddressBook, Asset, AuditLog, LimitTier, SecureVault, OperationType, SecurityConfig,
+        PendingOperation, PriceFeed, QuoteLimits, RecipientPolicy, MAX_EMERGENCY_CONTACTS,
+        PRICE_FEED_DISCRIMINATOR,
+    },
     error::VaultError,
+    security::SecurityManager,
+    utils::{constants::OPERATION_EXPIRY_HOURS, Utils},
 };
 
 pub struct Validator;
@@ -19,8 +29,10 @@ impl Validator {
         admin: &Pubkey,
         config: &SecurityConfig,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if *admin == Pubkey::default() {
+            return Err(VaultError::InvalidAccount);
+        }
+        Self::validate_security_config(config)
     }
 
     /// Validar autorización para operación
@@ -29,8 +41,10 @@ impl Validator {
         performer: &Pubkey,
         operation: &OperationType,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if !vault.check_authorization(performer, operation) {
+            return Err(VaultError::Unauthorized);
+        }
+        Ok(())
     }
 
     /// Validar solicitud de retiro
@@ -41,18 +55,282 @@ impl Validator {
         recipient: &Pubkey,
         current_timestamp: i64,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if *recipient == Pubkey::default() {
+            return Err(VaultError::InvalidPubkey);
+        }
+        // Los montos con tramo de delay solo salen mediante operación time-locked
+        if SecurityManager::requires_time_lock(vault, amount) {
+            return Err(VaultError::DelayTooShort);
+        }
+        Self::validate_asset_withdrawal(
+            vault,
+            performer,
+            &Asset::Native,
+            amount,
+            current_timestamp,
+        )
+    }
+
+    /// Validar solicitud de retiro de tokens SPL
+    pub fn validate_token_withdrawal_request(
+        vault: &SecureVault,
+        performer: &Pubkey,
+        mint: &Pubkey,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        let token = vault
+            .get_token_balance(mint)
+            .ok_or(VaultError::InvalidMintAccount)?;
+        if token.daily_withdrawal_limit == 0 {
+            msg!("Withdrawal limits not configured for mint {}", mint);
+            return Err(VaultError::DailyLimitExceeded);
+        }
+        Self::validate_asset_withdrawal(
+            vault,
+            performer,
+            &Asset::Token(*mint),
+            amount,
+            current_timestamp,
+        )
+    }
+
+    /// Capas comunes de retiro aplicadas sobre el ledger del activo
+    fn validate_asset_withdrawal(
+        vault: &SecureVault,
+        performer: &Pubkey,
+        asset: &Asset,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        let operation = match asset {
+            Asset::Native => OperationType::Withdraw,
+            Asset::Token(_) => OperationType::WithdrawToken,
+        };
+
+        Self::validate_not_paused(vault, current_timestamp)?;
+        Self::validate_authorization(vault, performer, &operation)?;
+        Self::validate_amount(amount, Some(1), None)?;
+        Self::validate_sufficient_balance(vault, asset, amount)?;
+        Self::validate_window_limits(vault, asset, amount, current_timestamp)?;
+        if *asset == Asset::Native {
+            Self::validate_manager_quota(vault, performer, amount, current_timestamp)?;
+        }
+
+        Self::validate_multisig_threshold(vault, asset, amount)
+    }
+
+    /// Montos desde el umbral de multisig del activo solo salen mediante operación
+    /// time-locked con firmas
+    fn validate_multisig_threshold(
+        vault: &SecureVault,
+        asset: &Asset,
+        amount: u64,
+    ) -> Result<(), VaultError> {
+        let ledger = vault
+            .get_asset_ledger(asset)
+            .ok_or(VaultError::InvalidMintAccount)?;
+        if amount >= ledger.multisig_threshold {
+            return Err(VaultError::InsufficientSignatures);
+        }
+        Ok(())
+    }
+
+    /// Validar retiro desde un compartimento: permiso `CompartmentWithdraw` en la tabla de
+    /// permisos, restringido además a los managers del compartimento o a quien tenga
+    /// `ManageCompartments`
+    pub fn validate_compartment_withdrawal(
+        vault: &SecureVault,
+        performer: &Pubkey,
+        compartment: &str,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        Self::validate_not_paused(vault, current_timestamp)?;
+        Self::validate_authorization(vault, performer, &OperationType::CompartmentWithdraw)?;
+        let entry = vault
+            .get_compartment(compartment)
+            .ok_or(VaultError::InvalidAccount)?;
+        if !entry.managers.contains(performer)
+            && !vault.check_authorization(performer, &OperationType::ManageCompartments)
+        {
+            return Err(VaultError::Unauthorized);
+        }
+        Self::validate_amount(amount, Some(1), None)?;
+        if amount > entry.balance {
+            return Err(VaultError::InsufficientBalance);
+        }
+        // Los tramos de delay también aplican: esos montos no salen de un compartimento
+        if SecurityManager::requires_time_lock(vault, amount) {
+            return Err(VaultError::DelayTooShort);
+        }
+
+        vault
+            .can_withdraw_from_compartment(compartment, amount, current_timestamp)
+            .map_err(|_| VaultError::DailyLimitExceeded)?;
+        Self::validate_window_limits(vault, &Asset::Native, amount, current_timestamp)?;
+        Self::validate_manager_quota(vault, performer, amount, current_timestamp)?;
+        // Montos grandes: mover al fondo general y usar la operación time-locked
+        Self::validate_multisig_threshold(vault, &Asset::Native, amount)
+    }
+
+    /// Validar destinatario contra el address book según la política del vault
+    pub fn validate_allowlisted_recipient(
+        vault: &SecureVault,
+        address_book: Option<&AddressBook>,
+        recipient: &Pubkey,
+    ) -> Result<(), VaultError> {
+        if vault.recipient_policy == RecipientPolicy::Disabled {
+            return Ok(());
+        }
+
+        match address_book {
+            Some(book) if book.contains(recipient) => Ok(()),
+            _ => Err(VaultError::RecipientNotAllowlisted),
+        }
     }
 
-    /// Validar límites diarios
-    pub fn validate_daily_limits(
+    /// Validar cuota de gasto del manager; si se excede, el retiro debe ir por multisig
+    pub fn validate_manager_quota(
         vault: &SecureVault,
+        performer: &Pubkey,
         amount: u64,
         current_timestamp: i64,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        vault
+            .check_manager_quota(performer, amount, current_timestamp)
+            .map_err(|_| VaultError::ManagerQuotaExceeded)
+    }
+
+    /// Validar alta/baja de contacto de emergencia antes de programarla
+    pub fn validate_emergency_contact_change(
+        vault: &SecureVault,
+        operation_type: &OperationType,
+        contact: &Pubkey,
+    ) -> Result<(), VaultError> {
+        let exists = vault.emergency_contacts.contains(contact);
+        match operation_type {
+            OperationType::AddEmergencyContact => {
+                if *contact == Pubkey::default() || *contact == vault.admin {
+                    return Err(VaultError::InvalidPubkey);
+                }
+                if exists {
+                    return Err(VaultError::InvalidAccount);
+                }
+                if vault.emergency_contacts.len() >= MAX_EMERGENCY_CONTACTS {
+                    return Err(VaultError::InvalidEmergencyOperation);
+                }
+                Ok(())
+            }
+            OperationType::RemoveEmergencyContact if exists => Ok(()),
+            OperationType::RemoveEmergencyContact => Err(VaultError::InvalidEmergencyOperation),
+            _ => Err(VaultError::InvalidOperationType),
+        }
+    }
+
+    /// Validar la cuenta del oráculo configurado: identidad, frescura y confianza del precio
+    pub fn validate_price_feed(
+        price_feed_account: &AccountInfo,
+        limits: &QuoteLimits,
+        current_timestamp: i64,
+    ) -> Result<PriceFeed, VaultError> {
+        if *price_feed_account.key != limits.price_feed {
+            return Err(VaultError::InvalidOracle);
+        }
+        let feed: PriceFeed = Utils::deserialize_from_account(price_feed_account)
+            .map_err(|_| VaultError::InvalidOracle)?;
+        if feed.discriminator != PRICE_FEED_DISCRIMINATOR
+            || feed.price <= 0
+            || !(-18..=0).contains(&feed.exponent)
+        {
+            return Err(VaultError::InvalidOracle);
+        }
+
+        let age = current_timestamp.saturating_sub(feed.publish_time);
+        if !(0..=limits.max_staleness_seconds).contains(&age) {
+            return Err(VaultError::StalePriceData);
+        }
+        // Un intervalo de confianza amplio indica un precio poco fiable o manipulado
+        let confidence_bps = feed.confidence_bps().ok_or(VaultError::PriceManipulation)?;
+        if confidence_bps > limits.max_confidence_bps as u64 {
+            return Err(VaultError::PriceManipulation);
+        }
+        Ok(feed)
+    }
+
+    /// Validar límites en moneda de cotización de un retiro nativo directo
+    pub fn validate_quote_limits(
+        vault: &SecureVault,
+        price: Option<&PriceFeed>,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        Self::validate_quote_daily_limit(vault, price, amount, current_timestamp)?;
+        if SecurityManager::exceeds_quote_threshold(vault, price, amount)? {
+            return Err(VaultError::InsufficientSignatures);
+        }
+        Ok(())
+    }
+
+    /// Validar el acumulado diario del activo nativo valuado al precio actual
+    pub fn validate_quote_daily_limit(
+        vault: &SecureVault,
+        price: Option<&PriceFeed>,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        let limits = match &vault.config.quote_limits {
+            Some(limits) => limits,
+            None => return Ok(()),
+        };
+        let price = price.ok_or(VaultError::InvalidOracle)?;
+
+        let mut temp_vault = vault.clone();
+        temp_vault.reset_daily_limits_if_needed(current_timestamp);
+        let withdrawn = Self::validate_no_overflow(temp_vault.daily_withdrawn_amount, amount)?;
+        let value = price
+            .quote_value(withdrawn)
+            .ok_or(VaultError::ArithmeticOverflow)?;
+        if value > limits.daily_withdrawal_limit {
+            return Err(VaultError::DailyLimitExceeded);
+        }
+        Ok(())
+    }
+
+    /// Validar los tramos de límite de retiro (hora, día, semana, mes) del activo
+    pub fn validate_window_limits(
+        vault: &SecureVault,
+        asset: &Asset,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        vault
+            .get_asset_ledger(asset)
+            .ok_or(VaultError::InvalidMintAccount)?;
+
+        let tier = match vault.exceeded_limit_tier(asset, amount, current_timestamp) {
+            Some(tier) => tier,
+            None => return Ok(()),
+        };
+        msg!("Withdrawal blocked by {:?} limit", tier);
+        Err(match tier {
+            LimitTier::Hourly => VaultError::HourlyLimitExceeded,
+            LimitTier::Daily => VaultError::DailyLimitExceeded,
+            LimitTier::Weekly => VaultError::WeeklyLimitExceeded,
+            LimitTier::Monthly => VaultError::MonthlyLimitExceeded,
+        })
+    }
+
+    /// Validar que el vault no está pausado, salvo para levantar el disyuntor por quórum
+    pub fn validate_not_paused_for(
+        vault: &SecureVault,
+        operation_type: &OperationType,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        if *operation_type == OperationType::Resume && vault.circuit_breaker.trip.is_some() {
+            return Ok(());
+        }
+        Self::validate_not_paused(vault, current_timestamp)
     }
 
     /// Validar rate limiting
@@ -65,24 +343,35 @@ impl Validator {
     }
 
     /// Validar que el vault no está pausado
-    pub fn validate_not_paused(vault: &SecureVault) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+    pub fn validate_not_paused(
+        vault: &SecureVault,
+        current_timestamp: i64,
+    ) -> Result<(), VaultError> {
+        if vault.is_paused_at(current_timestamp) {
+            return Err(VaultError::VaultPaused);
+        }
+        Ok(())
     }
 
     /// Validar balance suficiente
     pub fn validate_sufficient_balance(
         vault: &SecureVault,
+        asset: &Asset,
         amount: u64,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        let balance = vault
+            .get_asset_ledger(asset)
+            .map(|ledger| ledger.balance)
+            .unwrap_or(0);
+        if amount > balance {
+            return Err(VaultError::InsufficientBalance);
+        }
+        Ok(())
     }
 
     /// Validar configuración de seguridad
     pub fn validate_security_config(config: &SecurityConfig) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        SecurityManager::validate_security_config(config)
     }
 
     /// Validar operación pendiente
@@ -90,8 +379,13 @@ impl Validator {
         operation: &PendingOperation,
         current_timestamp: i64,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if !operation.is_active() {
+            return Err(VaultError::OperationAlreadyExecuted);
+        }
+        if operation.is_expired(current_timestamp, OPERATION_EXPIRY_HOURS) {
+            return Err(VaultError::OperationExpired);
+        }
+        Ok(())
     }
 
     /// Validar que la operación está lista para ejecutar
@@ -99,8 +393,13 @@ impl Validator {
         operation: &PendingOperation,
         current_timestamp: i64,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if current_timestamp < operation.scheduled_execution {
+            return Err(VaultError::TimeLockNotElapsed);
+        }
+        if !operation.has_sufficient_signatures() {
+            return Err(VaultError::InsufficientSignatures);
+        }
+        Ok(())
     }
 
     /// Validar firmas requeridas
@@ -121,10 +420,51 @@ impl Validator {
         todo!()
     }
 
+    /// Validar cuenta de token SPL (mint y owner esperados)
+    pub fn validate_token_account(
+        token_account: &AccountInfo,
+        expected_mint: &Pubkey,
+        expected_owner: &Pubkey,
+    ) -> Result<TokenAccount, VaultError> {
+        let state = Self::unpack_token_account(token_account)?;
+        if state.mint != *expected_mint {
+            return Err(VaultError::InvalidMintAccount);
+        }
+        if state.owner != *expected_owner {
+            return Err(VaultError::InvalidTokenAccount);
+        }
+        Ok(state)
+    }
+
+    /// Leer una token account SPL validando que pertenece al token program
+    pub fn unpack_token_account(token_account: &AccountInfo) -> Result<TokenAccount, VaultError> {
+        Self::validate_account_owner(token_account, &spl_token::id())
+            .map_err(|_| VaultError::InvalidTokenAccount)?;
+
+        let data = token_account
+            .try_borrow_data()
+            .map_err(|_| VaultError::InvalidTokenAccount)?;
+        TokenAccount::unpack(&data).map_err(|_| VaultError::InvalidTokenAccount)
+    }
+
+    /// Validar cuenta mint SPL y obtener sus decimales
+    pub fn validate_mint_account(mint: &AccountInfo) -> Result<u8, VaultError> {
+        Self::validate_account_owner(mint, &spl_token::id())
+            .map_err(|_| VaultError::InvalidMintAccount)?;
+
+        let data = mint
+            .try_borrow_data()
+            .map_err(|_| VaultError::InvalidMintAccount)?;
+        let state = Mint::unpack(&data).map_err(|_| VaultError::InvalidMintAccount)?;
+        Ok(state.decimals)
+    }
+
     /// Validar que la cuenta es signer
     pub fn validate_signer(account: &AccountInfo) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if !account.is_signer {
+            return Err(VaultError::Unauthorized);
+        }
+        Ok(())
     }
 
     /// Validar ownership de cuenta
@@ -132,8 +472,29 @@ impl Validator {
         account: &AccountInfo,
         expected_owner: &Pubkey,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if account.owner != expected_owner {
+            return Err(VaultError::InvalidAccountOwner);
+        }
+        Ok(())
+    }
+
+    /// Validar la cuenta de auditoría de instrucciones sin permisos: del programa, con el
+    /// tamaño de un `AuditLog` y distinta de las demás cuentas (que sobrescribiría)
+    pub fn validate_audit_account(
+        audit_account: &AccountInfo,
+        program_id: &Pubkey,
+        other_accounts: &[&AccountInfo],
+    ) -> Result<(), VaultError> {
+        Self::validate_account_owner(audit_account, program_id)?;
+        if audit_account.data_len() != AuditLog::MAX_SIZE
+            || other_accounts
+                .iter()
+                .any(|account| account.key == audit_account.key)
+        {
+            msg!("Invalid audit account {}", audit_account.key);
+            return Err(VaultError::InvalidAccount);
+        }
+        Ok(())
     }
 
     /// Validar tamaño de cuenta
@@ -153,8 +514,11 @@ impl Validator {
 
     /// Validar que la cuenta NO está inicializada
     pub fn validate_account_not_initialized(account: &AccountInfo) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        // Una cuenta nueva no tiene datos ni lamports: cualquier otra cosa ya fue creada
+        if !account.data_is_empty() || account.lamports() != 0 {
+            return Err(VaultError::AccountAlreadyInitialized);
+        }
+        Ok(())
     }
 
     /// Validar PDA (Program Derived Address)
@@ -173,8 +537,17 @@ impl Validator {
         min_amount: Option<u64>,
         max_amount: Option<u64>,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if let Some(min) = min_amount {
+            if amount < min {
+                return Err(VaultError::AmountTooSmall);
+            }
+        }
+        if let Some(max) = max_amount {
+            if amount > max {
+                return Err(VaultError::AmountTooLarge);
+            }
+        }
+        Ok(())
     }
 
     /// Validar timestamp
@@ -185,14 +558,12 @@ impl Validator {
 
     /// Validar que no hay overflow aritmético
     pub fn validate_no_overflow(a: u64, b: u64) -> Result<u64, VaultError> {
-        // TODO: Implementar
-        todo!()
+        a.checked_add(b).ok_or(VaultError::ArithmeticOverflow)
     }
 
     /// Validar que no hay underflow aritmético
     pub fn validate_no_underflow(a: u64, b: u64) -> Result<u64, VaultError> {
-        // TODO: Implementar
-        todo!()
+        a.checked_sub(b).ok_or(VaultError::ArithmeticUnderflow)
     }
 
     /// Validar lista de managers
@@ -209,8 +580,10 @@ impl Validator {
         vault: &SecureVault,
         manager: &Pubkey,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if !vault.managers.contains(manager) {
+            return Err(VaultError::ManagerNotFound);
+        }
+        Ok(())
     }
 
     /// Validar que el manager no existe (para agregar)
@@ -218,8 +591,10 @@ impl Validator {
         vault: &SecureVault,
         manager: &Pubkey,
     ) -> Result<(), VaultError> {
-        // TODO: Implementar
-        todo!()
+        if vault.managers.contains(manager) {
+            return Err(VaultError::InvalidAccount);
+        }
+        Ok(())
     }
 
     /// Validar operación de emergencia
@@ -301,4 +676,137 @@ impl ValidationUtils {
         // TODO: Implementar
         todo!()
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::system_program;
+
+    fn account_info<'a>(
+        key: &'a Pubkey,
+        owner: &'a Pubkey,
+        lamports: &'a mut u64,
+        data: &'a mut [u8],
+    ) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, owner, false, 0)
+    }
+
+    #[test]
+    fn account_not_initialized_accepts_fresh_account() {
+        let key = Pubkey::new_unique();
+        let owner = system_program::id();
+        let mut lamports = 0;
+        let mut data = [];
+        let account = account_info(&key, &owner, &mut lamports, &mut data);
+
+        assert!(Validator::validate_account_not_initialized(&account).is_ok());
+    }
+
+    #[test]
+    fn account_not_initialized_rejects_existing_account() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let mut lamports = 1_000;
+        let mut data = [];
+        let funded = account_info(&key, &owner, &mut lamports, &mut data);
+        assert!(matches!(
+            Validator::validate_account_not_initialized(&funded),
+            Err(VaultError::AccountAlreadyInitialized)
+        ));
+
+        let mut lamports = 0;
+        let mut data = [0u8; 8];
+        let with_data = account_info(&key, &owner, &mut lamports, &mut data);
+        assert!(matches!(
+            Validator::validate_account_not_initialized(&with_data),
+            Err(VaultError::AccountAlreadyInitialized)
+        ));
+    }
+
+    const PUBLISH_TIME: i64 = 1_000;
+
+    fn quote_limits(price_feed: Pubkey) -> QuoteLimits {
+        QuoteLimits {
+            price_feed,
+            daily_withdrawal_limit: u64::MAX,
+            multisig_threshold: u64::MAX,
+            max_staleness_seconds: 60,
+            max_confidence_bps: 100,
+        }
+    }
+
+    /// Validar datos de oráculo simulados guardados en la cuenta `key`
+    fn validate_feed(
+        limits: &QuoteLimits,
+        key: &Pubkey,
+        mut data: Vec<u8>,
+        current_timestamp: i64,
+    ) -> Result<PriceFeed, VaultError> {
+        let owner = Pubkey::new_unique();
+        let mut lamports = 1_000;
+        let account = account_info(key, &owner, &mut lamports, &mut data);
+        Validator::validate_price_feed(&account, limits, current_timestamp)
+    }
+
+    #[test]
+    fn price_feed_accepts_fresh_confident_price() {
+        let key = Pubkey::new_unique();
+        let limits = quote_limits(key);
+        let data = PriceFeed::mock_account_data(150, 1, 0, PUBLISH_TIME);
+
+        let feed = validate_feed(&limits, &key, data, PUBLISH_TIME + 60).unwrap();
+        assert_eq!(feed.price, 150);
+    }
+
+    #[test]
+    fn price_feed_rejects_unconfigured_account() {
+        let limits = quote_limits(Pubkey::new_unique());
+        let data = PriceFeed::mock_account_data(150, 1, 0, PUBLISH_TIME);
+
+        let result = validate_feed(&limits, &Pubkey::new_unique(), data, PUBLISH_TIME);
+        assert!(matches!(result, Err(VaultError::InvalidOracle)));
+    }
+
+    #[test]
+    fn price_feed_rejects_stale_or_future_price() {
+        let key = Pubkey::new_unique();
+        let limits = quote_limits(key);
+        let data = PriceFeed::mock_account_data(150, 1, 0, PUBLISH_TIME);
+
+        let result = validate_feed(&limits, &key, data.clone(), PUBLISH_TIME + 61);
+        assert!(matches!(result, Err(VaultError::StalePriceData)));
+        let result = validate_feed(&limits, &key, data, PUBLISH_TIME - 1);
+        assert!(matches!(result, Err(VaultError::StalePriceData)));
+    }
+
+    #[test]
+    fn price_feed_rejects_low_confidence() {
+        let key = Pubkey::new_unique();
+        let limits = quote_limits(key);
+
+        // 1.5 sobre 150 es exactamente el máximo de 100 bps
+        let data = PriceFeed::mock_account_data(15_000, 150, -2, PUBLISH_TIME);
+        assert!(validate_feed(&limits, &key, data, PUBLISH_TIME).is_ok());
+        let data = PriceFeed::mock_account_data(15_000, 165, -2, PUBLISH_TIME);
+        let result = validate_feed(&limits, &key, data, PUBLISH_TIME);
+        assert!(matches!(result, Err(VaultError::PriceManipulation)));
+    }
+
+    #[test]
+    fn price_feed_rejects_malformed_data() {
+        let key = Pubkey::new_unique();
+        let limits = quote_limits(key);
+
+        for data in [
+            PriceFeed::mock_account_data(0, 0, 0, PUBLISH_TIME),
+            PriceFeed::mock_account_data(-150, 1, 0, PUBLISH_TIME),
+            PriceFeed::mock_account_data(150, 1, 1, PUBLISH_TIME),
+            vec![0u8; PriceFeed::LEN],
+            vec![0u8; 4],
+        ] {
+            let result = validate_feed(&limits, &key, data, PUBLISH_TIME);
+            assert!(matches!(result, Err(VaultError::InvalidOracle)));
+        }
+    }
+}
@@ -0,0 +1,244 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{
+        OperationStatus, OperationType, DEFAULT_RECOVERY_DELAY_SECONDS,
+        EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS, RECOVERY_PROPOSAL_EXPIRY_SECONDS,
+    },
+};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+/// Vault con tres emergency contacts y el quorum por defecto (2)
+async fn setup_guardians() -> (VaultTest, [Keypair; 3]) {
+    let mut test = setup(test_config()).await;
+    let guardians = [Keypair::new(), Keypair::new(), Keypair::new()];
+    for guardian in &guardians {
+        test.add_emergency_contact(&guardian.pubkey()).await;
+    }
+    (test, guardians)
+}
+
+fn propose(test: &VaultTest, guardian: &Keypair, new_admin: &Pubkey) -> [Instruction; 1] {
+    [builders::propose_admin_recovery(
+        &test.program_id,
+        &guardian.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        new_admin,
+    )]
+}
+
+fn approve(test: &VaultTest, guardian: &Keypair, new_admin: &Pubkey) -> [Instruction; 1] {
+    [builders::approve_admin_recovery(
+        &test.program_id,
+        &guardian.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        new_admin,
+    )]
+}
+
+fn execute(test: &VaultTest, new_admin: &Keypair) -> [Instruction; 1] {
+    [builders::execute_admin_recovery(
+        &test.program_id,
+        &new_admin.pubkey(),
+        &test.vault,
+        &test.audit_log,
+    )]
+}
+
+#[tokio::test]
+async fn recovery_rotates_admin_after_quorum_and_time_lock() {
+    let (mut test, guardians) = setup_guardians().await;
+    let old_admin = test.admin.pubkey();
+    let new_admin = Keypair::new();
+
+    let outsider = Keypair::new();
+    let instruction = propose(&test, &outsider, &new_admin.pubkey());
+    let result = test.process(&instruction, &[&outsider]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    let instruction = propose(&test, &guardians[0], &new_admin.pubkey());
+    test.process(&instruction, &[&guardians[0]]).await.unwrap();
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::AdminRecovery);
+    assert_eq!(audit.operation_status, Some(OperationStatus::Pending));
+
+    // Sin quorum no hay time-lock en marcha
+    let result = test
+        .process(&execute(&test, &new_admin), &[&new_admin])
+        .await;
+    assert_vault_error(result, VaultError::InsufficientSignatures);
+
+    let instruction = approve(&test, &guardians[1], &new_admin.pubkey());
+    test.process(&instruction, &[&guardians[1]]).await.unwrap();
+    assert_eq!(
+        test.audit_entry().await.operation_status,
+        Some(OperationStatus::Approved)
+    );
+    let result = test
+        .process(&execute(&test, &new_admin), &[&new_admin])
+        .await;
+    assert_vault_error(result, VaultError::TimeLockNotElapsed);
+
+    test.advance_clock(DEFAULT_RECOVERY_DELAY_SECONDS).await;
+    let impostor = Keypair::new();
+    let result = test.process(&execute(&test, &impostor), &[&impostor]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    test.process(&execute(&test, &new_admin), &[&new_admin])
+        .await
+        .unwrap();
+    let vault = test.vault_state().await;
+    assert_eq!(vault.admin, new_admin.pubkey());
+    assert!(vault.admin_recovery.is_none());
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.performer, new_admin.pubkey());
+    assert_eq!(audit.operation_status, Some(OperationStatus::Executed));
+
+    // El admin anterior pierde sus permisos
+    let instruction =
+        builders::emergency_pause(&test.program_id, &old_admin, &test.vault, &test.audit_log);
+    assert_vault_error(
+        test.process(&[instruction], &[]).await,
+        VaultError::Unauthorized,
+    );
+}
+
+#[tokio::test]
+async fn admin_cancels_recovery_in_progress() {
+    let (mut test, guardians) = setup_guardians().await;
+    let new_admin = Pubkey::new_unique();
+    let instruction = propose(&test, &guardians[0], &new_admin);
+    test.process(&instruction, &[&guardians[0]]).await.unwrap();
+
+    let (program_id, vault, audit_log) = (test.program_id, test.vault, test.audit_log);
+    let cancel = |signer: &Pubkey| {
+        [builders::cancel_admin_recovery(
+            &program_id,
+            signer,
+            &vault,
+            &audit_log,
+        )]
+    };
+    let instruction = cancel(&guardians[1].pubkey());
+    let result = test.process(&instruction, &[&guardians[1]]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    let instruction = cancel(&test.admin.pubkey());
+    test.process(&instruction, &[]).await.unwrap();
+    assert!(test.vault_state().await.admin_recovery.is_none());
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.target_account, Some(new_admin));
+    assert_eq!(audit.operation_status, Some(OperationStatus::Rejected));
+
+    let instruction = propose(&test, &guardians[1], &Pubkey::new_unique());
+    test.process(&instruction, &[&guardians[1]]).await.unwrap();
+}
+
+#[tokio::test]
+async fn stale_recovery_proposal_can_be_replaced() {
+    let (mut test, guardians) = setup_guardians().await;
+    let rogue_admin = Pubkey::new_unique();
+    let new_admin = Pubkey::new_unique();
+
+    let instruction = propose(&test, &guardians[0], &rogue_admin);
+    test.process(&instruction, &[&guardians[0]]).await.unwrap();
+    let instruction = propose(&test, &guardians[1], &new_admin);
+    let result = test.process(&instruction, &[&guardians[1]]).await;
+    assert_vault_error(result, VaultError::RecoveryInProgress);
+
+    // Sin quorum dentro del plazo, la propuesta deja de admitir aprobaciones
+    test.advance_clock(RECOVERY_PROPOSAL_EXPIRY_SECONDS).await;
+    let instruction = approve(&test, &guardians[2], &rogue_admin);
+    let result = test.process(&instruction, &[&guardians[2]]).await;
+    assert_vault_error(result, VaultError::OperationExpired);
+
+    test.process(&propose(&test, &guardians[1], &new_admin), &[&guardians[1]])
+        .await
+        .unwrap();
+    let recovery = test.vault_state().await.admin_recovery.unwrap();
+    assert_eq!(recovery.proposed_admin, new_admin);
+    assert_eq!(recovery.approvals, vec![guardians[1].pubkey()]);
+}
+
+#[tokio::test]
+async fn recovery_rejects_invalid_proposed_admins() {
+    let (mut test, guardians) = setup_guardians().await;
+    let manager = Pubkey::new_unique();
+    let add = builders::add_manager(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        &manager,
+    );
+    test.process(&[add], &[]).await.unwrap();
+
+    for proposed in [Pubkey::default(), test.admin.pubkey(), test.vault, manager] {
+        let instruction = propose(&test, &guardians[0], &proposed);
+        let result = test.process(&instruction, &[&guardians[0]]).await;
+        assert_vault_error(result, VaultError::InvalidPubkey);
+    }
+    assert!(test.vault_state().await.admin_recovery.is_none());
+}
+
+#[tokio::test]
+async fn removed_contacts_do_not_count_toward_quorum() {
+    let (mut test, guardians) = setup_guardians().await;
+    let new_admin = Keypair::new();
+    let instruction = propose(&test, &guardians[0], &new_admin.pubkey());
+    test.process(&instruction, &[&guardians[0]]).await.unwrap();
+    let instruction = approve(&test, &guardians[1], &new_admin.pubkey());
+    test.process(&instruction, &[&guardians[1]]).await.unwrap();
+
+    let admin = test.admin.pubkey();
+    let operation_id = test.vault_state().await.pending_operation_count + 1;
+    let remove = builders::remove_emergency_contact(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        operation_id,
+        &guardians[1].pubkey(),
+        EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS,
+    );
+    test.process(&[remove], &[]).await.unwrap();
+    test.advance_clock(EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS)
+        .await;
+    let execute_removal = builders::execute_pending_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &guardians[1].pubkey(),
+        &test.audit_log,
+        &admin,
+        operation_id,
+    );
+    test.process(&[execute_removal], &[]).await.unwrap();
+
+    let result = test
+        .process(&execute(&test, &new_admin), &[&new_admin])
+        .await;
+    assert_vault_error(result, VaultError::InsufficientSignatures);
+
+    // Un contacto vigente repone el quorum y reinicia el time-lock
+    let instruction = approve(&test, &guardians[2], &new_admin.pubkey());
+    test.process(&instruction, &[&guardians[2]]).await.unwrap();
+    let result = test
+        .process(&execute(&test, &new_admin), &[&new_admin])
+        .await;
+    assert_vault_error(result, VaultError::TimeLockNotElapsed);
+    test.advance_clock(DEFAULT_RECOVERY_DELAY_SECONDS).await;
+    test.process(&execute(&test, &new_admin), &[&new_admin])
+        .await
+        .unwrap();
+    assert_eq!(test.vault_state().await.admin, new_admin.pubkey());
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{OperationStatus, OperationType, ADMIN_PROPOSAL_EXPIRY_SECONDS},
+};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+fn propose(test: &VaultTest, signer: &Pubkey, new_admin: &Pubkey) -> [Instruction; 1] {
+    [builders::propose_admin(
+        &test.program_id,
+        signer,
+        &test.vault,
+        &test.audit_log,
+        new_admin,
+    )]
+}
+
+fn accept(test: &VaultTest, signer: &Pubkey) -> [Instruction; 1] {
+    [builders::accept_admin(
+        &test.program_id,
+        signer,
+        &test.vault,
+        &test.audit_log,
+    )]
+}
+
+fn cancel(test: &VaultTest, signer: &Pubkey) -> [Instruction; 1] {
+    [builders::cancel_admin_proposal(
+        &test.program_id,
+        signer,
+        &test.vault,
+        &test.audit_log,
+    )]
+}
+
+#[tokio::test]
+async fn proposed_admin_takes_over_only_after_accepting() {
+    let mut test = setup(test_config()).await;
+    let old_admin = test.admin.pubkey();
+    let new_admin = Keypair::new();
+
+    let outsider = Keypair::new();
+    let instruction = propose(&test, &outsider.pubkey(), &new_admin.pubkey());
+    let result = test.process(&instruction, &[&outsider]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    let instruction = propose(&test, &old_admin, &new_admin.pubkey());
+    test.process(&instruction, &[]).await.unwrap();
+    let vault = test.vault_state().await;
+    assert_eq!(vault.admin, old_admin);
+    assert_eq!(vault.pending_admin.unwrap().new_admin, new_admin.pubkey());
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::TransferAdmin);
+    assert_eq!(audit.target_account, Some(new_admin.pubkey()));
+    assert_eq!(audit.operation_status, Some(OperationStatus::Pending));
+
+    // Solo la clave propuesta puede aceptar
+    let instruction = accept(&test, &outsider.pubkey());
+    let result = test.process(&instruction, &[&outsider]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+    assert_eq!(test.vault_state().await.admin, old_admin);
+
+    let instruction = accept(&test, &new_admin.pubkey());
+    test.process(&instruction, &[&new_admin]).await.unwrap();
+    let vault = test.vault_state().await;
+    assert_eq!(vault.admin, new_admin.pubkey());
+    assert!(vault.pending_admin.is_none());
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.performer, new_admin.pubkey());
+    assert_eq!(audit.target_account, Some(old_admin));
+    assert_eq!(audit.operation_status, Some(OperationStatus::Executed));
+
+    // El admin anterior ya no puede iniciar otra transferencia
+    let instruction = propose(&test, &old_admin, &Pubkey::new_unique());
+    let result = test.process(&instruction, &[]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+}
+
+#[tokio::test]
+async fn expired_admin_proposal_cannot_be_accepted() {
+    let mut test = setup(test_config()).await;
+    let admin = test.admin.pubkey();
+    let new_admin = Keypair::new();
+    let instruction = propose(&test, &admin, &new_admin.pubkey());
+    test.process(&instruction, &[]).await.unwrap();
+
+    test.advance_clock(ADMIN_PROPOSAL_EXPIRY_SECONDS + 1).await;
+    let instruction = accept(&test, &new_admin.pubkey());
+    let result = test.process(&instruction, &[&new_admin]).await;
+    assert_vault_error(result, VaultError::OperationExpired);
+    assert_eq!(test.vault_state().await.admin, admin);
+}
+
+#[tokio::test]
+async fn cancelled_admin_proposal_cannot_be_accepted() {
+    let mut test = setup(test_config()).await;
+    let admin = test.admin.pubkey();
+    let new_admin = Keypair::new();
+    let instruction = propose(&test, &admin, &new_admin.pubkey());
+    test.process(&instruction, &[]).await.unwrap();
+
+    // La clave propuesta no puede cancelar; solo el admin vigente
+    let instruction = cancel(&test, &new_admin.pubkey());
+    let result = test.process(&instruction, &[&new_admin]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    test.process(&cancel(&test, &admin), &[]).await.unwrap();
+    assert!(test.vault_state().await.pending_admin.is_none());
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.target_account, Some(new_admin.pubkey()));
+    assert_eq!(audit.operation_status, Some(OperationStatus::Rejected));
+
+    let instruction = accept(&test, &new_admin.pubkey());
+    let result = test.process(&instruction, &[&new_admin]).await;
+    assert_vault_error(result, VaultError::OperationNotFound);
+    let result = test.process(&cancel(&test, &admin), &[]).await;
+    assert_vault_error(result, VaultError::OperationNotFound);
+    assert_eq!(test.vault_state().await.admin, admin);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config};
+use secure_vault::{
+    error::VaultError,
+    instruction::{builders, BatchOperation},
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+const DEPOSIT: u64 = 2_000_000_000;
+const AMOUNT: u64 = 100_000_000;
+
+#[tokio::test]
+async fn handlers_reject_vault_as_audit_account() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    let instructions = vec![
+        builders::withdraw(
+            &test.program_id,
+            &admin,
+            &test.vault,
+            &recipient,
+            &test.vault,
+            AMOUNT,
+        ),
+        builders::emergency_pause(&test.program_id, &admin, &test.vault, &test.vault),
+        builders::update_security_config(
+            &test.program_id,
+            &admin,
+            &test.vault,
+            &test.vault,
+            test_config(),
+        ),
+        builders::add_manager(
+            &test.program_id,
+            &admin,
+            &test.vault,
+            &test.vault,
+            &Pubkey::new_unique(),
+        ),
+        builders::execute_batch(
+            &test.program_id,
+            &admin,
+            &test.vault,
+            &[test.vault],
+            vec![BatchOperation::Withdraw {
+                amount: AMOUNT,
+                recipient,
+            }],
+        ),
+    ];
+    for instruction in instructions {
+        let result = test.process(&[instruction], &[]).await;
+        assert_vault_error(result, VaultError::InvalidAccount);
+    }
+
+    let vault = test.vault_state().await;
+    assert!(vault.is_valid_state());
+    assert!(!vault.is_paused);
+    assert_eq!(vault.total_balance, DEPOSIT);
+    assert!(test.account(&recipient).await.is_none());
+}
+
+#[tokio::test]
+async fn batch_rejects_recipient_as_audit_account() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+    let admin = test.admin.pubkey();
+
+    // El destinatario de un retiro no puede ser a la vez la cuenta de auditoría
+    let audit_log = test.audit_log;
+    let batch = builders::execute_batch(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &[audit_log],
+        vec![BatchOperation::Withdraw {
+            amount: AMOUNT,
+            recipient: audit_log,
+        }],
+    );
+    let result = test.process(&[batch], &[]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+    assert_eq!(test.vault_state().await.total_balance, DEPOSIT);
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::{builders, BatchOperation},
+    state::{AuditLog, OperationType},
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+
+const DEPOSIT: u64 = 5_000_000_000;
+const AMOUNT: u64 = 100_000_000;
+
+/// Crear `count` cuentas de auditoría adicionales, una por operación del batch
+async fn extra_audit_logs(test: &mut VaultTest, count: usize) -> Vec<Pubkey> {
+    let lamports = test
+        .context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(AuditLog::MAX_SIZE);
+    let mut audit_logs = Vec::new();
+    for _ in 0..count {
+        let audit_log = Keypair::new();
+        let create = system_instruction::create_account(
+            &test.admin.pubkey(),
+            &audit_log.pubkey(),
+            lamports,
+            AuditLog::MAX_SIZE as u64,
+            &test.program_id,
+        );
+        test.process(&[create], &[&audit_log]).await.unwrap();
+        audit_logs.push(audit_log.pubkey());
+    }
+    audit_logs
+}
+
+/// Vault con `DEPOSIT` lamports y una cuenta de auditoría por operación
+async fn setup_batch(max_daily_operations: u32, operations: usize) -> (VaultTest, Vec<Pubkey>) {
+    let mut config = test_config();
+    config.max_daily_operations = max_daily_operations;
+    let mut test = setup(config).await;
+    test.deposit(DEPOSIT).await;
+    let mut audit_logs = vec![test.audit_log];
+    audit_logs.extend(extra_audit_logs(&mut test, operations - 1).await);
+    (test, audit_logs)
+}
+
+#[tokio::test]
+async fn failing_operation_aborts_the_whole_batch() {
+    let (mut test, audit_logs) = setup_batch(100, 3).await;
+    let admin = test.admin.pubkey();
+    let (manager, recipient) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let before = test.vault_state().await;
+
+    let operations = vec![
+        BatchOperation::AddManager {
+            new_manager: manager,
+        },
+        BatchOperation::Withdraw {
+            amount: AMOUNT,
+            recipient,
+        },
+        BatchOperation::RemoveManager {
+            manager_to_remove: Pubkey::new_unique(),
+        },
+    ];
+    let batch = builders::execute_batch(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &audit_logs,
+        operations,
+    );
+    let result = test.process(&[batch], &[]).await;
+    assert_vault_error(result, VaultError::ManagerNotFound);
+
+    // Ni el alta previa ni el retiro llegan a aplicarse
+    let vault = test.vault_state().await;
+    assert_eq!(vault.managers, before.managers);
+    assert_eq!(vault.total_balance, DEPOSIT);
+    assert_eq!(vault.operation_count, before.operation_count);
+    assert_eq!(vault.daily_operation_count, before.daily_operation_count);
+    assert!(test.account(&recipient).await.is_none());
+    for audit_log in &audit_logs {
+        let entry: AuditLog = test.load(audit_log).await;
+        assert_eq!(entry.operation_id, 0);
+    }
+}
+
+#[tokio::test]
+async fn batch_is_one_correlated_audit_group_and_one_rate_limit_charge() {
+    let (mut test, audit_logs) = setup_batch(2, 3).await;
+    let admin = test.admin.pubkey();
+    let (manager, recipient) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let before = test.vault_state().await;
+
+    let operations = vec![
+        BatchOperation::AddManager {
+            new_manager: manager,
+        },
+        BatchOperation::Withdraw {
+            amount: AMOUNT,
+            recipient,
+        },
+        BatchOperation::Withdraw {
+            amount: AMOUNT,
+            recipient,
+        },
+    ];
+    let batch = builders::execute_batch(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &audit_logs,
+        operations,
+    );
+    test.process(&[batch], &[]).await.unwrap();
+
+    let vault = test.vault_state().await;
+    assert!(vault.managers.contains(&manager));
+    assert_eq!(vault.total_balance, DEPOSIT - 2 * AMOUNT);
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, 2 * AMOUNT);
+    assert_eq!(vault.operation_count, before.operation_count + 3);
+    assert_eq!(
+        vault.daily_operation_count,
+        before.daily_operation_count + 1
+    );
+
+    // Ids consecutivos, todos correlacionados con el primero
+    let group = before.operation_count + 1;
+    let expected = [
+        (OperationType::AddManager, Some(manager)),
+        (OperationType::Withdraw, Some(recipient)),
+        (OperationType::Withdraw, Some(recipient)),
+    ];
+    for (index, (audit_log, (operation_type, target))) in
+        audit_logs.iter().zip(expected).enumerate()
+    {
+        let entry: AuditLog = test.load(audit_log).await;
+        assert_eq!(entry.operation_id, group + index as u64);
+        assert_eq!(entry.correlation_id, Some(group));
+        assert_eq!(entry.operation_type, operation_type);
+        assert_eq!(entry.performer, admin);
+        assert_eq!(entry.target_account, target);
+    }
+
+    // El límite diario (2) admite una operación más tras el batch de tres
+    let (program_id, vault, audit_log) = (test.program_id, test.vault, test.audit_log);
+    let add = |new_manager: &Pubkey| {
+        [builders::add_manager(
+            &program_id,
+            &admin,
+            &vault,
+            &audit_log,
+            new_manager,
+        )]
+    };
+    let instruction = add(&Pubkey::new_unique());
+    test.process(&instruction, &[]).await.unwrap();
+    let instruction = add(&Pubkey::new_unique());
+    let result = test.process(&instruction, &[]).await;
+    assert_vault_error(result, VaultError::RateLimited);
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    security::AnomalyType,
+    state::{CircuitBreakerConfig, OperationStatus, OperationType},
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const DEPOSIT: u64 = 5_000_000_000;
+const FIRST: u64 = 400_000_000;
+const SECOND: u64 = 200_000_000;
+
+/// Vault con disyuntor al 10% de salidas en 24h, admin (peso 3) y tres managers (peso 1):
+/// el quórum de `Resume` es la mayoría (4 de 6)
+async fn setup_breaker() -> (VaultTest, Keypair) {
+    let mut config = test_config();
+    config.auto_pause_on_suspicious_activity = true;
+    config.circuit_breaker = Some(CircuitBreakerConfig {
+        window_hours: 24,
+        max_outflow_bps: 1_000,
+    });
+    let mut test = setup(config).await;
+    test.deposit(DEPOSIT).await;
+
+    let manager = Keypair::new();
+    let admin = test.admin.pubkey();
+    for new_manager in [manager.pubkey(), Pubkey::new_unique(), Pubkey::new_unique()] {
+        let add = builders::add_manager(
+            &test.program_id,
+            &admin,
+            &test.vault,
+            &test.audit_log,
+            &new_manager,
+        );
+        test.process(&[add], &[]).await.unwrap();
+    }
+    (test, manager)
+}
+
+async fn withdraw(test: &mut VaultTest, recipient: &Pubkey, amount: u64) {
+    let instruction = builders::withdraw(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        recipient,
+        &test.audit_log,
+        amount,
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn tripped_breaker_records_the_anomaly_and_resumes_only_with_quorum() {
+    let (mut test, manager) = setup_breaker().await;
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    withdraw(&mut test, &recipient, FIRST).await;
+    assert!(test.vault_state().await.circuit_breaker.trip.is_none());
+
+    // 12% del TVL al inicio de la ventana: la salida se completa y el vault queda pausado
+    withdraw(&mut test, &recipient, SECOND).await;
+    let vault = test.vault_state().await;
+    assert!(vault.is_paused);
+    let trip = vault.circuit_breaker.trip.unwrap();
+    assert_eq!(trip.anomaly_type, AnomalyType::LargeAmountTransfer);
+    assert_eq!(trip.window_outflow, FIRST + SECOND);
+    assert_eq!(trip.tvl, DEPOSIT);
+    assert_eq!(
+        test.account(&recipient).await.unwrap().lamports,
+        FIRST + SECOND
+    );
+
+    let instruction = builders::withdraw(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &recipient,
+        &test.audit_log,
+        SECOND,
+    );
+    let result = test.process(&[instruction], &[]).await;
+    assert_vault_error(result, VaultError::VaultPaused);
+
+    // El Resume directo no levanta el disyuntor
+    let resume = builders::resume(&test.program_id, &admin, &test.vault, &test.audit_log);
+    let result = test.process(&[resume], &[]).await;
+    assert_vault_error(result, VaultError::CircuitBreakerTripped);
+
+    let target = Pubkey::new_unique();
+    let create = builders::create_time_lock_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        OperationType::Resume,
+        0,
+        &target,
+        0,
+    );
+    test.process(&[create], &[]).await.unwrap();
+    let execute = builders::execute_pending_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &target,
+        &test.audit_log,
+        &admin,
+        1,
+    );
+    // La firma del admin (3) no alcanza el quórum (4)
+    let result = test.process(std::slice::from_ref(&execute), &[]).await;
+    assert_vault_error(result, VaultError::InsufficientSignatures);
+    assert!(test.vault_state().await.is_paused);
+
+    let sign = builders::sign_pending_operation(
+        &test.program_id,
+        &manager.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        1,
+    );
+    test.process(&[sign], &[&manager]).await.unwrap();
+    test.process(&[execute], &[]).await.unwrap();
+
+    let vault = test.vault_state().await;
+    assert!(!vault.is_paused);
+    assert!(vault.circuit_breaker.trip.is_none());
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::Resume);
+    assert_eq!(audit.operation_status, Some(OperationStatus::Executed));
+
+    // Las salidas anteriores al levantamiento dejan de contar
+    withdraw(&mut test, &recipient, SECOND).await;
+    assert!(test.vault_state().await.circuit_breaker.trip.is_none());
+}
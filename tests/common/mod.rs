@@ -0,0 +1,258 @@
+//! Utilidades compartidas por los tests de integración del programa.
+
+#![allow(dead_code)]
+
+use borsh::BorshDeserialize;
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    process_instruction,
+    state::{AuditLog, SecureVault, SecurityConfig, EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS},
+};
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+
+pub const INITIAL_LAMPORTS: u64 = 100_000_000_000;
+
+/// Entorno de pruebas con un vault inicializado y una cuenta de auditoría del programa
+pub struct VaultTest {
+    pub context: ProgramTestContext,
+    pub program_id: Pubkey,
+    pub admin: Keypair,
+    pub vault: Pubkey,
+    pub audit_log: Pubkey,
+}
+
+/// Configuración sin rate limit entre operaciones para encadenar instrucciones
+pub fn test_config() -> SecurityConfig {
+    let mut config = SecurityConfig::default();
+    config.min_time_between_operations = 0;
+    config
+}
+
+/// Crear el entorno con las cuentas extra dadas e inicializar el vault con `config`
+pub async fn setup_with_accounts(
+    config: SecurityConfig,
+    extra_accounts: Vec<(Pubkey, Account)>,
+) -> VaultTest {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("secure_vault", program_id, processor!(process_instruction));
+
+    let admin = Keypair::new();
+    program_test.add_account(admin.pubkey(), funded_account(INITIAL_LAMPORTS));
+    let audit_log = Pubkey::new_unique();
+    program_test.add_account(audit_log, audit_account(&program_id));
+    for (key, account) in extra_accounts {
+        program_test.add_account(key, account);
+    }
+
+    let context = program_test.start_with_context().await;
+    let (vault, _) = builders::find_vault_address(&program_id, &admin.pubkey());
+    let mut test = VaultTest {
+        context,
+        program_id,
+        admin,
+        vault,
+        audit_log,
+    };
+    let instruction = builders::initialize_vault(&program_id, &test.admin.pubkey(), config);
+    test.process(&[instruction], &[]).await.unwrap();
+    test
+}
+
+pub async fn setup(config: SecurityConfig) -> VaultTest {
+    setup_with_accounts(config, Vec::new()).await
+}
+
+/// Cuenta del sistema con saldo
+pub fn funded_account(lamports: u64) -> Account {
+    Account::new(lamports, 0, &solana_sdk::system_program::id())
+}
+
+/// Cuenta de auditoría: propiedad del programa y con el tamaño de un `AuditLog`
+pub fn audit_account(program_id: &Pubkey) -> Account {
+    Account::new(INITIAL_LAMPORTS, AuditLog::MAX_SIZE, program_id)
+}
+
+/// Comprobar que la transacción falló con el `VaultError` dado
+pub fn assert_vault_error(result: Result<(), TransactionError>, expected: VaultError) {
+    match result {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(code))) => {
+            assert_eq!(code, expected as u32, "expected {:?}", expected)
+        }
+        other => panic!("expected {:?}, got {:?}", expected, other),
+    }
+}
+
+impl VaultTest {
+    /// Enviar instrucciones; el payer del contexto paga las comisiones
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), TransactionError> {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+
+        // El admin firma solo si alguna instrucción lo requiere
+        let admin = self.admin.pubkey();
+        let admin_signs = instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter())
+            .any(|meta| meta.is_signer && meta.pubkey == admin);
+        let mut all_signers: Vec<&Keypair> = vec![&self.context.payer];
+        if admin_signs {
+            all_signers.push(&self.admin);
+        }
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|err| err.unwrap())
+    }
+
+    pub async fn account(&mut self, key: &Pubkey) -> Option<Account> {
+        self.context.banks_client.get_account(*key).await.unwrap()
+    }
+
+    pub async fn vault_state(&mut self) -> SecureVault {
+        let vault = self.vault;
+        let account = self.account(&vault).await.expect("vault account");
+        SecureVault::deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn load<T: BorshDeserialize>(&mut self, key: &Pubkey) -> T {
+        let account = self.account(key).await.expect("account");
+        T::deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn audit_entry(&mut self) -> AuditLog {
+        let audit_log = self.audit_log;
+        self.load(&audit_log).await
+    }
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+    }
+
+    /// Adelantar el reloj `seconds` segundos
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Depositar lamports nativos desde el admin
+    pub async fn deposit(&mut self, amount: u64) {
+        let admin = self.admin.pubkey();
+        let instruction = builders::deposit(&self.program_id, &admin, &self.vault, &admin, amount);
+        self.process(&[instruction], &[]).await.unwrap();
+    }
+
+    /// Agregar un contacto de emergencia: programar el alta y ejecutarla tras su time-lock
+    pub async fn add_emergency_contact(&mut self, contact: &Pubkey) {
+        let admin = self.admin.pubkey();
+        let operation_id = self.vault_state().await.pending_operation_count + 1;
+        let schedule = builders::add_emergency_contact(
+            &self.program_id,
+            &admin,
+            &self.vault,
+            &self.audit_log,
+            operation_id,
+            contact,
+            EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS,
+        );
+        self.process(&[schedule], &[]).await.unwrap();
+        self.advance_clock(EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS)
+            .await;
+        let execute = builders::execute_pending_operation(
+            &self.program_id,
+            &admin,
+            &self.vault,
+            contact,
+            &self.audit_log,
+            &admin,
+            operation_id,
+        );
+        self.process(&[execute], &[]).await.unwrap();
+    }
+
+    /// Crear un mint SPL con el admin como autoridad
+    pub async fn create_mint(&mut self, decimals: u8) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &self.admin.pubkey(),
+                None,
+                decimals,
+            )
+            .unwrap(),
+        ];
+        self.process(&instructions, &[&mint]).await.unwrap();
+        mint.pubkey()
+    }
+
+    /// Crear la ATA de `owner` para `mint` y devolver su dirección
+    pub async fn create_token_account(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let payer = self.context.payer.pubkey();
+        let instruction = create_associated_token_account(&payer, owner, mint, &spl_token::id());
+        self.process(&[instruction], &[]).await.unwrap();
+        get_associated_token_address(owner, mint)
+    }
+
+    /// Acuñar tokens a una token account (firma el admin como autoridad del mint)
+    pub async fn mint_to(&mut self, mint: &Pubkey, destination: &Pubkey, amount: u64) {
+        let admin = self.admin.pubkey();
+        let instruction = spl_token::instruction::mint_to(
+            &spl_token::id(),
+            mint,
+            destination,
+            &admin,
+            &[],
+            amount,
+        )
+        .unwrap();
+        self.process(&[instruction], &[]).await.unwrap();
+    }
+
+    pub async fn token_balance(&mut self, token_account: &Pubkey) -> u64 {
+        let account = self.account(token_account).await.expect("token account");
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+}
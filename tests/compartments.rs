@@ -0,0 +1,316 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{Asset, DelayTier, OperationType, SecureVault, SecurityConfig},
+};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const DEPOSIT: u64 = 5_000_000_000;
+const ALLOCATION: u64 = 2_000_000_000;
+const AMOUNT: u64 = 100_000_000;
+
+/// Vault con el compartimento "opex" gestionado por `manager` y `ALLOCATION` lamports
+async fn setup_compartment(manager: &Pubkey) -> VaultTest {
+    setup_compartment_with(test_config(), manager).await
+}
+
+async fn setup_compartment_with(config: SecurityConfig, manager: &Pubkey) -> VaultTest {
+    let mut test = setup(config).await;
+    test.deposit(DEPOSIT).await;
+
+    let admin = test.admin.pubkey();
+    let configure = builders::configure_compartment(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        "opex".to_string(),
+        vec![*manager],
+        ALLOCATION,
+    );
+    let fund = builders::transfer_between_compartments(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        None,
+        Some("opex".to_string()),
+        ALLOCATION,
+    );
+    test.process(&[configure, fund], &[]).await.unwrap();
+    test
+}
+
+fn withdraw(test: &VaultTest, performer: &Pubkey, recipient: &Pubkey) -> [Instruction; 1] {
+    withdraw_amount(test, performer, recipient, AMOUNT)
+}
+
+fn withdraw_amount(
+    test: &VaultTest,
+    performer: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+) -> [Instruction; 1] {
+    [builders::withdraw_from_compartment(
+        &test.program_id,
+        performer,
+        &test.vault,
+        recipient,
+        &test.audit_log,
+        "opex".to_string(),
+        amount,
+    )]
+}
+
+fn assert_balances_consistent(vault: &SecureVault) {
+    let allocated: u64 = vault.compartments.iter().map(|c| c.balance).sum();
+    assert!(allocated + vault.vesting_reserved <= vault.total_balance);
+    assert!(vault.is_valid_state());
+}
+
+#[tokio::test]
+async fn compartment_withdrawal_requires_permission_from_the_table() {
+    let manager = Keypair::new();
+    let mut test = setup_compartment(&manager.pubkey()).await;
+    let recipient = Pubkey::new_unique();
+
+    // Manager del compartimento sin `CompartmentWithdraw` en la tabla de permisos
+    let instruction = withdraw(&test, &manager.pubkey(), &recipient);
+    let result = test.process(&instruction, &[&manager]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    let admin = test.admin.pubkey();
+    let upsert = builders::upsert_role(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        "opex".to_string(),
+        OperationType::CompartmentWithdraw.permission_bit(),
+    );
+    let assign = builders::assign_role(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        &manager.pubkey(),
+        "opex".to_string(),
+    );
+    test.process(&[upsert, assign], &[]).await.unwrap();
+    test.process(&instruction, &[&manager]).await.unwrap();
+
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, AMOUNT);
+    let vault = test.vault_state().await;
+    assert_eq!(
+        vault.get_compartment("opex").unwrap().balance,
+        ALLOCATION - AMOUNT
+    );
+    assert_eq!(vault.total_balance, DEPOSIT - AMOUNT);
+    assert_balances_consistent(&vault);
+}
+
+#[tokio::test]
+async fn vault_manager_outside_compartment_cannot_withdraw() {
+    let compartment_manager = Keypair::new();
+    let mut test = setup_compartment(&compartment_manager.pubkey()).await;
+
+    let vault_manager = Keypair::new();
+    let admin = test.admin.pubkey();
+    let add = builders::add_manager(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        &vault_manager.pubkey(),
+    );
+    test.process(&[add], &[]).await.unwrap();
+
+    let instruction = withdraw(&test, &vault_manager.pubkey(), &Pubkey::new_unique());
+    let result = test.process(&instruction, &[&vault_manager]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+}
+
+#[tokio::test]
+async fn compartment_override_follows_the_permission_table() {
+    let compartment_manager = Keypair::new();
+    let mut test = setup_compartment(&compartment_manager.pubkey()).await;
+    let treasurer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+
+    // Sin `ManageCompartments` solo los managers del compartimento pueden retirar
+    let admin = test.admin.pubkey();
+    let upsert = |permissions| {
+        builders::upsert_role(
+            &test.program_id,
+            &admin,
+            &test.vault,
+            &test.audit_log,
+            "treasurer".to_string(),
+            permissions,
+        )
+    };
+    let withdraw_only = upsert(OperationType::CompartmentWithdraw.permission_bit());
+    let with_override = upsert(OperationType::permissions_of(&[
+        OperationType::CompartmentWithdraw,
+        OperationType::ManageCompartments,
+    ]));
+    let assign = builders::assign_role(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        &treasurer.pubkey(),
+        "treasurer".to_string(),
+    );
+    test.process(&[withdraw_only, assign], &[]).await.unwrap();
+
+    let instruction = withdraw(&test, &treasurer.pubkey(), &recipient);
+    let result = test.process(&instruction, &[&treasurer]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    test.process(&[with_override], &[]).await.unwrap();
+    test.process(&instruction, &[&treasurer]).await.unwrap();
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, AMOUNT);
+}
+
+#[tokio::test]
+async fn compartments_cannot_exceed_vault_balance() {
+    let manager = Keypair::new();
+    let mut test = setup_compartment(&manager.pubkey()).await;
+
+    let admin = test.admin.pubkey();
+    let overfund = builders::transfer_between_compartments(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        None,
+        Some("opex".to_string()),
+        DEPOSIT - ALLOCATION + 1,
+    );
+    assert!(test.process(&[overfund], &[]).await.is_err());
+    assert_balances_consistent(&test.vault_state().await);
+}
+
+#[tokio::test]
+async fn compartment_withdrawal_respects_delay_tiers() {
+    let mut config = test_config();
+    config.withdrawal_delay_tiers = vec![DelayTier {
+        min_amount: 5 * AMOUNT,
+        delay_seconds: 600,
+        required_weight: 0,
+    }];
+    let manager = Keypair::new();
+    let mut test = setup_compartment_with(config, &manager.pubkey()).await;
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    // Un monto con tramo de delay no puede salir de inmediato por un compartimento
+    let instruction = withdraw_amount(&test, &admin, &recipient, 5 * AMOUNT);
+    let result = test.process(&instruction, &[]).await;
+    assert_vault_error(result, VaultError::DelayTooShort);
+    assert!(test.account(&recipient).await.is_none());
+
+    let instruction = withdraw_amount(&test, &admin, &recipient, 5 * AMOUNT - 1);
+    test.process(&instruction, &[]).await.unwrap();
+    assert_eq!(
+        test.account(&recipient).await.unwrap().lamports,
+        5 * AMOUNT - 1
+    );
+}
+
+#[tokio::test]
+async fn compartment_withdrawal_consumes_manager_quota() {
+    let manager = Keypair::new();
+    let mut test = setup_compartment(&manager.pubkey()).await;
+    let admin = test.admin.pubkey();
+    let add = builders::add_manager(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        &manager.pubkey(),
+    );
+    let quota = builders::set_manager_quota(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        &manager.pubkey(),
+        3 * AMOUNT,
+        2 * AMOUNT,
+    );
+    test.process(&[add, quota], &[]).await.unwrap();
+    let recipient = Pubkey::new_unique();
+
+    // Por transacción
+    let instruction = withdraw_amount(&test, &manager.pubkey(), &recipient, 2 * AMOUNT + 1);
+    let result = test.process(&instruction, &[&manager]).await;
+    assert_vault_error(result, VaultError::ManagerQuotaExceeded);
+
+    // Diaria: la primera salida consume la cuota
+    let instruction = withdraw_amount(&test, &manager.pubkey(), &recipient, 2 * AMOUNT);
+    test.process(&instruction, &[&manager]).await.unwrap();
+    let vault = test.vault_state().await;
+    assert_eq!(
+        vault
+            .get_manager_quota(&manager.pubkey())
+            .unwrap()
+            .daily_spent,
+        2 * AMOUNT
+    );
+    let instruction = withdraw_amount(&test, &manager.pubkey(), &recipient, AMOUNT + 1);
+    let result = test.process(&instruction, &[&manager]).await;
+    assert_vault_error(result, VaultError::ManagerQuotaExceeded);
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, 2 * AMOUNT);
+}
+
+#[tokio::test]
+async fn large_compartment_withdrawal_escalates_to_multisig() {
+    let mut config = test_config();
+    config.require_multisig_for_large_amounts = 5 * AMOUNT;
+    let manager = Keypair::new();
+    let mut test = setup_compartment_with(config, &manager.pubkey()).await;
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    // El límite del compartimento lo permitiría, pero el umbral del vault exige firmas
+    let instruction = withdraw_amount(&test, &admin, &recipient, 5 * AMOUNT);
+    let result = test.process(&instruction, &[]).await;
+    assert_vault_error(result, VaultError::InsufficientSignatures);
+    assert!(test.account(&recipient).await.is_none());
+    let vault = test.vault_state().await;
+    assert_eq!(vault.get_compartment("opex").unwrap().balance, ALLOCATION);
+}
+
+#[tokio::test]
+async fn compartment_withdrawal_respects_vault_daily_limit() {
+    let manager = Keypair::new();
+    let mut test = setup_compartment(&manager.pubkey()).await;
+    let admin = test.admin.pubkey();
+    let limits = builders::set_asset_limits(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        Asset::Native,
+        3 * AMOUNT,
+        ALLOCATION,
+    );
+    test.process(&[limits], &[]).await.unwrap();
+    let recipient = Pubkey::new_unique();
+
+    let instruction = withdraw_amount(&test, &admin, &recipient, 2 * AMOUNT);
+    test.process(&instruction, &[]).await.unwrap();
+    let instruction = withdraw_amount(&test, &admin, &recipient, AMOUNT + 1);
+    let result = test.process(&instruction, &[]).await;
+    assert_vault_error(result, VaultError::DailyLimitExceeded);
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, 2 * AMOUNT);
+}
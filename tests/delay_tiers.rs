@@ -0,0 +1,145 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{DelayTier, OperationType, PendingOperation},
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signer};
+
+const DEPOSIT: u64 = 5_000_000_000;
+const TIER_1: u64 = 200_000_000;
+const TIER_2: u64 = 500_000_000;
+const TIER_1_DELAY: i64 = 600;
+const TIER_2_DELAY: i64 = 1800;
+
+async fn setup_tiers() -> VaultTest {
+    let mut config = test_config();
+    config.withdrawal_delay_tiers = vec![
+        DelayTier {
+            min_amount: TIER_1,
+            delay_seconds: TIER_1_DELAY,
+            required_weight: 0,
+        },
+        DelayTier {
+            min_amount: TIER_2,
+            delay_seconds: TIER_2_DELAY,
+            required_weight: 0,
+        },
+    ];
+    let mut test = setup(config).await;
+    test.deposit(DEPOSIT).await;
+    test
+}
+
+fn withdraw(test: &VaultTest, recipient: &Pubkey, amount: u64) -> Instruction {
+    builders::withdraw(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        recipient,
+        &test.audit_log,
+        amount,
+    )
+}
+
+fn tiered_withdraw(
+    test: &VaultTest,
+    recipient: &Pubkey,
+    amount: u64,
+    operation_id: u64,
+) -> Instruction {
+    builders::with_pending_operation(
+        withdraw(test, recipient, amount),
+        &test.program_id,
+        &test.vault,
+        operation_id,
+    )
+}
+
+async fn scheduled_delay(test: &mut VaultTest, operation_id: u64) -> i64 {
+    let (pending, _) =
+        builders::find_pending_operation_address(&test.program_id, &test.vault, operation_id);
+    let operation: PendingOperation = test.load(&pending).await;
+    assert_eq!(operation.operation_type, OperationType::Withdraw);
+    operation.scheduled_execution - operation.created_at
+}
+
+#[tokio::test]
+async fn amount_below_first_tier_is_immediate() {
+    let mut test = setup_tiers().await;
+    let recipient = Pubkey::new_unique();
+
+    let instruction = withdraw(&test, &recipient, TIER_1 - 1);
+    test.process(&[instruction], &[]).await.unwrap();
+
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, TIER_1 - 1);
+    assert_eq!(test.vault_state().await.pending_operation_count, 0);
+}
+
+#[tokio::test]
+async fn tier_boundaries_select_the_tier_delay() {
+    let mut test = setup_tiers().await;
+    let recipient = Pubkey::new_unique();
+
+    let cases = [
+        (TIER_1, TIER_1_DELAY),
+        (TIER_2 - 1, TIER_1_DELAY),
+        (TIER_2, TIER_2_DELAY),
+    ];
+    for (index, (amount, delay)) in cases.into_iter().enumerate() {
+        let operation_id = index as u64 + 1;
+        let instruction = tiered_withdraw(&test, &recipient, amount, operation_id);
+        test.process(&[instruction], &[]).await.unwrap();
+        assert_eq!(scheduled_delay(&mut test, operation_id).await, delay);
+    }
+
+    // Nada se transfiere hasta ejecutar las operaciones
+    assert!(test.account(&recipient).await.is_none());
+    assert_eq!(test.vault_state().await.total_balance, DEPOSIT);
+}
+
+#[tokio::test]
+async fn tiered_withdraw_executes_after_tier_delay() {
+    let mut test = setup_tiers().await;
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    let instruction = tiered_withdraw(&test, &recipient, TIER_1, 1);
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let execute = builders::execute_pending_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &recipient,
+        &test.audit_log,
+        &admin,
+        1,
+    );
+    let result = test.process(std::slice::from_ref(&execute), &[]).await;
+    assert_vault_error(result, VaultError::TimeLockNotElapsed);
+
+    test.advance_clock(TIER_1_DELAY).await;
+    test.process(&[execute], &[]).await.unwrap();
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, TIER_1);
+}
+
+#[tokio::test]
+async fn tiered_withdraw_requires_next_pending_account() {
+    let mut test = setup_tiers().await;
+    let recipient = Pubkey::new_unique();
+
+    let instruction = withdraw(&test, &recipient, TIER_1);
+    let result = test.process(&[instruction], &[]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+
+    // PDA de un id distinto al próximo
+    let instruction = tiered_withdraw(&test, &recipient, TIER_1, 2);
+    let result = test.process(&[instruction], &[]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+
+    assert_eq!(test.vault_state().await.pending_operation_count, 0);
+    assert!(test.account(&recipient).await.is_none());
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::{assert_vault_error, funded_account, setup_with_accounts, test_config, VaultTest};
+use secure_vault::{error::VaultError, instruction::builders, state::DepositorLedger};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const DEPOSIT: u64 = 2_000_000_000;
+const REFUND: u64 = 500_000_000;
+
+/// Vault en modo reembolsable con un depositante que ya aportó `DEPOSIT`
+async fn setup_deposited() -> (VaultTest, Keypair, Pubkey) {
+    let depositor = Keypair::new();
+    let mut config = test_config();
+    config.refundable_mode = true;
+    let mut test = setup_with_accounts(
+        config,
+        vec![(depositor.pubkey(), funded_account(10 * DEPOSIT))],
+    )
+    .await;
+    let deposit = builders::deposit(
+        &test.program_id,
+        &depositor.pubkey(),
+        &test.vault,
+        &depositor.pubkey(),
+        DEPOSIT,
+    );
+    test.process(&[deposit], &[&depositor]).await.unwrap();
+    let (ledger, _) =
+        builders::find_depositor_ledger_address(&test.program_id, &test.vault, &depositor.pubkey());
+    (test, depositor, ledger)
+}
+
+#[tokio::test]
+async fn withdraw_deposit_rejects_aliased_audit_account() {
+    let (mut test, depositor, ledger) = setup_deposited().await;
+
+    // El depositante no puede hacer que el log sobrescriba el vault ni su registro
+    for audit_log in [test.vault, ledger] {
+        let withdraw = builders::withdraw_deposit(
+            &test.program_id,
+            &depositor.pubkey(),
+            &test.vault,
+            &audit_log,
+            REFUND,
+        );
+        let result = test.process(&[withdraw], &[&depositor]).await;
+        assert_vault_error(result, VaultError::InvalidAccount);
+    }
+
+    let state: DepositorLedger = test.load(&ledger).await;
+    assert_eq!(state.balance(), DEPOSIT);
+    assert!(test.vault_state().await.is_valid_state());
+
+    let withdraw = builders::withdraw_deposit(
+        &test.program_id,
+        &depositor.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        REFUND,
+    );
+    test.process(&[withdraw], &[&depositor]).await.unwrap();
+    let state: DepositorLedger = test.load(&ledger).await;
+    assert_eq!(state.balance(), DEPOSIT - REFUND);
+}
+
+#[tokio::test]
+async fn refund_depositor_rejects_aliased_audit_account() {
+    let (mut test, depositor, ledger) = setup_deposited().await;
+
+    for audit_log in [test.vault, ledger] {
+        let refund = builders::refund_depositor(
+            &test.program_id,
+            &test.admin.pubkey(),
+            &test.vault,
+            &depositor.pubkey(),
+            &audit_log,
+            REFUND,
+        );
+        let result = test.process(&[refund], &[]).await;
+        assert_vault_error(result, VaultError::InvalidAccount);
+    }
+
+    let state: DepositorLedger = test.load(&ledger).await;
+    assert_eq!(state.balance(), DEPOSIT);
+    assert!(test.vault_state().await.is_valid_state());
+}
+
+#[tokio::test]
+async fn deposit_without_ledger_is_only_accepted_when_not_refundable() {
+    let (mut test, depositor, ledger) = setup_deposited().await;
+    let mut deposit = builders::deposit(
+        &test.program_id,
+        &depositor.pubkey(),
+        &test.vault,
+        &depositor.pubkey(),
+        DEPOSIT,
+    );
+    deposit.accounts.pop();
+    let result = test.process(&[deposit.clone()], &[&depositor]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+
+    let mut config = test_config();
+    config.refundable_mode = false;
+    let update = builders::update_security_config(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        config,
+    );
+    test.process(&[update], &[]).await.unwrap();
+    test.process(&[deposit], &[&depositor]).await.unwrap();
+
+    // El depósito cuenta en el vault pero no en el registro del depositante
+    assert_eq!(test.vault_state().await.total_balance, 2 * DEPOSIT);
+    let state: DepositorLedger = test.load(&ledger).await;
+    assert_eq!(state.balance(), DEPOSIT);
+}
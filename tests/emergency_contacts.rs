@@ -0,0 +1,171 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{
+        OperationStatus, OperationType, EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS,
+        MAX_EMERGENCY_CONTACTS,
+    },
+};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+fn schedule_add(test: &VaultTest, signer: &Pubkey, id: u64, contact: &Pubkey) -> Instruction {
+    builders::add_emergency_contact(
+        &test.program_id,
+        signer,
+        &test.vault,
+        &test.audit_log,
+        id,
+        contact,
+        EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS,
+    )
+}
+
+fn execute(test: &VaultTest, id: u64, contact: &Pubkey) -> Instruction {
+    let admin = test.admin.pubkey();
+    builders::execute_pending_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        contact,
+        &test.audit_log,
+        &admin,
+        id,
+    )
+}
+
+async fn next_operation_id(test: &mut VaultTest) -> u64 {
+    test.vault_state().await.pending_operation_count + 1
+}
+
+#[tokio::test]
+async fn contact_changes_are_admin_only_and_time_locked() {
+    let mut test = setup(test_config()).await;
+    let admin = test.admin.pubkey();
+    let contact = Pubkey::new_unique();
+
+    let outsider = Keypair::new();
+    let instruction = schedule_add(&test, &outsider.pubkey(), 1, &contact);
+    let result = test.process(&[instruction], &[&outsider]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    let short = builders::add_emergency_contact(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        &contact,
+        EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS - 1,
+    );
+    assert_vault_error(test.process(&[short], &[]).await, VaultError::DelayTooShort);
+
+    test.process(&[schedule_add(&test, &admin, 1, &contact)], &[])
+        .await
+        .unwrap();
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::AddEmergencyContact);
+    assert_eq!(audit.target_account, Some(contact));
+    let result = test.process(&[execute(&test, 1, &contact)], &[]).await;
+    assert_vault_error(result, VaultError::TimeLockNotElapsed);
+    assert!(test.vault_state().await.emergency_contacts.is_empty());
+
+    test.advance_clock(EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS)
+        .await;
+    test.process(&[execute(&test, 1, &contact)], &[])
+        .await
+        .unwrap();
+    assert_eq!(test.vault_state().await.emergency_contacts, vec![contact]);
+    assert_eq!(
+        test.audit_entry().await.operation_status,
+        Some(OperationStatus::Executed)
+    );
+
+    // La baja sigue el mismo time-lock
+    let remove = |signer: &Pubkey, id: u64, contact: &Pubkey| {
+        builders::remove_emergency_contact(
+            &test.program_id,
+            signer,
+            &test.vault,
+            &test.audit_log,
+            id,
+            contact,
+            EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS,
+        )
+    };
+    let instruction = remove(&outsider.pubkey(), 2, &contact);
+    let unknown = remove(&admin, 2, &Pubkey::new_unique());
+    let scheduled = remove(&admin, 2, &contact);
+    let result = test.process(&[instruction], &[&outsider]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+    let result = test.process(&[unknown], &[]).await;
+    assert_vault_error(result, VaultError::InvalidEmergencyOperation);
+
+    test.process(&[scheduled], &[]).await.unwrap();
+    let result = test.process(&[execute(&test, 2, &contact)], &[]).await;
+    assert_vault_error(result, VaultError::TimeLockNotElapsed);
+    test.advance_clock(EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS)
+        .await;
+    test.process(&[execute(&test, 2, &contact)], &[])
+        .await
+        .unwrap();
+    assert!(test.vault_state().await.emergency_contacts.is_empty());
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::RemoveEmergencyContact);
+    assert_eq!(audit.operation_status, Some(OperationStatus::Executed));
+}
+
+#[tokio::test]
+async fn invalid_duplicate_and_excess_contacts_are_rejected() {
+    let mut test = setup(test_config()).await;
+    let admin = test.admin.pubkey();
+
+    for invalid in [admin, Pubkey::default()] {
+        let instruction = schedule_add(&test, &admin, 1, &invalid);
+        let result = test.process(&[instruction], &[]).await;
+        assert_vault_error(result, VaultError::InvalidPubkey);
+    }
+
+    let first = Pubkey::new_unique();
+    test.add_emergency_contact(&first).await;
+    let id = next_operation_id(&mut test).await;
+    let result = test
+        .process(&[schedule_add(&test, &admin, id, &first)], &[])
+        .await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+
+    for _ in 1..MAX_EMERGENCY_CONTACTS - 1 {
+        test.add_emergency_contact(&Pubkey::new_unique()).await;
+    }
+
+    // Dos altas programadas con un solo hueco libre: la segunda falla al ejecutar
+    let (last, extra) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let id = next_operation_id(&mut test).await;
+    test.process(&[schedule_add(&test, &admin, id, &last)], &[])
+        .await
+        .unwrap();
+    test.process(&[schedule_add(&test, &admin, id + 1, &extra)], &[])
+        .await
+        .unwrap();
+    test.advance_clock(EMERGENCY_CONTACT_CHANGE_DELAY_SECONDS)
+        .await;
+    test.process(&[execute(&test, id, &last)], &[])
+        .await
+        .unwrap();
+    let result = test.process(&[execute(&test, id + 1, &extra)], &[]).await;
+    assert_vault_error(result, VaultError::InvalidEmergencyOperation);
+
+    let vault = test.vault_state().await;
+    assert_eq!(vault.emergency_contacts.len(), MAX_EMERGENCY_CONTACTS);
+    assert!(!vault.emergency_contacts.contains(&extra));
+    let id = next_operation_id(&mut test).await;
+    let instruction = schedule_add(&test, &admin, id, &Pubkey::new_unique());
+    let result = test.process(&[instruction], &[]).await;
+    assert_vault_error(result, VaultError::InvalidEmergencyOperation);
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{
+        DeadManSwitch, PauseReason, MIN_INACTIVITY_CLAIM_DELAY_SECONDS,
+        MIN_INACTIVITY_TIMEOUT_SECONDS,
+    },
+};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+
+const DEPOSIT: u64 = 2_000_000_000;
+const PAUSE_SECONDS: i64 = 3600;
+
+/// Vault inactivo con un reclamo del beneficiario listo para ejecutarse
+async fn setup_executable_claim(beneficiary: &Keypair) -> VaultTest {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+
+    let switch = DeadManSwitch {
+        inactivity_timeout_seconds: MIN_INACTIVITY_TIMEOUT_SECONDS,
+        claim_delay_seconds: MIN_INACTIVITY_CLAIM_DELAY_SECONDS,
+        beneficiary: Some(beneficiary.pubkey()),
+    };
+    let admin = test.admin.pubkey();
+    let configure = builders::set_dead_man_switch(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        Some(switch),
+    );
+    test.process(&[configure], &[]).await.unwrap();
+
+    test.advance_clock(MIN_INACTIVITY_TIMEOUT_SECONDS).await;
+    let claim = builders::claim_inactive_vault(
+        &test.program_id,
+        &beneficiary.pubkey(),
+        &test.vault,
+        &test.audit_log,
+    );
+    test.process(&[claim], &[beneficiary]).await.unwrap();
+    test.advance_clock(MIN_INACTIVITY_CLAIM_DELAY_SECONDS).await;
+    test
+}
+
+fn execute_claim(test: &VaultTest, beneficiary: &Keypair) -> Instruction {
+    builders::execute_inactivity_claim(
+        &test.program_id,
+        &test.context.payer.pubkey(),
+        &test.vault,
+        &beneficiary.pubkey(),
+        &test.audit_log,
+    )
+}
+
+#[tokio::test]
+async fn inactivity_claim_sweeps_sol_and_keeps_vault_rent_exempt() {
+    let beneficiary = Keypair::new();
+    let mut test = setup_executable_claim(&beneficiary).await;
+
+    let instruction = execute_claim(&test, &beneficiary);
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let beneficiary_account = test.account(&beneficiary.pubkey()).await.unwrap();
+    assert_eq!(beneficiary_account.lamports, DEPOSIT);
+    let vault = test.vault_state().await;
+    assert_eq!(vault.total_balance, 0);
+    assert!(vault.inactivity_claim.is_none());
+
+    let vault_key = test.vault;
+    let vault_account = test.account(&vault_key).await.unwrap();
+    let rent = test.context.banks_client.get_rent().await.unwrap();
+    assert!(vault_account.lamports >= rent.minimum_balance(vault_account.data.len()));
+}
+
+#[tokio::test]
+async fn paused_vault_blocks_inactivity_claim_until_pause_expires() {
+    let beneficiary = Keypair::new();
+    let mut test = setup_executable_claim(&beneficiary).await;
+
+    // La pausa temporal no cuenta como actividad, así que el reclamo sigue vigente
+    let admin = test.admin.pubkey();
+    let pause = builders::timed_pause(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        PauseReason::SuspiciousActivity,
+        PAUSE_SECONDS,
+    );
+    test.process(&[pause], &[]).await.unwrap();
+
+    let instruction = execute_claim(&test, &beneficiary);
+    let result = test.process(std::slice::from_ref(&instruction), &[]).await;
+    assert_vault_error(result, VaultError::VaultPaused);
+    assert!(test.account(&beneficiary.pubkey()).await.is_none());
+
+    test.advance_clock(PAUSE_SECONDS).await;
+    test.process(&[instruction], &[]).await.unwrap();
+    let beneficiary_account = test.account(&beneficiary.pubkey()).await.unwrap();
+    assert_eq!(beneficiary_account.lamports, DEPOSIT);
+}
+
+#[tokio::test]
+async fn emergency_pause_blocks_inactivity_claim() {
+    let beneficiary = Keypair::new();
+    let mut test = setup_executable_claim(&beneficiary).await;
+
+    let admin = test.admin.pubkey();
+    let pause = builders::emergency_pause(&test.program_id, &admin, &test.vault, &test.audit_log);
+    test.process(&[pause], &[]).await.unwrap();
+
+    let instruction = execute_claim(&test, &beneficiary);
+    let result = test.process(&[instruction], &[]).await;
+    assert_vault_error(result, VaultError::VaultPaused);
+    assert_eq!(test.vault_state().await.total_balance, DEPOSIT);
+}
+
+#[tokio::test]
+async fn inactivity_handlers_reject_aliased_audit_account() {
+    let beneficiary = Keypair::new();
+    let mut test = setup_executable_claim(&beneficiary).await;
+
+    // Ni el reclamo ni su ejecución pueden hacer que el log sobrescriba el vault
+    let claim = builders::claim_inactive_vault(
+        &test.program_id,
+        &beneficiary.pubkey(),
+        &test.vault,
+        &test.vault,
+    );
+    let result = test.process(&[claim], &[&beneficiary]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+
+    let mut instruction = execute_claim(&test, &beneficiary);
+    instruction.accounts[3].pubkey = test.vault;
+    let result = test.process(&[instruction], &[]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+
+    let admin = test.admin.pubkey();
+    let heartbeat = builders::heartbeat(&test.program_id, &admin, &test.vault, &test.vault);
+    let result = test.process(&[heartbeat], &[]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+
+    let vault = test.vault_state().await;
+    assert!(vault.is_valid_state());
+    assert!(vault.inactivity_claim.is_some());
+    assert_eq!(vault.total_balance, DEPOSIT);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{SecureVault, SecurityConfig, VAULT_DISCRIMINATOR},
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+
+#[tokio::test]
+async fn initialize_vault_creates_pda_with_config() {
+    let config = test_config();
+    let mut test = setup(config.clone()).await;
+
+    let account = test.account(&test.vault.clone()).await.unwrap();
+    assert_eq!(account.owner, test.program_id);
+    assert_eq!(account.data.len(), SecureVault::MAX_SIZE);
+
+    let vault = test.vault_state().await;
+    let (_, bump) = builders::find_vault_address(&test.program_id, &test.admin.pubkey());
+    assert_eq!(vault.discriminator, VAULT_DISCRIMINATOR);
+    assert_eq!(vault.admin, test.admin.pubkey());
+    assert_eq!(vault.seed_authority, test.admin.pubkey());
+    assert_eq!(vault.bump, bump);
+    assert_eq!(vault.config, config);
+    assert!(vault.is_valid_state());
+}
+
+#[tokio::test]
+async fn initialize_vault_twice_fails() {
+    let mut test = setup(test_config()).await;
+
+    let instruction =
+        builders::initialize_vault(&test.program_id, &test.admin.pubkey(), test_config());
+    let result = test.process(&[instruction], &[]).await;
+    assert_vault_error(result, VaultError::AccountAlreadyInitialized);
+}
+
+#[tokio::test]
+async fn initialize_vault_rejects_invalid_config() {
+    let mut test = setup(test_config()).await;
+
+    let mut config = SecurityConfig::default();
+    config.max_daily_operations = 0;
+    let other_admin = Keypair::new();
+    let payer = test.context.payer.pubkey();
+    let fund = system_instruction::transfer(&payer, &other_admin.pubkey(), 1 << 30);
+    test.process(&[fund], &[]).await.unwrap();
+
+    let instruction = builders::initialize_vault(&test.program_id, &other_admin.pubkey(), config);
+    let result = test.process(&[instruction], &[&other_admin]).await;
+    assert_vault_error(result, VaultError::InvalidSecurityConfig);
+}
+
+#[tokio::test]
+async fn vault_copy_at_non_pda_address_is_rejected() {
+    let mut test = setup(test_config()).await;
+    let vault = test.vault;
+    let account = test.account(&vault).await.unwrap();
+
+    // Mismos datos y owner, pero en una dirección que no deriva de las semillas del vault
+    let fake_vault = Pubkey::new_unique();
+    test.context.set_account(&fake_vault, &account.into());
+
+    let admin = test.admin.pubkey();
+    let deposit = builders::deposit(&test.program_id, &admin, &fake_vault, &admin, 1_000_000_000);
+    let result = test.process(&[deposit], &[]).await;
+    assert_vault_error(result, VaultError::PDAAddressMismatch);
+}
+
+#[tokio::test]
+async fn vault_with_corrupted_state_is_rejected() {
+    let mut test = setup(test_config()).await;
+    let vault = test.vault;
+
+    let mut account = test.account(&vault).await.unwrap();
+    account.data[..8].copy_from_slice(b"NOTVAULT");
+    test.context.set_account(&vault, &account.clone().into());
+    let admin = test.admin.pubkey();
+    let deposit = builders::deposit(&test.program_id, &admin, &vault, &admin, 1_000_000_000);
+    let result = test.process(std::slice::from_ref(&deposit), &[]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+
+    // Estado inconsistente: más retirado hoy que el límite diario
+    let mut state = test.vault_state().await;
+    state.discriminator = VAULT_DISCRIMINATOR;
+    state.daily_withdrawal_limit = 1;
+    state.daily_withdrawn_amount = 2;
+    let data = borsh::to_vec(&state).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    test.context.set_account(&vault, &account.into());
+    let result = test.process(&[deposit], &[]).await;
+    assert_vault_error(result, VaultError::InvalidVaultState);
+}
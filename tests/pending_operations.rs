@@ -0,0 +1,321 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{OperationStatus, OperationType, PendingOperation},
+    utils::constants::OPERATION_EXPIRY_HOURS,
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const DEPOSIT: u64 = 5_000_000_000;
+const AMOUNT: u64 = 500_000_000;
+const DELAY: i64 = 3600;
+
+#[tokio::test]
+async fn time_locked_withdraw_end_to_end() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+    let create = builders::create_time_lock_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        OperationType::Withdraw,
+        AMOUNT,
+        &recipient,
+        DELAY,
+    );
+    test.process(&[create], &[]).await.unwrap();
+
+    let (pending, _) = builders::find_pending_operation_address(&test.program_id, &test.vault, 1);
+    let operation: PendingOperation = test.load(&pending).await;
+    assert_eq!(operation.id, 1);
+    assert_eq!(operation.operation_type, OperationType::Withdraw);
+    assert_eq!(operation.amount, AMOUNT);
+    assert_eq!(operation.target_account, recipient);
+    assert_eq!(operation.received_signatures, vec![admin]);
+    assert_eq!(test.vault_state().await.pending_operation_count, 1);
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.correlation_id, Some(1));
+    assert_eq!(audit.operation_status, Some(operation.status.clone()));
+
+    let execute = builders::execute_pending_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &recipient,
+        &test.audit_log,
+        &admin,
+        1,
+    );
+    let result = test.process(std::slice::from_ref(&execute), &[]).await;
+    assert_vault_error(result, VaultError::TimeLockNotElapsed);
+
+    test.advance_clock(DELAY).await;
+    test.process(&[execute], &[]).await.unwrap();
+
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, AMOUNT);
+    assert!(test.account(&pending).await.is_none());
+    assert_eq!(test.vault_state().await.total_balance, DEPOSIT - AMOUNT);
+    assert_eq!(
+        test.audit_entry().await.operation_status,
+        Some(OperationStatus::Executed)
+    );
+}
+
+#[tokio::test]
+async fn time_locked_operation_rejects_short_delay() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+
+    let admin = test.admin.pubkey();
+    let create = builders::create_time_lock_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        OperationType::Withdraw,
+        AMOUNT,
+        &Pubkey::new_unique(),
+        DELAY - 1,
+    );
+    let result = test.process(&[create], &[]).await;
+    assert_vault_error(result, VaultError::DelayTooShort);
+}
+
+#[tokio::test]
+async fn time_locked_operation_rejects_existing_pending_account() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+
+    let admin = test.admin.pubkey();
+    let (pending, _) = builders::find_pending_operation_address(&test.program_id, &test.vault, 1);
+    let payer = test.context.payer.pubkey();
+    let prefund = solana_sdk::system_instruction::transfer(&payer, &pending, AMOUNT);
+    test.process(&[prefund], &[]).await.unwrap();
+
+    let create = builders::create_time_lock_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        OperationType::Withdraw,
+        AMOUNT,
+        &Pubkey::new_unique(),
+        DELAY,
+    );
+    let result = test.process(&[create], &[]).await;
+    assert_vault_error(result, VaultError::AccountAlreadyInitialized);
+}
+
+#[tokio::test]
+async fn close_expired_operation_rejects_aliased_audit_account() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+
+    let admin = test.admin.pubkey();
+    let create = builders::create_time_lock_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        OperationType::Withdraw,
+        AMOUNT,
+        &Pubkey::new_unique(),
+        DELAY,
+    );
+    test.process(&[create], &[]).await.unwrap();
+    test.advance_clock(OPERATION_EXPIRY_HOURS * 3600 + 1).await;
+
+    // Un llamador cualquiera no puede hacer que el log sobrescriba el vault ni la operación
+    let caller = Keypair::new();
+    let (pending, _) = builders::find_pending_operation_address(&test.program_id, &test.vault, 1);
+    for audit_log in [test.vault, pending] {
+        let close = builders::close_expired_operation(
+            &test.program_id,
+            &caller.pubkey(),
+            &test.vault,
+            &audit_log,
+            &admin,
+            1,
+        );
+        let result = test.process(&[close], &[&caller]).await;
+        assert_vault_error(result, VaultError::InvalidAccount);
+    }
+    let vault_before = test.vault_state().await;
+
+    let close = builders::close_expired_operation(
+        &test.program_id,
+        &caller.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        &admin,
+        1,
+    );
+    test.process(&[close], &[&caller]).await.unwrap();
+
+    assert!(test.account(&pending).await.is_none());
+    let vault = test.vault_state().await;
+    assert!(vault.is_valid_state());
+    assert_eq!(vault.total_balance, vault_before.total_balance);
+    assert_eq!(vault.operation_count, vault_before.operation_count + 1);
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.correlation_id, Some(1));
+    assert_eq!(audit.operation_status, Some(OperationStatus::Expired));
+}
+
+#[tokio::test]
+async fn votes_reject_aliased_audit_account() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+
+    let admin = test.admin.pubkey();
+    let create = builders::create_time_lock_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        OperationType::Withdraw,
+        AMOUNT,
+        &Pubkey::new_unique(),
+        DELAY,
+    );
+    test.process(&[create], &[]).await.unwrap();
+
+    // Ni firmas ni rechazos pueden hacer que el log sobrescriba el vault o la operación
+    let signer = Keypair::new();
+    let (pending, _) = builders::find_pending_operation_address(&test.program_id, &test.vault, 1);
+    for audit_log in [test.vault, pending] {
+        let sign = builders::sign_pending_operation(
+            &test.program_id,
+            &signer.pubkey(),
+            &test.vault,
+            &audit_log,
+            1,
+        );
+        let result = test.process(&[sign], &[&signer]).await;
+        assert_vault_error(result, VaultError::InvalidAccount);
+
+        let reject = builders::reject_pending_operation(
+            &test.program_id,
+            &signer.pubkey(),
+            &test.vault,
+            &audit_log,
+            1,
+        );
+        let result = test.process(&[reject], &[&signer]).await;
+        assert_vault_error(result, VaultError::InvalidAccount);
+    }
+
+    let operation: PendingOperation = test.load(&pending).await;
+    assert_eq!(operation.received_signatures, vec![admin]);
+    assert!(test.vault_state().await.is_valid_state());
+}
+
+#[tokio::test]
+async fn rejected_operation_can_no_longer_execute() {
+    let mut config = test_config();
+    config.require_multisig_for_large_amounts = AMOUNT;
+    let mut test = setup(config).await;
+    test.deposit(DEPOSIT).await;
+    let admin = test.admin.pubkey();
+    let managers = [Keypair::new(), Keypair::new()];
+    for manager in &managers {
+        let add = builders::add_manager(
+            &test.program_id,
+            &admin,
+            &test.vault,
+            &test.audit_log,
+            &manager.pubkey(),
+        );
+        test.process(&[add], &[]).await.unwrap();
+    }
+
+    // Umbral 4 de un peso total de 5: el admin (3) necesita a un manager (1)
+    let recipient = Pubkey::new_unique();
+    let create = builders::create_time_lock_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        OperationType::Withdraw,
+        AMOUNT,
+        &recipient,
+        DELAY,
+    );
+    test.process(&[create], &[]).await.unwrap();
+
+    let outsider = Keypair::new();
+    let (program_id, vault, audit_log) = (test.program_id, test.vault, test.audit_log);
+    let reject = |signer: &Pubkey| {
+        [builders::reject_pending_operation(
+            &program_id,
+            signer,
+            &vault,
+            &audit_log,
+            1,
+        )]
+    };
+    let result = test
+        .process(&reject(&outsider.pubkey()), &[&outsider])
+        .await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    // Con un rechazo el umbral sigue alcanzable; con dos ya no
+    test.process(&reject(&managers[0].pubkey()), &[&managers[0]])
+        .await
+        .unwrap();
+    let (pending, _) = builders::find_pending_operation_address(&test.program_id, &test.vault, 1);
+    let operation: PendingOperation = test.load(&pending).await;
+    assert_eq!(operation.status, OperationStatus::Pending);
+    test.process(&reject(&managers[1].pubkey()), &[&managers[1]])
+        .await
+        .unwrap();
+    let operation: PendingOperation = test.load(&pending).await;
+    assert_eq!(operation.status, OperationStatus::Rejected);
+    assert_eq!(operation.rejected_weight, 2);
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::Withdraw);
+    assert_eq!(audit.performer, managers[1].pubkey());
+    assert_eq!(audit.correlation_id, Some(1));
+    assert_eq!(audit.operation_status, Some(OperationStatus::Rejected));
+
+    let sign = builders::sign_pending_operation(
+        &test.program_id,
+        &managers[0].pubkey(),
+        &test.vault,
+        &test.audit_log,
+        1,
+    );
+    let result = test.process(&[sign], &[&managers[0]]).await;
+    assert_vault_error(result, VaultError::OperationAlreadyExecuted);
+
+    test.advance_clock(DELAY).await;
+    let execute = builders::execute_pending_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &recipient,
+        &test.audit_log,
+        &admin,
+        1,
+    );
+    let result = test.process(&[execute], &[]).await;
+    assert_vault_error(result, VaultError::OperationAlreadyExecuted);
+    assert!(test.account(&recipient).await.is_none());
+    assert_eq!(test.vault_state().await.total_balance, DEPOSIT);
+}
@@ -0,0 +1,319 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{Asset, OperationType, PendingOperation, RecipientPolicy},
+};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const DEPOSIT: u64 = 5_000_000_000;
+const AMOUNT: u64 = 100_000_000;
+const UNLISTED_DELAY: i64 = 7200;
+const TOKEN_DEPOSIT: u64 = 10_000;
+const TOKEN_AMOUNT: u64 = 500;
+
+/// Vault con `DEPOSIT` lamports y la política de destinatarios dada
+async fn setup_policy(policy: RecipientPolicy) -> VaultTest {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+    let instruction = builders::set_recipient_policy(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        policy,
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+    test
+}
+
+fn add_entry(test: &VaultTest, signer: &Pubkey, address: &Pubkey) -> [Instruction; 1] {
+    [builders::add_address_book_entry(
+        &test.program_id,
+        signer,
+        &test.vault,
+        &test.audit_log,
+        address,
+        "treasury".to_string(),
+    )]
+}
+
+fn remove_entry(test: &VaultTest, signer: &Pubkey, address: &Pubkey) -> [Instruction; 1] {
+    [builders::remove_address_book_entry(
+        &test.program_id,
+        signer,
+        &test.vault,
+        &test.audit_log,
+        address,
+    )]
+}
+
+fn withdraw(test: &VaultTest, recipient: &Pubkey) -> Instruction {
+    builders::withdraw(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        recipient,
+        &test.audit_log,
+        AMOUNT,
+    )
+}
+
+/// Vault con `TOKEN_DEPOSIT` tokens de un mint registrado
+async fn setup_token_policy(policy: RecipientPolicy) -> (VaultTest, Pubkey) {
+    let mut test = setup_policy(policy).await;
+    let admin = test.admin.pubkey();
+    let vault = test.vault;
+    let mint = test.create_mint(6).await;
+    let limits = builders::set_asset_limits(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        Asset::Token(mint),
+        TOKEN_DEPOSIT,
+        TOKEN_DEPOSIT,
+    );
+    test.process(&[limits], &[]).await.unwrap();
+
+    let source = test.create_token_account(&admin, &mint).await;
+    test.create_token_account(&vault, &mint).await;
+    test.mint_to(&mint, &source, TOKEN_DEPOSIT).await;
+    let deposit = builders::deposit_token(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &source,
+        &mint,
+        TOKEN_DEPOSIT,
+    );
+    test.process(&[deposit], &[]).await.unwrap();
+    (test, mint)
+}
+
+fn withdraw_token(test: &VaultTest, mint: &Pubkey, recipient: &Pubkey) -> Instruction {
+    builders::withdraw_token(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        recipient,
+        mint,
+        &test.audit_log,
+        TOKEN_AMOUNT,
+    )
+}
+
+#[tokio::test]
+async fn withdrawals_follow_listed_and_removed_entries() {
+    let mut test = setup_policy(RecipientPolicy::RejectUnlisted).await;
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    let result = test.process(&[withdraw(&test, &recipient)], &[]).await;
+    assert_vault_error(result, VaultError::RecipientNotAllowlisted);
+
+    test.process(&add_entry(&test, &admin, &recipient), &[])
+        .await
+        .unwrap();
+    test.process(&[withdraw(&test, &recipient)], &[])
+        .await
+        .unwrap();
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, AMOUNT);
+
+    test.process(&remove_entry(&test, &admin, &recipient), &[])
+        .await
+        .unwrap();
+    test.advance_clock(1).await;
+    let result = test.process(&[withdraw(&test, &recipient)], &[]).await;
+    assert_vault_error(result, VaultError::RecipientNotAllowlisted);
+}
+
+#[tokio::test]
+async fn address_book_changes_are_admin_gated_and_audited() {
+    let mut test = setup_policy(RecipientPolicy::RejectUnlisted).await;
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    let outsider = Keypair::new();
+    let instruction = add_entry(&test, &outsider.pubkey(), &recipient);
+    let result = test.process(&instruction, &[&outsider]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+    let instruction = builders::set_recipient_policy(
+        &test.program_id,
+        &outsider.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        RecipientPolicy::Disabled,
+    );
+    let result = test.process(&[instruction], &[&outsider]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    test.process(&add_entry(&test, &admin, &recipient), &[])
+        .await
+        .unwrap();
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::AddAddressBookEntry);
+    assert_eq!(audit.performer, admin);
+    assert_eq!(audit.target_account, Some(recipient));
+
+    let instruction = remove_entry(&test, &outsider.pubkey(), &recipient);
+    let result = test.process(&instruction, &[&outsider]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    test.process(&remove_entry(&test, &admin, &recipient), &[])
+        .await
+        .unwrap();
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::RemoveAddressBookEntry);
+    assert_eq!(audit.target_account, Some(recipient));
+}
+
+#[tokio::test]
+async fn unlisted_native_withdrawal_is_time_locked() {
+    let mut test = setup_policy(RecipientPolicy::TimeLockUnlisted {
+        min_delay_seconds: UNLISTED_DELAY,
+    })
+    .await;
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    // Sin la cuenta de operación pendiente no hay forma de programarlo
+    let result = test.process(&[withdraw(&test, &recipient)], &[]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+
+    let instruction = builders::with_pending_operation(
+        withdraw(&test, &recipient),
+        &test.program_id,
+        &test.vault,
+        1,
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+    assert!(test.account(&recipient).await.is_none());
+    let (pending, _) = builders::find_pending_operation_address(&test.program_id, &test.vault, 1);
+    let operation: PendingOperation = test.load(&pending).await;
+    assert_eq!(operation.operation_type, OperationType::Withdraw);
+    assert_eq!(operation.target_account, recipient);
+    assert_eq!(
+        operation.scheduled_execution - operation.created_at,
+        UNLISTED_DELAY
+    );
+    assert_eq!(test.audit_entry().await.correlation_id, Some(1));
+
+    let execute = builders::execute_pending_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &recipient,
+        &test.audit_log,
+        &admin,
+        1,
+    );
+    let result = test.process(std::slice::from_ref(&execute), &[]).await;
+    assert_vault_error(result, VaultError::TimeLockNotElapsed);
+
+    test.advance_clock(UNLISTED_DELAY).await;
+    test.process(&[execute], &[]).await.unwrap();
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, AMOUNT);
+}
+
+#[tokio::test]
+async fn token_withdrawals_follow_the_address_book() {
+    let (mut test, mint) = setup_token_policy(RecipientPolicy::RejectUnlisted).await;
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+    let destination = test.create_token_account(&recipient, &mint).await;
+
+    let result = test
+        .process(&[withdraw_token(&test, &mint, &recipient)], &[])
+        .await;
+    assert_vault_error(result, VaultError::RecipientNotAllowlisted);
+
+    test.process(&add_entry(&test, &admin, &recipient), &[])
+        .await
+        .unwrap();
+    test.process(&[withdraw_token(&test, &mint, &recipient)], &[])
+        .await
+        .unwrap();
+    assert_eq!(test.token_balance(&destination).await, TOKEN_AMOUNT);
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::WithdrawToken);
+    assert_eq!(audit.target_account, Some(recipient));
+
+    test.process(&remove_entry(&test, &admin, &recipient), &[])
+        .await
+        .unwrap();
+    test.advance_clock(1).await;
+    let result = test
+        .process(&[withdraw_token(&test, &mint, &recipient)], &[])
+        .await;
+    assert_vault_error(result, VaultError::RecipientNotAllowlisted);
+}
+
+#[tokio::test]
+async fn unlisted_token_withdrawal_is_time_locked() {
+    let (mut test, mint) = setup_token_policy(RecipientPolicy::TimeLockUnlisted {
+        min_delay_seconds: UNLISTED_DELAY,
+    })
+    .await;
+    let admin = test.admin.pubkey();
+    let recipient = Pubkey::new_unique();
+    let destination = test.create_token_account(&recipient, &mint).await;
+
+    let instruction = builders::with_pending_operation(
+        withdraw_token(&test, &mint, &recipient),
+        &test.program_id,
+        &test.vault,
+        1,
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+    assert_eq!(test.token_balance(&destination).await, 0);
+    let (pending, _) = builders::find_pending_operation_address(&test.program_id, &test.vault, 1);
+    let operation: PendingOperation = test.load(&pending).await;
+    assert_eq!(operation.operation_type, OperationType::WithdrawToken);
+    assert_eq!(
+        operation.target_account,
+        get_associated_token_address(&recipient, &mint)
+    );
+    assert_eq!(
+        operation.scheduled_execution - operation.created_at,
+        UNLISTED_DELAY
+    );
+
+    // Programarlo a mano exige al menos el delay de la política
+    let short = builders::create_token_withdraw_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        2,
+        &recipient,
+        &mint,
+        TOKEN_AMOUNT,
+        UNLISTED_DELAY - 1,
+    );
+    assert_vault_error(test.process(&[short], &[]).await, VaultError::DelayTooShort);
+
+    let execute = builders::execute_token_withdraw_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        &admin,
+        1,
+        &recipient,
+        &mint,
+    );
+    let result = test.process(std::slice::from_ref(&execute), &[]).await;
+    assert_vault_error(result, VaultError::TimeLockNotElapsed);
+
+    test.advance_clock(UNLISTED_DELAY).await;
+    test.process(&[execute], &[]).await.unwrap();
+    assert_eq!(test.token_balance(&destination).await, TOKEN_AMOUNT);
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{OperationStatus, OperationType, PendingOperation, RecurringPayment},
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const DEPOSIT: u64 = 5_000_000_000;
+const AMOUNT: u64 = 100_000_000;
+const INTERVAL: i64 = 3600;
+
+/// Crear y aprobar un pago recurrente de `AMOUNT` cada `INTERVAL` segundos
+async fn approved_payment(test: &mut common::VaultTest, recipient: &Pubkey) -> Pubkey {
+    let admin = test.admin.pubkey();
+    let first_payment_at = test.clock().await.unix_timestamp + INTERVAL;
+    let create = builders::create_recurring_payment(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        1,
+        recipient,
+        AMOUNT,
+        INTERVAL,
+        2,
+        first_payment_at,
+        0,
+    );
+    test.process(&[create], &[]).await.unwrap();
+
+    let (payment, _) = builders::find_recurring_payment_address(&test.program_id, &test.vault, 1);
+    let (pending, _) = builders::find_pending_operation_address(&test.program_id, &test.vault, 1);
+    let state: RecurringPayment = test.load(&payment).await;
+    assert_eq!(state.status, OperationStatus::Pending);
+    let operation: PendingOperation = test.load(&pending).await;
+    assert_eq!(operation.operation_type, OperationType::RecurringPayment);
+    assert_eq!(operation.target_account, payment);
+
+    let approve = builders::execute_pending_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &payment,
+        &test.audit_log,
+        &admin,
+        1,
+    );
+    test.process(&[approve], &[]).await.unwrap();
+    let state: RecurringPayment = test.load(&payment).await;
+    assert_eq!(state.status, OperationStatus::Approved);
+    payment
+}
+
+#[tokio::test]
+async fn recurring_payment_is_created_approved_and_executed() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+    let recipient = Pubkey::new_unique();
+    let payment = approved_payment(&mut test, &recipient).await;
+
+    let caller = Keypair::new();
+    let execute = builders::execute_recurring_payment(
+        &test.program_id,
+        &caller.pubkey(),
+        &test.vault,
+        &recipient,
+        &test.audit_log,
+        1,
+    );
+    let result = test
+        .process(std::slice::from_ref(&execute), &[&caller])
+        .await;
+    assert_vault_error(result, VaultError::TimeLockNotElapsed);
+
+    test.advance_clock(INTERVAL).await;
+    test.process(&[execute], &[&caller]).await.unwrap();
+
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, AMOUNT);
+    let state: RecurringPayment = test.load(&payment).await;
+    assert_eq!(state.payments_made, 1);
+    assert_eq!(test.vault_state().await.total_balance, DEPOSIT - AMOUNT);
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::RecurringPayment);
+    assert_eq!(audit.correlation_id, Some(1));
+}
+
+#[tokio::test]
+async fn execute_recurring_payment_rejects_aliased_audit_account() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+    let recipient = Pubkey::new_unique();
+    let payment = approved_payment(&mut test, &recipient).await;
+    test.advance_clock(INTERVAL).await;
+
+    // Un llamador cualquiera no puede hacer que el log sobrescriba el vault ni el pago
+    let caller = Keypair::new();
+    for audit_log in [test.vault, payment] {
+        let execute = builders::execute_recurring_payment(
+            &test.program_id,
+            &caller.pubkey(),
+            &test.vault,
+            &recipient,
+            &audit_log,
+            1,
+        );
+        let result = test.process(&[execute], &[&caller]).await;
+        assert_vault_error(result, VaultError::InvalidAccount);
+    }
+
+    let state: RecurringPayment = test.load(&payment).await;
+    assert_eq!(state.payments_made, 0);
+    assert!(test.vault_state().await.is_valid_state());
+}
@@ -0,0 +1,194 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{OperationType, PauseReason, TimedPause, TIMED_PAUSE_COOLDOWN_SECONDS},
+};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const DEPOSIT: u64 = 2_000_000_000;
+const AMOUNT: u64 = 100_000_000;
+const DURATION: i64 = 3600;
+
+fn timed_pause(test: &VaultTest, reason: PauseReason) -> Instruction {
+    builders::timed_pause(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        reason,
+        DURATION,
+    )
+}
+
+fn withdraw(test: &VaultTest, recipient: &Pubkey) -> Instruction {
+    builders::withdraw(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        recipient,
+        &test.audit_log,
+        AMOUNT,
+    )
+}
+
+#[tokio::test]
+async fn timed_pause_audit_records_reason_and_expiry() {
+    let mut test = setup(test_config()).await;
+    let now = test.clock().await.unix_timestamp;
+
+    let instruction = timed_pause(&test, PauseReason::SuspectedKeyCompromise);
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let expected = TimedPause {
+        paused_by: test.admin.pubkey(),
+        reason: PauseReason::SuspectedKeyCompromise,
+        paused_at: now,
+        expires_at: now + DURATION,
+    };
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::EmergencyPause);
+    assert_eq!(audit.amount, None);
+    assert_eq!(audit.pause, Some(expected.clone()));
+    assert_eq!(test.vault_state().await.timed_pause, Some(expected));
+
+    let admin = test.admin.pubkey();
+    let extend =
+        builders::extend_pause(&test.program_id, &admin, &test.vault, &test.audit_log, 600);
+    test.process(&[extend], &[]).await.unwrap();
+    let pause = test.audit_entry().await.pause.unwrap();
+    assert_eq!(pause.reason, PauseReason::SuspectedKeyCompromise);
+    assert_eq!(pause.expires_at, now + DURATION + 600);
+}
+
+#[tokio::test]
+async fn timed_pause_resumes_automatically_at_expiry() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+    let recipient = Pubkey::new_unique();
+
+    let instruction = timed_pause(&test, PauseReason::Maintenance);
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let instruction = withdraw(&test, &recipient);
+    let result = test.process(std::slice::from_ref(&instruction), &[]).await;
+    assert_vault_error(result, VaultError::VaultPaused);
+    test.advance_clock(DURATION - 1).await;
+    let result = test.process(std::slice::from_ref(&instruction), &[]).await;
+    assert_vault_error(result, VaultError::VaultPaused);
+
+    test.advance_clock(1).await;
+    test.process(&[instruction], &[]).await.unwrap();
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, AMOUNT);
+    assert!(!test.vault_state().await.is_paused);
+}
+
+#[tokio::test]
+async fn new_timed_pause_waits_for_cooldown() {
+    let mut test = setup(test_config()).await;
+
+    let instruction = timed_pause(&test, PauseReason::ExternalIncident);
+    test.process(&[instruction], &[]).await.unwrap();
+
+    // Pausa vigente: se rechaza por estar pausado
+    let instruction = timed_pause(&test, PauseReason::Other);
+    let result = test.process(std::slice::from_ref(&instruction), &[]).await;
+    assert_vault_error(result, VaultError::VaultPaused);
+
+    test.advance_clock(DURATION).await;
+    let result = test.process(std::slice::from_ref(&instruction), &[]).await;
+    assert_vault_error(result, VaultError::PauseCooldownActive);
+
+    test.advance_clock(TIMED_PAUSE_COOLDOWN_SECONDS - 1).await;
+    let result = test.process(std::slice::from_ref(&instruction), &[]).await;
+    assert_vault_error(result, VaultError::PauseCooldownActive);
+
+    test.advance_clock(1).await;
+    test.process(&[instruction], &[]).await.unwrap();
+    let pause = test.audit_entry().await.pause.unwrap();
+    assert_eq!(pause.reason, PauseReason::Other);
+}
+
+#[tokio::test]
+async fn timed_pause_rejects_aliased_audit_account() {
+    let mut test = setup(test_config()).await;
+
+    let mut instruction = timed_pause(&test, PauseReason::Other);
+    instruction.accounts[2].pubkey = test.vault;
+    let result = test.process(&[instruction], &[]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+    assert!(test.vault_state().await.timed_pause.is_none());
+
+    let instruction = timed_pause(&test, PauseReason::Other);
+    test.process(&[instruction], &[]).await.unwrap();
+    let admin = test.admin.pubkey();
+    let extend = builders::extend_pause(&test.program_id, &admin, &test.vault, &test.vault, 600);
+    let result = test.process(&[extend], &[]).await;
+    assert_vault_error(result, VaultError::InvalidAccount);
+
+    let vault = test.vault_state().await;
+    assert!(vault.is_valid_state());
+    let pause = vault.timed_pause.unwrap();
+    assert_eq!(pause.expires_at, pause.paused_at + DURATION);
+}
+
+/// Crear o actualizar el rol personalizado "pauser" con los permisos dados
+fn upsert_pauser_role(test: &VaultTest, operations: &[OperationType]) -> Instruction {
+    builders::upsert_role(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        "pauser".to_string(),
+        OperationType::permissions_of(operations),
+    )
+}
+
+#[tokio::test]
+async fn indefinite_pause_and_extension_follow_the_permission_table() {
+    let mut test = setup(test_config()).await;
+    let pauser = Keypair::new();
+    let upsert = upsert_pauser_role(&test, &[OperationType::EmergencyPause]);
+    let assign = builders::assign_role(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        &pauser.pubkey(),
+        "pauser".to_string(),
+    );
+    test.process(&[upsert, assign], &[]).await.unwrap();
+
+    // `EmergencyPause` solo habilita la pausa temporal
+    let (program_id, vault, audit_log) = (test.program_id, test.vault, test.audit_log);
+    let pause = builders::emergency_pause(&program_id, &pauser.pubkey(), &vault, &audit_log);
+    let result = test.process(std::slice::from_ref(&pause), &[&pauser]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+    let mut timed = timed_pause(&test, PauseReason::Maintenance);
+    timed.accounts[0].pubkey = pauser.pubkey();
+    test.process(&[timed], &[&pauser]).await.unwrap();
+    let extend = builders::extend_pause(&program_id, &pauser.pubkey(), &vault, &audit_log, 600);
+    let result = test
+        .process(std::slice::from_ref(&extend), &[&pauser])
+        .await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    let upsert = upsert_pauser_role(
+        &test,
+        &[
+            OperationType::EmergencyPause,
+            OperationType::IndefinitePause,
+            OperationType::ExtendPause,
+        ],
+    );
+    test.process(&[upsert], &[]).await.unwrap();
+    test.process(&[extend], &[&pauser]).await.unwrap();
+    test.process(&[pause], &[&pauser]).await.unwrap();
+    assert!(test.vault_state().await.is_paused);
+}
@@ -0,0 +1,255 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config, VaultTest};
+use secure_vault::{
+    error::VaultError,
+    instruction::builders,
+    state::{Asset, OperationStatus, OperationType, MAX_TOKEN_MINTS},
+};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const DEPOSIT: u64 = 10_000;
+const DAILY_LIMIT: u64 = 5_000;
+const MULTISIG_THRESHOLD: u64 = 1_000;
+const DELAY: i64 = 3600;
+
+/// Vault con `DEPOSIT` tokens de un mint registrado y una ATA destino para `recipient`
+async fn setup_token_vault() -> (VaultTest, Pubkey, Pubkey) {
+    let mut test = setup(test_config()).await;
+    let mint = test.create_mint(6).await;
+    set_limits(&mut test, &mint).await;
+    let deposit = deposit(&mut test, &mint).await;
+    test.process(&[deposit], &[]).await.unwrap();
+
+    let recipient = Pubkey::new_unique();
+    test.create_token_account(&recipient, &mint).await;
+    (test, mint, recipient)
+}
+
+/// Depósito de `DEPOSIT` tokens recién acuñados desde una cuenta del admin
+async fn deposit(test: &mut VaultTest, mint: &Pubkey) -> Instruction {
+    let admin = test.admin.pubkey();
+    let vault = test.vault;
+    let source = test.create_token_account(&admin, mint).await;
+    test.create_token_account(&vault, mint).await;
+    test.mint_to(mint, &source, DEPOSIT).await;
+    builders::deposit_token(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &source,
+        mint,
+        DEPOSIT,
+    )
+}
+
+fn withdraw(test: &VaultTest, mint: &Pubkey, recipient: &Pubkey, amount: u64) -> [Instruction; 1] {
+    [builders::withdraw_token(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        recipient,
+        mint,
+        &test.audit_log,
+        amount,
+    )]
+}
+
+async fn set_limits(test: &mut VaultTest, mint: &Pubkey) {
+    let instruction = builders::set_asset_limits(
+        &test.program_id,
+        &test.admin.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        Asset::Token(*mint),
+        DAILY_LIMIT,
+        MULTISIG_THRESHOLD,
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn deposits_require_a_registered_mint() {
+    let mut test = setup(test_config()).await;
+    let mint = test.create_mint(6).await;
+
+    // Un depósito no puede registrar el mint ni ocupar una de sus entradas
+    let instruction = deposit(&mut test, &mint).await;
+    let result = test.process(std::slice::from_ref(&instruction), &[]).await;
+    assert_vault_error(result, VaultError::InvalidMintAccount);
+    assert!(test.vault_state().await.token_balances.is_empty());
+
+    set_limits(&mut test, &mint).await;
+    test.process(&[instruction], &[]).await.unwrap();
+    let token = test
+        .vault_state()
+        .await
+        .get_token_balance(&mint)
+        .cloned()
+        .unwrap();
+    assert_eq!(token.balance, DEPOSIT);
+    assert_eq!(token.daily_withdrawal_limit, DAILY_LIMIT);
+    assert_eq!(token.multisig_threshold, MULTISIG_THRESHOLD);
+
+    let recipient = Pubkey::new_unique();
+    test.create_token_account(&recipient, &mint).await;
+    test.process(&withdraw(&test, &mint, &recipient, 500), &[])
+        .await
+        .unwrap();
+    let destination = spl_associated_token_account::get_associated_token_address(&recipient, &mint);
+    assert_eq!(test.token_balance(&destination).await, 500);
+}
+
+#[tokio::test]
+async fn admin_removes_empty_token_entries() {
+    let (mut test, mint, _) = setup_token_vault().await;
+    let admin = test.admin.pubkey();
+    let mut empty_mints = Vec::new();
+    for _ in 1..MAX_TOKEN_MINTS {
+        let empty = Pubkey::new_unique();
+        set_limits(&mut test, &empty).await;
+        empty_mints.push(empty);
+    }
+    let extra = Pubkey::new_unique();
+    let register = builders::set_asset_limits(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        Asset::Token(extra),
+        DAILY_LIMIT,
+        MULTISIG_THRESHOLD,
+    );
+    let result = test.process(std::slice::from_ref(&register), &[]).await;
+    assert_vault_error(result, VaultError::InvalidMintAccount);
+
+    let (program_id, vault, audit_log) = (test.program_id, test.vault, test.audit_log);
+    let remove = |signer: &Pubkey, mint: &Pubkey| {
+        builders::remove_token_asset(&program_id, signer, &vault, &audit_log, mint)
+    };
+    let outsider = Keypair::new();
+    let instruction = remove(&outsider.pubkey(), &empty_mints[0]);
+    let result = test.process(&[instruction], &[&outsider]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+
+    // Un mint con balance no se puede dar de baja
+    let instruction = remove(&admin, &mint);
+    let result = test.process(&[instruction], &[]).await;
+    assert_vault_error(result, VaultError::InvalidMintAccount);
+
+    let instruction = remove(&admin, &empty_mints[0]);
+    test.process(&[instruction], &[]).await.unwrap();
+    let vault = test.vault_state().await;
+    assert_eq!(vault.token_balances.len(), MAX_TOKEN_MINTS - 1);
+    assert!(vault.get_token_balance(&empty_mints[0]).is_none());
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.operation_type, OperationType::UpdateConfig);
+    assert_eq!(audit.target_account, Some(empty_mints[0]));
+
+    test.process(&[register], &[]).await.unwrap();
+    assert!(test.vault_state().await.get_token_balance(&extra).is_some());
+}
+
+#[tokio::test]
+async fn large_token_withdrawal_goes_through_pending_operation() {
+    let (mut test, mint, recipient) = setup_token_vault().await;
+
+    let amount = MULTISIG_THRESHOLD * 2;
+    let result = test
+        .process(&withdraw(&test, &mint, &recipient, amount), &[])
+        .await;
+    assert_vault_error(result, VaultError::InsufficientSignatures);
+
+    let admin = test.admin.pubkey();
+    let short = builders::create_token_withdraw_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        &recipient,
+        &mint,
+        amount,
+        DELAY - 1,
+    );
+    assert_vault_error(test.process(&[short], &[]).await, VaultError::DelayTooShort);
+
+    let create = builders::create_token_withdraw_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        &recipient,
+        &mint,
+        amount,
+        DELAY,
+    );
+    test.process(&[create], &[]).await.unwrap();
+
+    let execute = builders::execute_token_withdraw_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        &admin,
+        1,
+        &recipient,
+        &mint,
+    );
+    let result = test.process(std::slice::from_ref(&execute), &[]).await;
+    assert_vault_error(result, VaultError::TimeLockNotElapsed);
+
+    test.advance_clock(DELAY).await;
+    test.process(&[execute], &[]).await.unwrap();
+
+    let destination = spl_associated_token_account::get_associated_token_address(&recipient, &mint);
+    assert_eq!(test.token_balance(&destination).await, amount);
+    let vault = test.vault_state().await;
+    let token = vault.get_token_balance(&mint).unwrap();
+    assert_eq!(token.balance, DEPOSIT - amount);
+    assert_eq!(token.daily_withdrawn_amount, amount);
+    assert_eq!(
+        test.audit_entry().await.operation_status,
+        Some(OperationStatus::Executed)
+    );
+}
+
+#[tokio::test]
+async fn pending_token_withdrawal_respects_daily_limit() {
+    let (mut test, mint, recipient) = setup_token_vault().await;
+
+    let admin = test.admin.pubkey();
+    let create = builders::create_token_withdraw_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        &recipient,
+        &mint,
+        DAILY_LIMIT + 1,
+        DELAY,
+    );
+    test.process(&[create], &[]).await.unwrap();
+    test.advance_clock(DELAY).await;
+
+    let execute = builders::execute_token_withdraw_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        &admin,
+        1,
+        &recipient,
+        &mint,
+    );
+    assert_vault_error(
+        test.process(&[execute], &[]).await,
+        VaultError::DailyLimitExceeded,
+    );
+}
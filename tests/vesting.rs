@@ -0,0 +1,131 @@
+mod common;
+
+use common::{assert_vault_error, setup, test_config};
+use secure_vault::{error::VaultError, instruction::builders, state::VestingSchedule};
+use solana_sdk::signature::{Keypair, Signer};
+
+const DEPOSIT: u64 = 5_000_000_000;
+const TOTAL: u64 = 2_000_000_000;
+
+#[tokio::test]
+async fn create_and_claim_vesting_schedule() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+
+    let admin = test.admin.pubkey();
+    let beneficiary = Keypair::new();
+    let now = test.clock().await.unix_timestamp;
+    let create = builders::create_vesting_schedule(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        &beneficiary.pubkey(),
+        TOTAL,
+        now,
+        now + 100,
+        now + 1000,
+    );
+    test.process(&[create], &[]).await.unwrap();
+
+    let (schedule_key, _) =
+        builders::find_vesting_schedule_address(&test.program_id, &test.vault, 1);
+    let schedule: VestingSchedule = test.load(&schedule_key).await;
+    assert_eq!(schedule.id, 1);
+    assert_eq!(schedule.beneficiary, beneficiary.pubkey());
+    assert_eq!(schedule.total_amount, TOTAL);
+    let vault = test.vault_state().await;
+    assert_eq!(vault.vesting_schedule_count, 1);
+    assert_eq!(vault.vesting_reserved, TOTAL);
+    assert_eq!(vault.available_native_balance(), DEPOSIT - TOTAL);
+
+    let claim = builders::claim_vested(
+        &test.program_id,
+        &beneficiary.pubkey(),
+        &test.vault,
+        &test.audit_log,
+        1,
+    );
+    let result = test
+        .process(std::slice::from_ref(&claim), &[&beneficiary])
+        .await;
+    assert_vault_error(result, VaultError::AmountTooSmall);
+
+    test.advance_clock(500).await;
+    test.process(&[claim], &[&beneficiary]).await.unwrap();
+
+    let claimed = test.account(&beneficiary.pubkey()).await.unwrap().lamports;
+    assert_eq!(claimed, TOTAL / 2);
+    let schedule: VestingSchedule = test.load(&schedule_key).await;
+    assert_eq!(schedule.claimed_amount, TOTAL / 2);
+    let vault = test.vault_state().await;
+    assert_eq!(vault.vesting_reserved, TOTAL / 2);
+    assert_eq!(vault.total_balance, DEPOSIT - TOTAL / 2);
+}
+
+#[tokio::test]
+async fn vesting_schedule_cannot_exceed_available_balance() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+
+    let admin = test.admin.pubkey();
+    let now = test.clock().await.unix_timestamp;
+    let create = builders::create_vesting_schedule(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        &Keypair::new().pubkey(),
+        DEPOSIT + 1,
+        now,
+        now,
+        now + 1000,
+    );
+    let result = test.process(&[create], &[]).await;
+    assert_vault_error(result, VaultError::InsufficientBalance);
+}
+
+#[tokio::test]
+async fn claim_vested_rejects_aliased_audit_account() {
+    let mut test = setup(test_config()).await;
+    test.deposit(DEPOSIT).await;
+
+    let admin = test.admin.pubkey();
+    let beneficiary = Keypair::new();
+    let now = test.clock().await.unix_timestamp;
+    let create = builders::create_vesting_schedule(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        &beneficiary.pubkey(),
+        TOTAL,
+        now,
+        now,
+        now + 1000,
+    );
+    test.process(&[create], &[]).await.unwrap();
+    test.advance_clock(500).await;
+
+    // El beneficiario no puede hacer que el log sobrescriba el vault ni el calendario
+    let (schedule_key, _) =
+        builders::find_vesting_schedule_address(&test.program_id, &test.vault, 1);
+    for audit_log in [test.vault, schedule_key] {
+        let claim = builders::claim_vested(
+            &test.program_id,
+            &beneficiary.pubkey(),
+            &test.vault,
+            &audit_log,
+            1,
+        );
+        let result = test.process(&[claim], &[&beneficiary]).await;
+        assert_vault_error(result, VaultError::InvalidAccount);
+    }
+
+    let schedule: VestingSchedule = test.load(&schedule_key).await;
+    assert_eq!(schedule.claimed_amount, 0);
+    assert!(test.vault_state().await.is_valid_state());
+}
@@ -0,0 +1,417 @@
+mod common;
+
+use borsh::BorshSerialize;
+use common::{
+    assert_vault_error, audit_account, funded_account, setup, test_config, VaultTest,
+    INITIAL_LAMPORTS,
+};
+use secure_vault::{
+    error::VaultError,
+    instruction::{
+        builders, tag, BatchOperationV1, SecurityConfigV1, VaultInstruction, INSTRUCTION_VERSION,
+        INSTRUCTION_VERSION_V1,
+    },
+    process_instruction,
+    state::{
+        AuditLog, DelayTier, OperationStatus, OperationType, PendingOperation, SecurityConfig,
+    },
+    utils::constants::OPERATION_EXPIRY_HOURS,
+};
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+
+const DEPOSIT: u64 = 5_000_000_000;
+const AMOUNT: u64 = 500_000_000;
+const DELAY: i64 = 3600;
+
+fn v1_config() -> SecurityConfigV1 {
+    SecurityConfigV1 {
+        max_daily_operations: 42,
+        min_time_between_operations: 0,
+        require_multisig_for_large_amounts: 7_000_000_000,
+        auto_pause_on_suspicious_activity: false,
+        max_managers: 3,
+        withdrawal_delay_seconds: 120,
+    }
+}
+
+fn tiered_config() -> SecurityConfig {
+    let mut config = test_config();
+    config.withdrawal_delay_tiers = vec![DelayTier {
+        min_amount: 1_000_000_000,
+        delay_seconds: 600,
+        required_weight: 0,
+    }];
+    config.hourly_withdrawal_limit = 2_000_000_000;
+    config
+}
+
+/// Reemplazar los datos de una instrucción por `[version][tag][payload]`
+fn with_data<T: BorshSerialize>(
+    mut instruction: Instruction,
+    version: u8,
+    variant: u8,
+    payload: &T,
+) -> Instruction {
+    instruction.data = vec![version, variant];
+    instruction.data.extend(payload.try_to_vec().unwrap());
+    instruction
+}
+
+fn assert_v1_fields(config: &SecurityConfig, expected: &SecurityConfigV1) {
+    assert_eq!(config.max_daily_operations, expected.max_daily_operations);
+    assert_eq!(
+        config.min_time_between_operations,
+        expected.min_time_between_operations
+    );
+    assert_eq!(
+        config.require_multisig_for_large_amounts,
+        expected.require_multisig_for_large_amounts
+    );
+    assert_eq!(
+        config.auto_pause_on_suspicious_activity,
+        expected.auto_pause_on_suspicious_activity
+    );
+    assert_eq!(config.max_managers, expected.max_managers);
+    assert_eq!(
+        config.withdrawal_delay_seconds,
+        expected.withdrawal_delay_seconds
+    );
+}
+
+#[test]
+fn pack_uses_current_version_and_v1_for_legacy_config_update() {
+    let current = VaultInstruction::UpdateSecurityConfig {
+        new_config: test_config(),
+    };
+    assert_eq!(
+        current.pack()[..2],
+        [INSTRUCTION_VERSION, tag::UPDATE_SECURITY_CONFIG]
+    );
+
+    let legacy = VaultInstruction::UpdateSecurityConfigV1 {
+        new_config: v1_config(),
+    };
+    let data = legacy.pack();
+    assert_eq!(
+        data[..2],
+        [INSTRUCTION_VERSION_V1, tag::UPDATE_SECURITY_CONFIG]
+    );
+    assert!(matches!(
+        VaultInstruction::unpack(&data).unwrap(),
+        VaultInstruction::UpdateSecurityConfigV1 { new_config } if new_config == v1_config()
+    ));
+}
+
+#[test]
+fn unpack_rejects_unknown_versions_and_v2_only_tags_in_v1() {
+    let data = VaultInstruction::Resume.pack();
+    for version in [0, INSTRUCTION_VERSION + 1] {
+        let mut data = data.clone();
+        data[0] = version;
+        assert!(VaultInstruction::unpack(&data).is_err());
+    }
+
+    // Tag posterior a v1
+    let mut data = VaultInstruction::RemoveAddressBookEntry {
+        address: Pubkey::new_unique(),
+    }
+    .pack();
+    data[0] = INSTRUCTION_VERSION_V1;
+    assert!(VaultInstruction::unpack(&data).is_err());
+
+    // Mismo layout en ambas versiones
+    let mut data = VaultInstruction::Resume.pack();
+    data[0] = INSTRUCTION_VERSION_V1;
+    assert!(matches!(
+        VaultInstruction::unpack(&data).unwrap(),
+        VaultInstruction::Resume
+    ));
+}
+
+#[tokio::test]
+async fn v1_initialize_vault_fills_new_fields_with_defaults() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("secure_vault", program_id, processor!(process_instruction));
+    let admin = Keypair::new();
+    program_test.add_account(admin.pubkey(), funded_account(INITIAL_LAMPORTS));
+    let audit_log = Pubkey::new_unique();
+    program_test.add_account(audit_log, audit_account(&program_id));
+    let context = program_test.start_with_context().await;
+    let (vault, _) = builders::find_vault_address(&program_id, &admin.pubkey());
+    let mut test = VaultTest {
+        context,
+        program_id,
+        admin,
+        vault,
+        audit_log,
+    };
+
+    let instruction = with_data(
+        builders::initialize_vault(&program_id, &test.admin.pubkey(), test_config()),
+        INSTRUCTION_VERSION_V1,
+        tag::INITIALIZE_VAULT,
+        &v1_config(),
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let config = test.vault_state().await.config;
+    assert_v1_fields(&config, &v1_config());
+    let defaults = SecurityConfig::default();
+    assert_eq!(config.quote_limits, defaults.quote_limits);
+    assert_eq!(config.limit_window, defaults.limit_window);
+    assert_eq!(
+        config.hourly_withdrawal_limit,
+        defaults.hourly_withdrawal_limit
+    );
+    assert_eq!(
+        config.withdrawal_delay_tiers,
+        defaults.withdrawal_delay_tiers
+    );
+    assert_eq!(config.circuit_breaker, defaults.circuit_breaker);
+}
+
+#[tokio::test]
+async fn v1_config_update_keeps_fields_added_in_v2() {
+    let mut test = setup(tiered_config()).await;
+
+    let instruction = with_data(
+        builders::update_security_config(
+            &test.program_id,
+            &test.admin.pubkey(),
+            &test.vault,
+            &test.audit_log,
+            test_config(),
+        ),
+        INSTRUCTION_VERSION_V1,
+        tag::UPDATE_SECURITY_CONFIG,
+        &v1_config(),
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let config = test.vault_state().await.config;
+    assert_v1_fields(&config, &v1_config());
+    assert_eq!(
+        config.withdrawal_delay_tiers,
+        tiered_config().withdrawal_delay_tiers
+    );
+    assert_eq!(config.hourly_withdrawal_limit, 2_000_000_000);
+}
+
+#[tokio::test]
+async fn v1_batch_config_update_keeps_fields_added_in_v2() {
+    let mut test = setup(tiered_config()).await;
+    let manager = Pubkey::new_unique();
+
+    // Cada operación del batch registra en su propia cuenta de auditoría
+    let second_audit_log = Keypair::new();
+    let lamports = test
+        .context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(AuditLog::MAX_SIZE);
+    let create = system_instruction::create_account(
+        &test.admin.pubkey(),
+        &second_audit_log.pubkey(),
+        lamports,
+        AuditLog::MAX_SIZE as u64,
+        &test.program_id,
+    );
+    test.process(&[create], &[&second_audit_log]).await.unwrap();
+
+    let operations = vec![
+        BatchOperationV1::AddManager {
+            new_manager: manager,
+        },
+        BatchOperationV1::UpdateSecurityConfig {
+            new_config: v1_config(),
+        },
+    ];
+    let audit_logs = [test.audit_log, second_audit_log.pubkey()];
+    let instruction = with_data(
+        builders::execute_batch(
+            &test.program_id,
+            &test.admin.pubkey(),
+            &test.vault,
+            &audit_logs,
+            Vec::new(),
+        ),
+        INSTRUCTION_VERSION_V1,
+        tag::EXECUTE_BATCH,
+        &operations,
+    );
+    test.process(&[instruction], &[]).await.unwrap();
+
+    let vault = test.vault_state().await;
+    assert!(vault.managers.contains(&manager));
+    assert_v1_fields(&vault.config, &v1_config());
+    assert_eq!(
+        vault.config.withdrawal_delay_tiers,
+        tiered_config().withdrawal_delay_tiers
+    );
+}
+
+#[test]
+fn v1_pending_operation_instructions_round_trip() {
+    let sign = VaultInstruction::SignPendingOperationV1 { operation_id: 7 };
+    let data = sign.pack();
+    assert_eq!(
+        data[..2],
+        [INSTRUCTION_VERSION_V1, tag::SIGN_PENDING_OPERATION]
+    );
+    assert!(matches!(
+        VaultInstruction::unpack(&data).unwrap(),
+        VaultInstruction::SignPendingOperationV1 { operation_id: 7 }
+    ));
+
+    // Deposit y ExecutePendingOperation conservan payload; solo se añadieron cuentas
+    for instruction in [
+        VaultInstruction::Deposit { amount: 42 },
+        VaultInstruction::ExecutePendingOperation { operation_id: 7 },
+    ] {
+        let mut data = instruction.pack();
+        data[0] = INSTRUCTION_VERSION_V1;
+        assert_eq!(
+            VaultInstruction::unpack(&data).unwrap().pack()[1..],
+            data[1..]
+        );
+    }
+}
+
+/// Pasar una instrucción al formato v1 conservando solo las primeras `accounts` cuentas
+fn as_v1(mut instruction: Instruction, accounts: usize) -> Instruction {
+    instruction.data[0] = INSTRUCTION_VERSION_V1;
+    instruction.accounts.truncate(accounts);
+    instruction
+}
+
+#[tokio::test]
+async fn v1_deposit_sign_and_execute_use_the_v1_account_layouts() {
+    let mut test = setup(test_config()).await;
+    let admin = test.admin.pubkey();
+    let deposit = builders::deposit(&test.program_id, &admin, &test.vault, &admin, DEPOSIT);
+    test.process(&[as_v1(deposit, 4)], &[]).await.unwrap();
+    assert_eq!(test.vault_state().await.total_balance, DEPOSIT);
+    let (ledger, _) =
+        builders::find_depositor_ledger_address(&test.program_id, &test.vault, &admin);
+    assert!(test.account(&ledger).await.is_none());
+
+    let manager = Keypair::new();
+    let add = builders::add_manager(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        &manager.pubkey(),
+    );
+    test.process(&[add], &[]).await.unwrap();
+    let recipient = Pubkey::new_unique();
+    let create = builders::create_time_lock_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        1,
+        OperationType::Withdraw,
+        AMOUNT,
+        &recipient,
+        DELAY,
+    );
+    // v1 no incluye el system program para crear la PDA de la operación
+    let result = test.process(&[as_v1(create.clone(), 4)], &[]).await;
+    assert_vault_error(result, VaultError::InvalidInstruction);
+    test.process(&[create], &[]).await.unwrap();
+
+    // v1: [firmante, operación pendiente, audit log]
+    let (pending, _) = builders::find_pending_operation_address(&test.program_id, &test.vault, 1);
+    let (program_id, audit_log) = (test.program_id, test.audit_log);
+    let sign = |signer: &Pubkey| Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(pending, false),
+            AccountMeta::new(audit_log, false),
+        ],
+        data: VaultInstruction::SignPendingOperationV1 { operation_id: 1 }.pack(),
+    };
+    let outsider = Keypair::new();
+    let instruction = sign(&outsider.pubkey());
+    let result = test.process(&[instruction], &[&outsider]).await;
+    assert_vault_error(result, VaultError::Unauthorized);
+    let instruction = sign(&manager.pubkey());
+    test.process(&[instruction], &[&manager]).await.unwrap();
+    let operation: PendingOperation = test.load(&pending).await;
+    assert_eq!(operation.received_signatures, vec![admin, manager.pubkey()]);
+    assert_eq!(operation.status, OperationStatus::Approved);
+    let audit = test.audit_entry().await;
+    assert_eq!(audit.performer, manager.pubkey());
+    assert_eq!(audit.correlation_id, Some(1));
+
+    // v1: sin el solicitante la operación queda ejecutada pero abierta
+    test.advance_clock(DELAY).await;
+    let execute = as_v1(
+        builders::execute_pending_operation(
+            &test.program_id,
+            &admin,
+            &test.vault,
+            &recipient,
+            &test.audit_log,
+            &admin,
+            1,
+        ),
+        5,
+    );
+    test.process(std::slice::from_ref(&execute), &[])
+        .await
+        .unwrap();
+    assert_eq!(test.account(&recipient).await.unwrap().lamports, AMOUNT);
+    assert_eq!(test.vault_state().await.total_balance, DEPOSIT - AMOUNT);
+    let operation: PendingOperation = test.load(&pending).await;
+    assert_eq!(operation.status, OperationStatus::Executed);
+    test.advance_clock(1).await;
+    assert_vault_error(
+        test.process(&[execute], &[]).await,
+        VaultError::OperationAlreadyExecuted,
+    );
+
+    // La renta se recupera al expirar
+    test.advance_clock(OPERATION_EXPIRY_HOURS * 3600).await;
+    let close = builders::close_expired_operation(
+        &test.program_id,
+        &admin,
+        &test.vault,
+        &test.audit_log,
+        &admin,
+        1,
+    );
+    test.process(&[close], &[]).await.unwrap();
+    assert!(test.account(&pending).await.is_none());
+    assert_eq!(
+        test.audit_entry().await.operation_status,
+        Some(OperationStatus::Executed)
+    );
+}
+
+#[tokio::test]
+async fn v1_deposit_requires_the_ledger_on_refundable_vaults() {
+    let mut config = test_config();
+    config.refundable_mode = true;
+    let mut test = setup(config).await;
+    let admin = test.admin.pubkey();
+
+    let deposit = builders::deposit(&test.program_id, &admin, &test.vault, &admin, DEPOSIT);
+    assert_vault_error(
+        test.process(&[as_v1(deposit.clone(), 4)], &[]).await,
+        VaultError::InvalidAccount,
+    );
+    test.process(&[as_v1(deposit, 5)], &[]).await.unwrap();
+    assert_eq!(test.vault_state().await.total_balance, DEPOSIT);
+}